@@ -1,26 +1,506 @@
-use std::{error::Error, time::Duration};
+use std::{
+    error::Error,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
-use sqlx::{Pool, postgres::PgPoolOptions};
+use log::LevelFilter;
+use sea_orm::{DatabaseConnection, SqlxPostgresConnector};
+use sqlx::{
+    ConnectOptions, Connection, PgConnection, Pool,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
 
-pub async fn get_db_sea_orm() -> Result<DatabaseConnection, Box<dyn Error>> {
-    let database_url = std::env::var("DATABASE_URL")?;
-    let mut db_options = ConnectOptions::new(database_url.clone());
-    db_options
-        .min_connections(1)
-        .max_connections(5)
-        .connect_timeout(Duration::from_secs(8));
-    let db = Database::connect(db_options).await?;
+// The keys of `ConnectionConfig`, durations are in seconds.
+const KEYS: &[&str] = &[
+    "DATABASE_URL",
+    "DATABASE_MIN_CONNECTIONS",
+    "DATABASE_MAX_CONNECTIONS",
+    "DATABASE_CONNECT_TIMEOUT",
+    "DATABASE_CONNECT_RETRIES",
+    "DATABASE_CONNECT_DEADLINE",
+    "DATABASE_IDLE_TIMEOUT",
+    "DATABASE_MAX_LIFETIME",
+    "DATABASE_STATEMENT_TIMEOUT",
+    "DATABASE_APPLICATION_NAME",
+    "DATABASE_LOG_LEVEL",
+];
+
+// How long to wait for the database, e.g. while Postgres of `docker-compose.yml` starts.
+// Each attempt connects and runs `SELECT 1`, the wait between attempts doubles from `backoff`
+// up to `max_backoff`, and no attempt starts after `deadline`.
+#[derive(Clone, Debug)]
+pub struct Retry {
+    pub attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    // The timeout of one attempt.
+    pub timeout: Duration,
+    pub deadline: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            attempts: 10,
+            backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(4),
+            timeout: Duration::from_secs(8),
+            deadline: Duration::from_secs(30),
+        }
+    }
+}
+
+// The options of the SeaORM and sqlx pools. `None` is no limit.
+#[derive(Clone, Debug)]
+pub struct ConnectionConfig {
+    // Note: the URL is a key as the others, so it may be in the file of `DATABASE_CONFIG`.
+    pub database_url: Option<String>,
+    pub min_connections: u32,
+    pub max_connections: u32,
+    // An idle connection is closed after `idle_timeout`, any connection after `max_lifetime`.
+    pub idle_timeout: Option<Duration>,
+    pub max_lifetime: Option<Duration>,
+    pub statement_timeout: Option<Duration>,
+    pub application_name: String,
+    // The level sqlx logs the statements at.
+    pub log_level: LevelFilter,
+    pub retry: Retry,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            database_url: None,
+            min_connections: 1,
+            max_connections: 5,
+            idle_timeout: Some(Duration::from_secs(600)),
+            max_lifetime: Some(Duration::from_secs(1800)),
+            statement_timeout: None,
+            application_name: "learning-sql-querying".to_string(),
+            log_level: LevelFilter::Debug,
+            retry: Retry::default(),
+        }
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, Box<dyn Error>> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{key} is `{value}`, which is not valid").into())
+}
+
+fn parse_duration(key: &str, value: &str) -> Result<Duration, Box<dyn Error>> {
+    let secs: f64 = parse(key, value)?;
+
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| format!("{key} is `{value}`, which is not a duration").into())
+}
+
+// Note: 0 is no limit, as for `statement_timeout` of Postgres.
+fn parse_limit(key: &str, value: &str) -> Result<Option<Duration>, Box<dyn Error>> {
+    Ok(Some(parse_duration(key, value)?).filter(|duration| !duration.is_zero()))
+}
+
+impl ConnectionConfig {
+    // The defaults, then the keys of the file in `DATABASE_CONFIG`, then the keys of the
+    // environment, which the binaries load from `.env`.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let path = std::env::var("DATABASE_CONFIG").ok();
+
+        Self::load_from(path.as_deref(), |key| std::env::var(key).ok())
+    }
+
+    // Note: the file and the environment are arguments, the tests do not change the
+    // environment of the process.
+    fn load_from(
+        path: Option<&str>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut config = Self::default();
+
+        if let Some(path) = path {
+            for item in dotenvy::from_path_iter(path)? {
+                let (key, value) = item?;
+
+                config
+                    .set(&key, &value)
+                    .map_err(|error| format!("{path}: {error}"))?;
+            }
+        }
+
+        for key in KEYS {
+            if let Some(value) = var(key) {
+                config.set(key, &value)?;
+            }
+        }
+
+        if config.max_connections == 0 || config.min_connections > config.max_connections {
+            return Err(format!(
+                "the pool is {} to {} connections, set DATABASE_MIN_CONNECTIONS and \
+                DATABASE_MAX_CONNECTIONS to at most the other and at least 1",
+                config.min_connections, config.max_connections
+            )
+            .into());
+        }
+
+        Ok(config)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        match key {
+            "DATABASE_URL" => self.database_url = Some(value.to_string()),
+            "DATABASE_MIN_CONNECTIONS" => self.min_connections = parse(key, value)?,
+            "DATABASE_MAX_CONNECTIONS" => self.max_connections = parse(key, value)?,
+            "DATABASE_CONNECT_TIMEOUT" => self.retry.timeout = parse_duration(key, value)?,
+            "DATABASE_CONNECT_RETRIES" => self.retry.attempts = parse(key, value)?,
+            "DATABASE_CONNECT_DEADLINE" => self.retry.deadline = parse_duration(key, value)?,
+            "DATABASE_IDLE_TIMEOUT" => self.idle_timeout = parse_limit(key, value)?,
+            "DATABASE_MAX_LIFETIME" => self.max_lifetime = parse_limit(key, value)?,
+            "DATABASE_STATEMENT_TIMEOUT" => self.statement_timeout = parse_limit(key, value)?,
+            "DATABASE_APPLICATION_NAME" => self.application_name = value.to_string(),
+            "DATABASE_LOG_LEVEL" => self.log_level = parse(key, value)?,
+            _ => {
+                return Err(format!("unknown key {key}, one of {}", KEYS.join(", ")).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // Note: `statement_timeout` is set when the connection starts, in milliseconds.
+    pub fn get_connect_options(&self, options: PgConnectOptions) -> PgConnectOptions {
+        let options = options
+            .application_name(&self.application_name)
+            .log_statements(self.log_level);
+
+        match self.statement_timeout {
+            Some(timeout) => options.options([("statement_timeout", timeout.as_millis())]),
+            None => options,
+        }
+    }
+
+    pub fn get_pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .min_connections(self.min_connections)
+            .max_connections(self.max_connections)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+}
+
+// Note: the password is always `***`, the options do not give it back.
+fn get_target(options: &PgConnectOptions) -> String {
+    format!(
+        "postgres://{}:***@{}:{}/{}",
+        options.get_username(),
+        options.get_host(),
+        options.get_port(),
+        options.get_database().unwrap_or_default()
+    )
+}
+
+// Note: the errors of a server that is not up yet, the same ones sqlx retries in its pools.
+// A wrong password or database fails at once.
+fn is_transient(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) => true,
+        sqlx::Error::Database(error) => {
+            matches!(error.code().as_deref(), Some("53300") | Some("57P03"))
+        }
+        _ => false,
+    }
+}
+
+async fn probe(options: &PgConnectOptions) -> Result<(), sqlx::Error> {
+    let mut conn = PgConnection::connect_with(options).await?;
+    sqlx::query("SELECT 1").execute(&mut conn).await?;
+
+    conn.close().await
+}
+
+// Waits until the database answers `SELECT 1`, then opens the pool.
+pub async fn connect_with_retry(
+    options: PgConnectOptions,
+    pool_options: PgPoolOptions,
+    retry: &Retry,
+) -> Result<Pool<sqlx::Postgres>, Box<dyn Error>> {
+    let started = Instant::now();
+    let mut backoff = retry.backoff;
+    let mut attempt = 1;
+
+    loop {
+        let timeout = retry
+            .timeout
+            .min(retry.deadline.saturating_sub(started.elapsed()));
+        let error = match tokio::time::timeout(timeout, probe(&options)).await {
+            Ok(Ok(())) => break,
+            Ok(Err(error)) => error,
+            Err(_) => sqlx::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("no answer in {:.1}s", timeout.as_secs_f64()),
+            )),
+        };
+        let retry_at = started.elapsed() + backoff;
+
+        if !is_transient(&error) || attempt >= retry.attempts || retry_at >= retry.deadline {
+            return Err(format!(
+                "could not connect to {} after {attempt} attempt(s) in {:.1}s: {error}",
+                get_target(&options),
+                started.elapsed().as_secs_f64()
+            )
+            .into());
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(retry.max_backoff);
+        attempt += 1;
+    }
+
+    let db = pool_options
+        .acquire_timeout(retry.timeout)
+        .connect_with(options.clone())
+        .await
+        .map_err(|error| format!("could not connect to {}: {error}", get_target(&options)))?;
 
     Ok(db)
 }
 
+// The pool of `DATABASE_URL`, or of another database (dataset) on the same server with the
+// same credentials, with the options of `ConnectionConfig::load`.
+async fn connect(dataset: Option<&str>) -> Result<Pool<sqlx::Postgres>, Box<dyn Error>> {
+    let config = ConnectionConfig::load()?;
+    let database_url = config
+        .database_url
+        .as_deref()
+        .ok_or("DATABASE_URL is not set, in the environment or the file of DATABASE_CONFIG")?;
+    let options = PgConnectOptions::from_str(database_url)?;
+    let options = match dataset {
+        Some(dataset) => options.database(dataset),
+        None => options,
+    };
+
+    connect_with_retry(
+        config.get_connect_options(options),
+        config.get_pool_options(),
+        &config.retry,
+    )
+    .await
+}
+
+// Note: SeaORM runs on a sqlx pool, its options are the ones of sqlx.
+pub async fn get_db_sea_orm() -> Result<DatabaseConnection, Box<dyn Error>> {
+    let pool = connect(None).await?;
+
+    Ok(SqlxPostgresConnector::from_sqlx_postgres_pool(pool))
+}
+
 pub async fn get_db_sqlx() -> Result<Pool<sqlx::Postgres>, Box<dyn Error>> {
-    let database_url = std::env::var("DATABASE_URL")?;
-    let db = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await?;
+    connect(None).await
+}
 
-    Ok(db)
+pub async fn get_db_sqlx_dataset(dataset: &str) -> Result<Pool<sqlx::Postgres>, Box<dyn Error>> {
+    connect(Some(dataset)).await
+}
+
+pub async fn get_db_sea_orm_dataset(dataset: &str) -> Result<DatabaseConnection, Box<dyn Error>> {
+    let pool = connect(Some(dataset)).await?;
+
+    Ok(SqlxPostgresConnector::from_sqlx_postgres_pool(pool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A config file of the test, removed when dropped.
+    struct ConfigFile(std::path::PathBuf);
+
+    impl ConfigFile {
+        fn new(name: &str, content: &str) -> Result<Self, Box<dyn Error>> {
+            let path = std::env::temp_dir().join(format!("{name}-{}.env", std::process::id()));
+            std::fs::write(&path, content)?;
+
+            Ok(Self(path))
+        }
+
+        fn path(&self) -> Option<&str> {
+            self.0.to_str()
+        }
+    }
+
+    impl Drop for ConfigFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn load(path: Option<&str>, vars: &[(&str, &str)]) -> Result<ConnectionConfig, Box<dyn Error>> {
+        ConnectionConfig::load_from(path, |key| {
+            vars.iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn set_parses_the_keys() -> Result<(), Box<dyn Error>> {
+        let mut config = ConnectionConfig::default();
+
+        config.set("DATABASE_MAX_CONNECTIONS", " 8 ")?;
+        config.set("DATABASE_CONNECT_TIMEOUT", "1.5")?;
+        config.set("DATABASE_IDLE_TIMEOUT", "0")?;
+        config.set("DATABASE_STATEMENT_TIMEOUT", "30")?;
+        config.set("DATABASE_LOG_LEVEL", "off")?;
+
+        assert_eq!(config.max_connections, 8);
+        assert_eq!(config.retry.timeout, Duration::from_millis(1500));
+        assert_eq!(config.idle_timeout, None);
+        assert_eq!(config.statement_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.log_level, LevelFilter::Off);
+
+        for (key, value) in [
+            ("DATABASE_MAX_CONNECTIONS", "eight"),
+            ("DATABASE_MIN_CONNECTIONS", "-1"),
+            ("DATABASE_CONNECT_RETRIES", "1.5"),
+            ("DATABASE_CONNECT_TIMEOUT", "-1"),
+            ("DATABASE_MAX_LIFETIME", "soon"),
+            ("DATABASE_LOG_LEVEL", "loud"),
+            ("DATABASE_PORT", "5432"),
+        ] {
+            assert!(config.set(key, value).is_err(), "{key}={value}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_rejects_the_bad_limits() -> Result<(), Box<dyn Error>> {
+        for vars in [
+            [
+                ("DATABASE_MIN_CONNECTIONS", "4"),
+                ("DATABASE_MAX_CONNECTIONS", "2"),
+            ],
+            [
+                ("DATABASE_MIN_CONNECTIONS", "0"),
+                ("DATABASE_MAX_CONNECTIONS", "0"),
+            ],
+            [
+                ("DATABASE_MIN_CONNECTIONS", "1"),
+                ("DATABASE_MAX_CONNECTIONS", "many"),
+            ],
+        ] {
+            assert!(load(None, &vars).is_err(), "{vars:?}");
+        }
+
+        let config = load(None, &[("DATABASE_MIN_CONNECTIONS", "0")])?;
+        assert_eq!((config.min_connections, config.max_connections), (0, 5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_reads_the_environment_over_the_file() -> Result<(), Box<dyn Error>> {
+        let file = ConfigFile::new(
+            "lib-core-config",
+            "DATABASE_URL=postgres://file@localhost/file\n\
+             DATABASE_MAX_CONNECTIONS=7\n\
+             DATABASE_APPLICATION_NAME=from-file\n",
+        )?;
+        let vars = [
+            ("DATABASE_URL", "postgres://env@localhost/env"),
+            ("DATABASE_MAX_CONNECTIONS", "3"),
+        ];
+
+        let config = load(file.path(), &vars)?;
+        assert_eq!(
+            config.database_url.as_deref(),
+            Some("postgres://env@localhost/env")
+        );
+        assert_eq!(config.max_connections, 3);
+        assert_eq!(config.application_name, "from-file");
+
+        let config = load(file.path(), &[])?;
+        assert_eq!(
+            config.database_url.as_deref(),
+            Some("postgres://file@localhost/file")
+        );
+        assert_eq!(config.max_connections, 7);
+
+        let bad = ConfigFile::new("lib-core-bad-config", "DATABASE_PORT=5432\n")?;
+        assert!(load(bad.path(), &vars).is_err());
+        assert!(load(Some("/nonexistent/database.env"), &vars).is_err());
+
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct CodeError(&'static str);
+
+    impl std::fmt::Display for CodeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "error {}", self.0)
+        }
+    }
+
+    impl Error for CodeError {}
+
+    impl sqlx::error::DatabaseError for CodeError {
+        fn message(&self) -> &str {
+            self.0
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.0.into())
+        }
+
+        fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    #[test]
+    fn is_transient_retries_a_server_that_is_not_up() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+
+        assert!(is_transient(&sqlx::Error::Io(io)));
+        assert!(is_transient(&sqlx::Error::Database(Box::new(CodeError(
+            "53300"
+        )))));
+        assert!(is_transient(&sqlx::Error::Database(Box::new(CodeError(
+            "57P03"
+        )))));
+        assert!(!is_transient(&sqlx::Error::Database(Box::new(CodeError(
+            "28P01"
+        )))));
+        assert!(!is_transient(&sqlx::Error::Database(Box::new(CodeError(
+            "3D000"
+        )))));
+        assert!(!is_transient(&sqlx::Error::PoolTimedOut));
+        assert!(!is_transient(&sqlx::Error::RowNotFound));
+    }
+
+    #[test]
+    fn get_target_hides_the_password() -> Result<(), Box<dyn Error>> {
+        let options = PgConnectOptions::from_str("postgres://learner:s3cret@db:5433/salesdb")?;
+        let target = get_target(&options);
+
+        assert_eq!(target, "postgres://learner:***@db:5433/salesdb");
+        assert!(!target.contains("s3cret"));
+
+        Ok(())
+    }
 }
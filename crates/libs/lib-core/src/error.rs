@@ -11,6 +11,12 @@ pub enum AppError {
     SeaOrm(#[from] sea_orm::DbErr),
     #[error("Polars error: {0}")]
     Polars(#[from] PolarsError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Assertion error: {0}")]
+    Assertion(String),
+    #[error("Transpile error: {0}")]
+    Transpile(String),
     #[error("Dynamic error: {0}")]
     Dynamic(#[from] Box<dyn std::error::Error>),
 }
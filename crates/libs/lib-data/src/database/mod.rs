@@ -1,4 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
+//! `SeaORM` Entity, @generated by `cargo run --bin codegen`
 
 pub mod prelude;
 
@@ -1,26 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
-
-// use sea_orm::entity::prelude::*;
-// use sqlx::prelude::FromRow;
-
-// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, FromRow)]
-// #[sea_orm(schema_name = "sales", table_name = "employees")]
-// pub struct Model {
-//     #[sea_orm(primary_key, auto_increment = false)]
-//     pub employeeid: i32,
-//     pub firstname: Option<String>,
-//     pub lastname: Option<String>,
-//     pub department: Option<String>,
-//     pub birthdate: Option<Date>,
-//     pub gender: Option<String>,
-//     pub salary: Option<i32>,
-//     pub managerid: Option<i32>,
-// }
-
-// #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-// pub enum Relation {}
-
-// impl ActiveModelBehavior for ActiveModel {}
+//! `SeaORM` Entity, @generated by `cargo run --bin codegen`
 
 use sea_orm::entity::prelude::*;
 use sqlx::prelude::FromRow;
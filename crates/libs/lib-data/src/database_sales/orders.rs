@@ -1,30 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
-
-// use sea_orm::entity::prelude::*;
-// use sqlx::prelude::FromRow;
-
-// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, FromRow)]
-// #[sea_orm(schema_name = "sales", table_name = "orders")]
-// pub struct Model {
-//     #[sea_orm(primary_key, auto_increment = false)]
-//     pub orderid: i32,
-//     pub productid: Option<i32>,
-//     pub customerid: Option<i32>,
-//     pub salespersonid: Option<i32>,
-//     pub orderdate: Option<Date>,
-//     pub shipdate: Option<Date>,
-//     pub orderstatus: Option<String>,
-//     pub shipaddress: Option<String>,
-//     pub billaddress: Option<String>,
-//     pub quantity: Option<i32>,
-//     pub sales: Option<i32>,
-//     pub creationtime: Option<DateTime>,
-// }
-
-// #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-// pub enum Relation {}
-
-// impl ActiveModelBehavior for ActiveModel {}
+//! `SeaORM` Entity, @generated by `cargo run --bin codegen`
 
 use sea_orm::entity::prelude::*;
 use sqlx::prelude::FromRow;
@@ -1,22 +1,4 @@
-//! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
-
-// use sea_orm::entity::prelude::*;
-// use sqlx::prelude::FromRow;
-
-// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, FromRow)]
-// #[sea_orm(schema_name = "sales", table_name = "products")]
-// pub struct Model {
-//     #[sea_orm(primary_key, auto_increment = false)]
-//     pub productid: i32,
-//     pub product: Option<String>,
-//     pub category: Option<String>,
-//     pub price: Option<i32>,
-// }
-
-// #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-// pub enum Relation {}
-
-// impl ActiveModelBehavior for ActiveModel {}
+//! `SeaORM` Entity, @generated by `cargo run --bin codegen`
 
 use sea_orm::entity::prelude::*;
 use sqlx::prelude::FromRow;
@@ -1,3 +1,9 @@
+mod registry;
+
+pub use registry::{
+    DisplayTable, Lesson, PolarsQuery, SeaOrmQuery, get_lesson, get_lessons, reset_sandboxes,
+};
+
 pub mod p_001;
 pub mod p_002;
 pub mod p_003;
@@ -46,3 +52,27 @@ pub mod p_045;
 pub mod p_046;
 pub mod p_047;
 pub mod p_048;
+pub mod p_049;
+pub mod p_050;
+pub mod p_051;
+pub mod p_052;
+pub mod p_053;
+pub mod p_054;
+pub mod p_055;
+pub mod p_056;
+pub mod p_057;
+pub mod p_058;
+pub mod p_059;
+pub mod p_060;
+pub mod p_061;
+pub mod p_062;
+pub mod p_063;
+pub mod p_064;
+pub mod p_065;
+pub mod p_066;
+pub mod p_067;
+pub mod p_068;
+pub mod p_069;
+pub mod p_070;
+pub mod p_071;
+pub mod p_072;
@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every customer with all of their columns.
+*/
+
 /*
 # QUERY:
 
@@ -31,14 +37,14 @@ shape: (5, 4)
 └─────┴────────────┴─────────┴───────┘
 */
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     customers::Entity::find()
         .all(db)
         .await
         .map_err(AppError::SeaOrm)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "SELECT * FROM customers;";
 
     sqlx::query_as::<_, customers::Model>(query)
@@ -47,10 +53,16 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<customers::Model
         .map_err(AppError::Sqlx)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df = df_customers.collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
-    let df = df_customers.collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_orders;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every order with all of their columns.
+*/
+
 /*
 # QUERY:
 
@@ -30,14 +36,14 @@ shape: (4, 4)
 └──────────┴─────────────┴────────────┴───────┘
 */
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<orders::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<orders::Model>> {
     orders::Entity::find()
         .all(db)
         .await
         .map_err(AppError::SeaOrm)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>> {
     let query = "SELECT * FROM orders;";
 
     sqlx::query_as::<_, orders::Model>(query)
@@ -46,10 +52,16 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>>
         .map_err(AppError::Sqlx)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df = df_orders.collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_orders = get_df_orders(&db_sea_orm).await?.lazy();
-    let df = df_orders.collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
@@ -11,6 +11,12 @@ use crate::utils::compare::compare_vecs;
 use crate::utils::database::get_database;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve the first name, the country and the score of each customer.
+*/
+
 /*
 # QUERY:
 
@@ -36,13 +42,13 @@ shape: (5, 3)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     first_name: String,
     country: Option<String>,
     score: Option<i32>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::FirstName)
@@ -58,7 +64,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT first_name, country, score
     FROM customers;
@@ -73,14 +79,20 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .select(&[col("first_name"), col("country"), col("score")])
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
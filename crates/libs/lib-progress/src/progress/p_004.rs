@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::debug::log_debug;
 use crate::utils::dataframe::get_df_customers;
 
+/*
+# TASK:
+
+Retrieve the customers whose score is not 0.
+*/
+
 /*
 # QUERY:
 
@@ -34,7 +40,7 @@ shape: (4, 4)
 
 const DEBUG: bool = false;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     let results = customers::Entity::find()
         .filter(customers::Column::Score.ne(0))
         .all(db)
@@ -46,7 +52,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "
     SELECT * FROM 
     customers 
@@ -62,14 +68,20 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .filter(col("score").neq(0))
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -12,6 +12,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve the first name and the country of the customers from Germany.
+*/
+
 /*
 # QUERY:
 
@@ -35,12 +41,12 @@ shape: (2, 2)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     first_name: String,
     country: Option<String>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::FirstName)
@@ -56,7 +62,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT first_name, country 
     FROM customers 
@@ -72,15 +78,21 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .select([col("first_name"), col("country")])
         .filter(col("country").eq(lit("Germany")))
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -10,6 +10,12 @@ use crate::utils::compare::compare_vecs;
 use crate::utils::database::get_database;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve all customers, the highest score first.
+*/
+
 /*
 # QUERY:
 
@@ -35,7 +41,7 @@ shape: (5, 4)
 
 const DEBUG: bool = false;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     let results = customers::Entity::find()
         .order_by_desc(customers::Column::Score)
         .all(db)
@@ -47,7 +53,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "
     SELECT *
     FROM customers
@@ -63,9 +69,8 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .sort(
             ["score"],
@@ -74,6 +79,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
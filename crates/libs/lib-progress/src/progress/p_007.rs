@@ -10,6 +10,13 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve all customers sorted by country alphabetically, then by the highest score within
+each country.
+*/
+
 /*
 # QUERY:
 
@@ -35,7 +42,7 @@ shape: (5, 4)
 
 const DEBUG: bool = false;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     let results = customers::Entity::find()
         .order_by_asc(customers::Column::Country)
         .order_by_desc(customers::Column::Score)
@@ -48,7 +55,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "
     SELECT *
     FROM customers
@@ -64,9 +71,8 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .sort(
             ["country", "score"],
@@ -75,6 +81,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -11,12 +11,19 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+For each country, compute the total score as `total_score` and the number of customers as
+`total_customers`.
+*/
+
 /*
 # QUERY:
 
 SELECT
     country,
-    SUM(score) AS total_score
+    SUM(score) AS total_score,
     COUNT(id) AS total_customers
 FROM customers
 GROUP BY country;
@@ -38,13 +45,13 @@ shape: (3, 3)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     country: Option<String>,
     total_score: i64,
     total_customers: i64,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::Country)
@@ -61,7 +68,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         country,
@@ -80,9 +87,8 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .group_by(["country"])
         .agg([
@@ -92,6 +98,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -11,6 +11,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Find the countries whose total score is above 800, with that total as `total_score`.
+*/
+
 /*
 # QUERY:
 
@@ -37,12 +43,12 @@ shape: (2, 2)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     country: Option<String>,
     total_score: i64,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::Country)
@@ -59,7 +65,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         country,
@@ -78,9 +84,8 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .group_by(["country"])
         .agg([col("score").sum().alias("total_score")])
@@ -88,6 +93,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
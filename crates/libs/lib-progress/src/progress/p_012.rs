@@ -13,12 +13,19 @@ use crate::utils::compare::compare_vecs;
 use crate::utils::database::get_database;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Leaving out the customers with a score of 0, find the countries whose average score is
+above 430, with the average as a double precision `avg_score`.
+*/
+
 /*
 # QUERY:
 
 SELECT
     country,
-    AVG(score) AS avg_score
+    AVG(score)::FLOAT8 AS avg_score
 FROM customers
 WHERE score != 0
 GROUP BY country
@@ -40,12 +47,12 @@ shape: (2, 2)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     country: Option<String>,
     avg_score: f64,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::Country)
@@ -63,7 +70,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         country,
@@ -83,9 +90,8 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .filter(col("score").neq(0))
         .group_by(["country"])
@@ -94,6 +100,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -10,6 +10,12 @@ use crate::utils::compare::compare_vecs;
 use crate::utils::database::get_database;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve the first 3 customers as they are stored in the table.
+*/
+
 /*
 # QUERY:
 
@@ -33,7 +39,7 @@ shape: (3, 4)
 
 const DEBUG: bool = false;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     let results = customers::Entity::find()
         .limit(3)
         .all(db)
@@ -45,7 +51,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "
     SELECT *
     FROM customers
@@ -61,10 +67,16 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     Ok(results)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df = df_customers.limit(3).collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
-    let df = df_customers.limit(3).collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
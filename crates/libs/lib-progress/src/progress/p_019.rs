@@ -5,11 +5,17 @@ use sqlx::{Pool, Postgres};
 use lib_core::error::{AppError, AppResult};
 use lib_data::database::customers;
 
-use crate::utils::dataframe::get_df_customers;
 use crate::utils::compare::compare_vecs;
 use crate::utils::database::get_database;
+use crate::utils::dataframe::scan_table;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve the customers with a score of 500 or more.
+*/
+
 /*
 # QUERY:
 
@@ -33,7 +39,7 @@ shape: (3, 4)
 
 const DEBUG: bool = false;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Model>> {
     let results = customers::Entity::find()
         .filter(customers::Column::Score.gte(500))
         .all(db)
@@ -45,7 +51,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<customers::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     let query = "
     SELECT *
     FROM customers
@@ -61,14 +67,20 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<customers::Model>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = scan_table(db, "customers").await?;
     let df = df_customers
         .filter(col("score").gt_eq(500))
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
@@ -10,6 +10,13 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::{get_df_customers, get_df_orders};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve all customers with the id and the sales of their orders, keeping the customers
+without orders.
+*/
+
 /*
 # QUERY:
 
@@ -41,7 +48,7 @@ shape: (5, 4)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     // customers
     pub id: i32,
     pub first_name: String,
@@ -50,7 +57,7 @@ struct Customer {
     pub sales: Option<i32>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     // Note: Entities have no relation. No-relation code solution.
     let results = customers::Entity::find()
         .select_only()
@@ -75,7 +82,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         c.id,
@@ -96,10 +103,9 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
-    let df_orders = get_df_orders(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_orders = get_df_orders(db).await?.lazy();
 
     // NOTE, joins in polars only keeps comparison key (id & customer_id) column of the chosen table (in this case LEFT -> customers)
     let df = df_customers
@@ -113,6 +119,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     /*
     shape: (5, 7)
     ┌─────┬────────────┬─────────┬───────┬──────────┬────────────┬───────┐
@@ -10,6 +10,13 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::{get_df_customers, get_df_orders};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve all orders with the id and the first name of their customer, keeping the orders
+without a known customer.
+*/
+
 /*
 # QUERY:
 
@@ -40,7 +47,7 @@ shape: (4, 4)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     // customers
     pub id: Option<i32>,
     pub first_name: Option<String>,
@@ -49,7 +56,7 @@ struct Customer {
     pub sales: Option<i32>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     // Note: Entities have no relation. No-relation code solution.
     let results = customers::Entity::find()
         .select_only()
@@ -74,7 +81,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         c.id,
@@ -95,15 +102,13 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
     // RIGHT JOIN via LEFT JOIN
-    let df_customers = get_df_customers(&db_sea_orm)
+    let df_customers = get_df_customers(db)
         .await?
         .lazy()
         .with_column(col("id").alias("join_id"));
-    let df_orders = get_df_orders(&db_sea_orm)
+    let df_orders = get_df_orders(db)
         .await?
         .lazy()
         .with_column(col("customer_id").alias("join_customer_id"));
@@ -120,11 +125,11 @@ pub async fn display_table() -> AppResult<()> {
         .map_err(AppError::Polars)?;
 
     // RIGHT JOIN
-    let df_customers = get_df_customers(&db_sea_orm)
+    let df_customers = get_df_customers(db)
         .await?
         .lazy()
         .with_column(col("id").alias("join_id"));
-    let df_orders = get_df_orders(&db_sea_orm)
+    let df_orders = get_df_orders(db)
         .await?
         .lazy()
         .with_column(col("customer_id").alias("join_customer_id"));
@@ -135,23 +140,17 @@ pub async fn display_table() -> AppResult<()> {
             [col("join_customer_id")],
             JoinArgs::new(JoinType::Right),
         )
-        // .select([col("id"), col("first_name"), col("order_id"), col("sales")])
+        .select([col("id"), col("first_name"), col("order_id"), col("sales")])
         .collect()
         .map_err(AppError::Polars)?;
 
-    /*
-    shape: (4, 9)
-    ┌──────┬────────────┬─────────┬───────┬───┬─────────────┬────────────┬───────┬──────────────────┐
-    │ id   ┆ first_name ┆ country ┆ score ┆ … ┆ customer_id ┆ order_date ┆ sales ┆ join_customer_id │
-    │ ---  ┆ ---        ┆ ---     ┆ ---   ┆   ┆ ---         ┆ ---        ┆ ---   ┆ ---              │
-    │ i32  ┆ str        ┆ str     ┆ i32   ┆   ┆ i32         ┆ date       ┆ i32   ┆ i32              │
-    ╞══════╪════════════╪═════════╪═══════╪═══╪═════════════╪════════════╪═══════╪══════════════════╡
-    │ 1    ┆ Maria      ┆ Germany ┆ 350   ┆ … ┆ 1           ┆ 2021-01-11 ┆ 35    ┆ 1                │
-    │ 2    ┆  John      ┆ USA     ┆ 900   ┆ … ┆ 2           ┆ 2021-04-05 ┆ 15    ┆ 2                │
-    │ 3    ┆ Georg      ┆ UK      ┆ 750   ┆ … ┆ 3           ┆ 2021-06-18 ┆ 20    ┆ 3                │
-    │ null ┆ null       ┆ null    ┆ null  ┆ … ┆ 6           ┆ 2021-08-31 ┆ 10    ┆ 6                │
-    └──────┴────────────┴─────────┴───────┴───┴─────────────┴────────────┴───────┴──────────────────┘
-    */
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::{get_df_customers, get_df_orders};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve all customers and all orders, matched where possible and kept where not.
+*/
+
 /*
 # QUERY:
 
@@ -42,7 +48,7 @@ shape: (6, 4)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     // customers
     pub id: Option<i32>,
     pub first_name: Option<String>,
@@ -51,7 +57,7 @@ struct Customer {
     pub sales: Option<i32>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     // Note: Entities have no relation. No-relation code solution.
     let results = customers::Entity::find()
         .select_only()
@@ -76,7 +82,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         c.id,
@@ -97,13 +103,12 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm)
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db)
         .await?
         .lazy()
         .with_column(col("id").alias("join_id"));
-    let df_orders = get_df_orders(&db_sea_orm)
+    let df_orders = get_df_orders(db)
         .await?
         .lazy()
         .with_column(col("customer_id").alias("join_customer_id"));
@@ -118,6 +123,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     /*
     shape: (6, 10)
     ┌──────┬────────────┬─────────┬───────┬───┬─────────────┬────────────┬───────┬──────────────────┐
@@ -12,6 +12,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::{get_df_customers, get_df_orders};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve the orders whose customer is not in the customers table.
+*/
+
 /*
 # QUERY:
 
@@ -23,7 +29,7 @@ SELECT
 FROM customers AS c
 RIGHT JOIN orders AS o
 ON c.id = o.customer_id
-WHERE o.customer_id IS NULL;
+WHERE c.id IS NULL;
 */
 
 /*
@@ -40,7 +46,7 @@ shape: (1, 4)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, FromRow, FromQueryResult)]
-struct Customer {
+pub struct Customer {
     // customers
     pub id: Option<i32>,
     pub first_name: Option<String>,
@@ -49,7 +55,7 @@ struct Customer {
     pub sales: Option<i32>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     // Note: Entities have no relation. No-relation code solution.
     let results = customers::Entity::find()
         .select_only()
@@ -75,7 +81,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
+pub async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     let query = "
     SELECT
         c.id,
@@ -97,13 +103,12 @@ async fn sqlx_query(db: &Pool<Postgres>) -> AppResult<Vec<Customer>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm)
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db)
         .await?
         .lazy()
         .with_column(col("id").alias("join_id"));
-    let df_orders = get_df_orders(&db_sea_orm)
+    let df_orders = get_df_orders(db)
         .await?
         .lazy()
         .with_column(col("customer_id").alias("join_customer_id"));
@@ -120,6 +125,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     /*
     shape: (4, 9)
     ┌──────┬────────────┬─────────┬───────┬───┬─────────────┬────────────┬───────┬──────────────────┐
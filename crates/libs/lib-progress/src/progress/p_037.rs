@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::get_df_employees;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every employee of the sales schema.
+*/
+
 /*
 # QUERY:
 
@@ -34,7 +40,7 @@ shape: (5, 8)
 
 const DEBUG: bool = true;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<employees::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<employees::Model>> {
     let results = employees::Entity::find()
         .all(db)
         .await
@@ -45,7 +51,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<employees::Mode
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<employees::Model>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<employees::Model>> {
     let query = "
     SELECT * 
     FROM sales.employees;
@@ -60,10 +66,16 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<employees::Model
     Ok(results)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df = df_employees.collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_employees = get_df_employees(&db_sea_orm).await?.lazy();
-    let df = df_employees.collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::get_df_orders;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every order of the sales schema.
+*/
+
 /*
 # QUERY:
 
@@ -39,7 +45,7 @@ shape: (10, 12)
 
 const DEBUG: bool = true;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<orders::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<orders::Model>> {
     let results = orders::Entity::find()
         .all(db)
         .await
@@ -50,7 +56,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<orders::Model>>
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>> {
     let query = "
     SELECT * 
     FROM sales.orders;
@@ -65,10 +71,16 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<orders::Model>>
     Ok(results)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df = df_orders.collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_orders = get_df_orders(&db_sea_orm).await?.lazy();
-    let df = df_orders.collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
@@ -10,6 +10,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::get_df_products;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every product of the sales schema.
+*/
+
 /*
 # QUERY:
 
@@ -34,7 +40,7 @@ shape: (5, 4)
 
 const DEBUG: bool = true;
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<products::Model>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<products::Model>> {
     let results = products::Entity::find()
         .all(db)
         .await
@@ -45,7 +51,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<products::Model
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<products::Model>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<products::Model>> {
     let query = "
     SELECT * 
     FROM sales.products;
@@ -60,10 +66,16 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<products::Model>
     Ok(results)
 }
 
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_products = get_df_products(db).await?.lazy();
+    let df = df_products.collect().map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
 pub async fn display_table() -> AppResult<()> {
     let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_products = get_df_products(&db_sea_orm).await?.lazy();
-    let df = df_products.collect().map_err(AppError::Polars)?;
+    let df = polars_query(&db_sea_orm).await?;
 
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
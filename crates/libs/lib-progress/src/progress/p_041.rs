@@ -13,6 +13,13 @@ use crate::utils::dataframe::sales::{
 };
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Retrieve every order with its sales, the name of its customer, its product and price, and
+the name of its salesperson, keeping the orders with missing details.
+*/
+
 /*
 # QUERY:
 
@@ -37,27 +44,27 @@ ON o.salespersonid = e.employeeid;
 /*
 shape: (10, 8)
 ┌──────────┬───────┬────────────────────┬───────────────────┬─────────┬───────┬────────────────────┬───────────────────┐
-│ order_id ┆ sales ┆ customer_firstname ┆ customer_lastname ┆ product ┆ price ┆ employee_firstname ┆ employee_lastname │
+│ orderid  ┆ sales ┆ customer_firstname ┆ customer_lastname ┆ product ┆ price ┆ employee_firstname ┆ employee_lastname │
 │ ---      ┆ ---   ┆ ---                ┆ ---               ┆ ---     ┆ ---   ┆ ---                ┆ ---               │
 │ i32      ┆ i32   ┆ str                ┆ str               ┆ str     ┆ i32   ┆ str                ┆ str               │
 ╞══════════╪═══════╪════════════════════╪═══════════════════╪═════════╪═══════╪════════════════════╪═══════════════════╡
-│ 1        ┆ 10    ┆ Kevin              ┆ Brown             ┆ Bottle  ┆ 10    ┆ Kevin              ┆ Brown             │
+│ 1        ┆ 10    ┆ Kevin              ┆ Brown             ┆ Bottle  ┆ 10    ┆ Mary               ┆ null              │
 │ 2        ┆ 15    ┆ Mary               ┆ null              ┆ Tire    ┆ 15    ┆ Mary               ┆ null              │
-│ 3        ┆ 20    ┆ Jossef             ┆ Goldberg          ┆ Bottle  ┆ 10    ┆ Jossef             ┆ Goldberg          │
-│ 4        ┆ 60    ┆ Jossef             ┆ Goldberg          ┆ Gloves  ┆ 30    ┆ Jossef             ┆ Goldberg          │
-│ 5        ┆ 25    ┆ Kevin              ┆ Brown             ┆ Caps    ┆ 25    ┆ Kevin              ┆ Brown             │
-│ 6        ┆ 50    ┆ Mary               ┆ null              ┆ Caps    ┆ 25    ┆ Mary               ┆ null              │
-│ 7        ┆ 30    ┆ Jossef             ┆ Goldberg          ┆ Tire    ┆ 15    ┆ Jossef             ┆ Goldberg          │
-│ 8        ┆ 90    ┆ Mark               ┆ Schwarz           ┆ Bottle  ┆ 10    ┆ Mark               ┆ Schwarz           │
-│ 9        ┆ 20    ┆ Kevin              ┆ Brown             ┆ Bottle  ┆ 10    ┆ Kevin              ┆ Brown             │
-│ 10       ┆ 60    ┆ Mary               ┆ null              ┆ Tire    ┆ 15    ┆ Mary               ┆ null              │
+│ 3        ┆ 20    ┆ Jossef             ┆ Goldberg          ┆ Bottle  ┆ 10    ┆ Carol              ┆ Baker             │
+│ 4        ┆ 60    ┆ Jossef             ┆ Goldberg          ┆ Gloves  ┆ 30    ┆ Mary               ┆ null              │
+│ 5        ┆ 25    ┆ Kevin              ┆ Brown             ┆ Caps    ┆ 25    ┆ Carol              ┆ Baker             │
+│ 6        ┆ 50    ┆ Mary               ┆ null              ┆ Caps    ┆ 25    ┆ Carol              ┆ Baker             │
+│ 7        ┆ 30    ┆ Jossef             ┆ Goldberg          ┆ Tire    ┆ 15    ┆ Frank              ┆ Lee               │
+│ 8        ┆ 90    ┆ Mark               ┆ Schwarz           ┆ Bottle  ┆ 10    ┆ Mary               ┆ null              │
+│ 9        ┆ 20    ┆ Kevin              ┆ Brown             ┆ Bottle  ┆ 10    ┆ Mary               ┆ null              │
+│ 10       ┆ 60    ┆ Mary               ┆ null              ┆ Tire    ┆ 15    ┆ Carol              ┆ Baker             │
 └──────────┴───────┴────────────────────┴───────────────────┴─────────┴───────┴────────────────────┴───────────────────┘
 */
 
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow)]
-struct OrderDetails {
+pub struct OrderDetails {
     orderid: i32,
     sales: i32,
     customer_firstname: Option<String>,
@@ -68,7 +75,7 @@ struct OrderDetails {
     employee_lastname: Option<String>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderDetails>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderDetails>> {
     let results = orders::Entity::find()
         .select_only()
         .column(orders::Column::Orderid)
@@ -101,7 +108,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderDetails>>
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderDetails>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderDetails>> {
     let query = "
     SELECT
         o.orderid,
@@ -130,12 +137,11 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderDetails>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_orders = get_df_orders(&db_sea_orm).await?.lazy();
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
-    let df_products = get_df_products(&db_sea_orm).await?.lazy();
-    let df_employees = get_df_employees(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df_employees = get_df_employees(db).await?.lazy();
     let df = df_orders
         .join(
             df_customers,
@@ -156,18 +162,26 @@ pub async fn display_table() -> AppResult<()> {
             JoinType::Left.into(),
         )
         .select(&[
-            col("orderid").alias("order_id"),
+            col("orderid"),
             col("sales"),
             col("firstname").alias("customer_firstname"),
             col("lastname").alias("customer_lastname"),
             col("product"),
             col("price"),
-            col("firstname").alias("employee_firstname"),
-            col("lastname").alias("employee_lastname"),
+            // Note: the clashing columns of the employees are suffixed `_right`.
+            col("firstname_right").alias("employee_firstname"),
+            col("lastname_right").alias("employee_lastname"),
         ])
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     if compare_vecs(
         &sea_orm_query(&db_sea_orm).await?,
         &sqlx_query(&db_sqlx).await?,
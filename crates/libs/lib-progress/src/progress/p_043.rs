@@ -11,6 +11,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::{get_df_customers, get_df_employees};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+List the first and last names of the employees and the customers, duplicates included.
+*/
+
 /*
 # QUERY:
 
@@ -48,12 +54,12 @@ shape: (10, 2)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
-struct Person {
+pub struct Person {
     firstname: Option<String>,
     lastname: Option<String>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     // Note: sea-orm doesn't have a union API by the time of code creation.
     let mut results = Vec::new();
     let employees_query = employees::Entity::find()
@@ -79,7 +85,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     let query = "
     SELECT
         firstname,
@@ -101,10 +107,9 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_employees = get_df_employees(&db_sea_orm).await?.lazy();
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = concat(
         &[
             df_employees.select([col("firstname"), col("lastname")]),
@@ -124,6 +129,13 @@ pub async fn display_table() -> AppResult<()> {
     .collect()
     .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     // Note: without ORDER BY, the row order in both results will vary
     if compare_vecs_unordered(
         &sea_orm_query(&db_sea_orm).await?,
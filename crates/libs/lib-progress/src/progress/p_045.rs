@@ -11,6 +11,12 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::{get_df_customers, get_df_employees};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+List the people who are both employees and customers, by first and last name.
+*/
+
 /*
 # QUERY:
 
@@ -26,25 +32,26 @@ FROM sales.customers;
 */
 
 /*
-shape: (1, 2)
+shape: (2, 2)
 ┌───────────┬──────────┐
 │ firstname ┆ lastname │
 │ ---       ┆ ---      │
 │ str       ┆ str      │
 ╞═══════════╪══════════╡
 │ Kevin     ┆ Brown    │
+│ Mary      ┆ null     │
 └───────────┴──────────┘
 */
 
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
-struct Person {
+pub struct Person {
     firstname: Option<String>,
     lastname: Option<String>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     // Note: sea-orm doesn't have a union API by the time of code creation.
     let mut results = Vec::new();
     let employees_query = employees::Entity::find()
@@ -78,7 +85,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     let query = "
     SELECT
         firstname,
@@ -100,30 +107,32 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_employees = get_df_employees(&db_sea_orm).await?.lazy();
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_employees
         .join(
             df_customers,
             [col("firstname"), col("lastname")],
             [col("firstname"), col("lastname")],
-            JoinType::Inner.into(),
+            // Note: INTERSECT compares rows as DISTINCT does, NULL equals NULL.
+            JoinArgs {
+                nulls_equal: true,
+                ..JoinArgs::new(JoinType::Inner)
+            },
         )
         .select([col("firstname"), col("lastname")])
-        .collect()
-        .map_err(AppError::Polars)?
-        .lazy()
-        .filter(
-            col("firstname")
-                .is_not_null()
-                .and(col("lastname").is_not_null()),
-        )
         .unique(None, UniqueKeepStrategy::First)
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     // Note: without ORDER BY, the row order in both results will vary
     if compare_vecs_unordered(
         &sea_orm_query(&db_sea_orm).await?,
@@ -14,6 +14,13 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::{get_df_orders, get_df_ordersarchive};
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Combine the current and the archived orders without duplicates, tagging each row with the
+table it comes from, 'Orders' or 'OrdersArchive', as `source_table`.
+*/
+
 /*
 # QUERY:
 
@@ -74,7 +81,7 @@ shape: (20, 13)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
-struct Order {
+pub struct Order {
     source_table: String,
     orderid: Option<i32>,
     productid: Option<i32>,
@@ -90,7 +97,7 @@ struct Order {
     creationtime: Option<NaiveDateTime>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Order>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Order>> {
     let mut results = Vec::new();
     let orders_query = orders::Entity::find()
         .select_only()
@@ -144,7 +151,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Order>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Order>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Order>> {
     let query = "
     SELECT
         'Orders' AS source_table,
@@ -188,9 +195,8 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Order>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_orders = get_df_orders(&db_sea_orm)
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db)
         .await?
         .lazy()
         .with_column(lit("Orders").alias("source_table"))
@@ -209,7 +215,7 @@ pub async fn display_table() -> AppResult<()> {
             col("sales"),
             col("creationtime"),
         ]);
-    let df_ordersarchive = get_df_ordersarchive(&db_sea_orm)
+    let df_ordersarchive = get_df_ordersarchive(db)
         .await?
         .lazy()
         .with_column(lit("OrdersArchive").alias("source_table"))
@@ -243,6 +249,13 @@ pub async fn display_table() -> AppResult<()> {
     .collect()
     .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     // Note: without ORDER BY, the row order in both results will vary
     if compare_vecs_unordered(
         &sea_orm_query(&db_sea_orm).await?,
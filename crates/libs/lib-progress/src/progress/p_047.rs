@@ -13,14 +13,21 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Show the first name and the country of each customer, and both joined by a '-' as
+`name_country`.
+*/
+
 /*
 # QUERY:
 
 SELECT
-    first_name,
+    firstname,
     country,
-    CONCAT(first_name, '-', country) AS name_country
-FROM customers;
+    CONCAT(firstname, '-', country) AS name_country
+FROM sales.customers;
 */
 
 /*
@@ -41,13 +48,13 @@ shape: (5, 3)
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
-struct Person {
+pub struct Person {
     firstname: Option<String>,
     country: Option<String>,
     name_country: String,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::Firstname)
@@ -67,7 +74,7 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     let query = "
     SELECT
         firstname,
@@ -85,9 +92,8 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .select([
             col("firstname"),
@@ -97,6 +103,13 @@ pub async fn display_table() -> AppResult<()> {
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     // Note: without ORDER BY, the row order in both results will vary
     if compare_vecs_unordered(
         &sea_orm_query(&db_sea_orm).await?,
@@ -1,6 +1,5 @@
 use polars::prelude::*;
-use sea_orm::sea_query::Expr as SeaExpr;
-use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Expr as SeaExpr, Func};
 use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect};
 use sqlx::Pool;
 use sqlx::prelude::FromRow;
@@ -13,50 +12,58 @@ use crate::utils::database::get_database;
 use crate::utils::dataframe::sales::get_df_customers;
 use crate::utils::debug::log_debug;
 
+/*
+# TASK:
+
+Show the first name of each customer, in lower case as `lower_name` and in upper case as
+`upper_name`.
+*/
+
 /*
 # QUERY:
 
 SELECT
-    first_name,
-    LOWER(first_name) AS lower_name,
-    UPPER(first_name) AS upper_name
-FROM customers;
+    firstname,
+    LOWER(firstname) AS lower_name,
+    UPPER(firstname) AS upper_name
+FROM sales.customers;
 */
 
 /*
 shape: (5, 3)
-┌───────────┬─────────┬────────────────┐
-│ firstname ┆ country ┆ name_country   │
-│ ---       ┆ ---     ┆ ---            │
-│ str       ┆ str     ┆ str            │
-╞═══════════╪═════════╪════════════════╡
-│ Jossef    ┆ Germany ┆ Jossef-Germany │
-│ Kevin     ┆ USA     ┆ Kevin-USA      │
-│ Mary      ┆ USA     ┆ Mary-USA       │
-│ Mark      ┆ Germany ┆ Mark-Germany   │
-│ Anna      ┆ USA     ┆ Anna-USA       │
-└───────────┴─────────┴────────────────┘
+┌───────────┬────────────┬────────────┐
+│ firstname ┆ lower_name ┆ upper_name │
+│ ---       ┆ ---        ┆ ---        │
+│ str       ┆ str        ┆ str        │
+╞═══════════╪════════════╪════════════╡
+│ Jossef    ┆ jossef     ┆ JOSSEF     │
+│ Kevin     ┆ kevin      ┆ KEVIN      │
+│ Mary      ┆ mary       ┆ MARY       │
+│ Mark      ┆ mark       ┆ MARK       │
+│ Anna      ┆ anna       ┆ ANNA       │
+└───────────┴────────────┴────────────┘
 */
 
 const DEBUG: bool = false;
 
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
-struct Person {
+pub struct Person {
     firstname: Option<String>,
-    lower_name: String,
-    upper_name: String,
+    lower_name: Option<String>,
+    upper_name: Option<String>,
 }
 
-async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     let results = customers::Entity::find()
         .select_only()
         .column(customers::Column::Firstname)
-        .column(customers::Column::Country)
         .column_as(
-            SeaExpr::col(customers::Column::Firstname)
-                .concat(SeaExpr::val("-")) // literal from sea_query
-                .concat(SeaExpr::col(customers::Column::Country)),
-            "name_country",
+            SeaExpr::expr(Func::lower(SeaExpr::col(customers::Column::Firstname))),
+            "lower_name",
+        )
+        .column_as(
+            SeaExpr::expr(Func::upper(SeaExpr::col(customers::Column::Firstname))),
+            "upper_name",
         )
         .into_model::<Person>()
         .all(db)
@@ -67,12 +74,12 @@ async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     let query = "
     SELECT
         firstname,
-        country,
-        CONCAT(firstname, '-', country) AS name_country
+        LOWER(firstname) AS lower_name,
+        UPPER(firstname) AS upper_name
     FROM sales.customers;
     ";
     let results = sqlx::query_as::<_, Person>(query)
@@ -85,18 +92,24 @@ async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<Person>> {
     Ok(results)
 }
 
-pub async fn display_table() -> AppResult<()> {
-    let (db_sea_orm, db_sqlx) = get_database().await?;
-    let df_customers = get_df_customers(&db_sea_orm).await?.lazy();
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
     let df = df_customers
         .select([
             col("firstname"),
-            col("country"),
-            (col("firstname") + lit("-") + col("country")).alias("name_country"),
+            col("firstname").str().to_lowercase().alias("lower_name"),
+            col("firstname").str().to_uppercase().alias("upper_name"),
         ])
         .collect()
         .map_err(AppError::Polars)?;
 
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
     // Note: without ORDER BY, the row order in both results will vary
     if compare_vecs_unordered(
         &sea_orm_query(&db_sea_orm).await?,
@@ -0,0 +1,175 @@
+use polars::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{customers, orders};
+
+use crate::utils::compare::{compare_dfs_unordered, compare_vecs_unordered};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::{get_df_customers, get_df_orders};
+use crate::utils::debug::log_debug;
+use crate::utils::grouping::{cube, grouping_sets};
+
+/*
+# TASK:
+
+Total the sales for every combination of customer country and order status, with the
+subtotals of each column alone and a grand total, and flag the rolled-up columns of each
+row.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    c.country,
+    o.orderstatus,
+    SUM(o.sales) AS total_sales,
+    GROUPING(c.country) AS grouping_country,
+    GROUPING(o.orderstatus) AS grouping_orderstatus
+FROM sales.orders AS o
+LEFT JOIN sales.customers AS c
+ON o.customerid = c.customerid
+GROUP BY CUBE(c.country, o.orderstatus);
+*/
+
+/*
+shape: (9, 5)
+┌─────────┬─────────────┬─────────────┬──────────────────┬──────────────────────┐
+│ country ┆ orderstatus ┆ total_sales ┆ grouping_country ┆ grouping_orderstatus │
+│ ---     ┆ ---         ┆ ---         ┆ ---              ┆ ---                  │
+│ str     ┆ str         ┆ i64         ┆ i32              ┆ i32                  │
+╞═════════╪═════════════╪═════════════╪══════════════════╪══════════════════════╡
+│ USA     ┆ Delivered   ┆ 85          ┆ 0                ┆ 0                    │
+│ USA     ┆ Shipped     ┆ 95          ┆ 0                ┆ 0                    │
+│ Germany ┆ Delivered   ┆ 50          ┆ 0                ┆ 0                    │
+│ Germany ┆ Shipped     ┆ 150         ┆ 0                ┆ 0                    │
+│ Germany ┆ null        ┆ 200         ┆ 0                ┆ 1                    │
+│ USA     ┆ null        ┆ 180         ┆ 0                ┆ 1                    │
+│ null    ┆ Delivered   ┆ 135         ┆ 1                ┆ 0                    │
+│ null    ┆ Shipped     ┆ 245         ┆ 1                ┆ 0                    │
+│ null    ┆ null        ┆ 380         ┆ 1                ┆ 1                    │
+└─────────┴─────────────┴─────────────┴──────────────────┴──────────────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct SalesCube {
+    country: Option<String>,
+    orderstatus: Option<String>,
+    total_sales: Option<i64>,
+    grouping_country: i32,
+    grouping_orderstatus: i32,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<SalesCube>> {
+    let results = orders::Entity::find()
+        .select_only()
+        .column(customers::Column::Country)
+        .column(orders::Column::Orderstatus)
+        .column_as(Expr::cust("SUM(sales)"), "total_sales")
+        .column_as(Expr::cust("GROUPING(country)"), "grouping_country")
+        .column_as(Expr::cust("GROUPING(orderstatus)"), "grouping_orderstatus")
+        .join(
+            sea_orm::JoinType::LeftJoin,
+            orders::Relation::Customers.def(),
+        )
+        // Note: sea-orm has no CUBE builder, the grouping is passed as a custom expression.
+        .group_by(Expr::cust("CUBE(country, orderstatus)"))
+        .into_model::<SalesCube>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<SalesCube>> {
+    let query = "
+    SELECT
+        c.country,
+        o.orderstatus,
+        SUM(o.sales) AS total_sales,
+        GROUPING(c.country) AS grouping_country,
+        GROUPING(o.orderstatus) AS grouping_orderstatus
+    FROM sales.orders AS o
+    LEFT JOIN sales.customers AS c
+    ON o.customerid = c.customerid
+    GROUP BY CUBE(c.country, o.orderstatus);
+    ";
+    let results = sqlx::query_as::<_, SalesCube>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[SalesCube]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let countries: Vec<Option<String>> = iter.clone().map(|r| r.country.clone()).collect();
+    let orderstatuses: Vec<Option<String>> = iter.clone().map(|r| r.orderstatus.clone()).collect();
+    let total_sales: Vec<Option<i64>> = iter.clone().map(|r| r.total_sales).collect();
+    let grouping_countries: Vec<i32> = iter.clone().map(|r| r.grouping_country).collect();
+    let grouping_orderstatuses: Vec<i32> = iter.clone().map(|r| r.grouping_orderstatus).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("country".into(), countries).into(),
+        Series::new("orderstatus".into(), orderstatuses).into(),
+        Series::new("total_sales".into(), total_sales).into(),
+        Series::new("grouping_country".into(), grouping_countries).into(),
+        Series::new("grouping_orderstatus".into(), grouping_orderstatuses).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
+    let keys = ["country", "orderstatus"];
+    let df_joined = df_orders.join(
+        df_customers,
+        [col("customerid")],
+        [col("customerid")],
+        JoinType::Left.into(),
+    );
+    let df = grouping_sets(
+        df_joined,
+        &keys,
+        &cube(&keys),
+        &[col("sales")
+            .sum()
+            .cast(DataType::Int64)
+            .alias("total_sales")],
+    )?
+    .collect()
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && compare_dfs_unordered(&df, &get_df_results(&sqlx_results)?)?
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use polars::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::orders;
+
+use crate::utils::compare::{compare_dfs_unordered, compare_vecs_unordered};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::{get_df_customers, get_df_orders};
+use crate::utils::debug::log_debug;
+use crate::utils::grouping::grouping_sets;
+
+/*
+# TASK:
+
+Count the orders and total the sales per customer country, per order status and overall,
+labelling the rolled-up values 'All countries' and 'All statuses'.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    CASE WHEN GROUPING(c.country) = 1 THEN 'All countries' ELSE c.country END AS country,
+    CASE WHEN GROUPING(o.orderstatus) = 1 THEN 'All statuses' ELSE o.orderstatus END AS orderstatus,
+    COUNT(*) AS total_orders,
+    SUM(o.sales) AS total_sales
+FROM sales.orders AS o
+LEFT JOIN sales.customers AS c
+ON o.customerid = c.customerid
+GROUP BY GROUPING SETS ((c.country), (o.orderstatus), ());
+*/
+
+/*
+shape: (5, 4)
+┌───────────────┬──────────────┬──────────────┬─────────────┐
+│ country       ┆ orderstatus  ┆ total_orders ┆ total_sales │
+│ ---           ┆ ---          ┆ ---          ┆ ---         │
+│ str           ┆ str          ┆ i64          ┆ i64         │
+╞═══════════════╪══════════════╪══════════════╪═════════════╡
+│ Germany       ┆ All statuses ┆ 4            ┆ 200         │
+│ USA           ┆ All statuses ┆ 6            ┆ 180         │
+│ All countries ┆ Delivered    ┆ 5            ┆ 135         │
+│ All countries ┆ Shipped      ┆ 5            ┆ 245         │
+│ All countries ┆ All statuses ┆ 10           ┆ 380         │
+└───────────────┴──────────────┴──────────────┴─────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct SalesGroupingSets {
+    country: Option<String>,
+    orderstatus: Option<String>,
+    total_orders: i64,
+    total_sales: Option<i64>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<SalesGroupingSets>> {
+    let results = orders::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::cust("CASE WHEN GROUPING(country) = 1 THEN 'All countries' ELSE country END"),
+            "country",
+        )
+        .column_as(
+            Expr::cust(
+                "CASE WHEN GROUPING(orderstatus) = 1 THEN 'All statuses' ELSE orderstatus END",
+            ),
+            "orderstatus",
+        )
+        .column_as(Expr::cust("COUNT(*)"), "total_orders")
+        .column_as(Expr::cust("SUM(sales)"), "total_sales")
+        .join(
+            sea_orm::JoinType::LeftJoin,
+            orders::Relation::Customers.def(),
+        )
+        // Note: sea-orm has no GROUPING SETS builder, the grouping is passed as a custom expression.
+        .group_by(Expr::cust("GROUPING SETS ((country), (orderstatus), ())"))
+        .into_model::<SalesGroupingSets>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<SalesGroupingSets>> {
+    let query = "
+    SELECT
+        CASE WHEN GROUPING(c.country) = 1 THEN 'All countries' ELSE c.country END AS country,
+        CASE WHEN GROUPING(o.orderstatus) = 1 THEN 'All statuses' ELSE o.orderstatus END AS orderstatus,
+        COUNT(*) AS total_orders,
+        SUM(o.sales) AS total_sales
+    FROM sales.orders AS o
+    LEFT JOIN sales.customers AS c
+    ON o.customerid = c.customerid
+    GROUP BY GROUPING SETS ((c.country), (o.orderstatus), ());
+    ";
+    let results = sqlx::query_as::<_, SalesGroupingSets>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[SalesGroupingSets]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let countries: Vec<Option<String>> = iter.clone().map(|r| r.country.clone()).collect();
+    let orderstatuses: Vec<Option<String>> = iter.clone().map(|r| r.orderstatus.clone()).collect();
+    let total_orders: Vec<i64> = iter.clone().map(|r| r.total_orders).collect();
+    let total_sales: Vec<Option<i64>> = iter.clone().map(|r| r.total_sales).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("country".into(), countries).into(),
+        Series::new("orderstatus".into(), orderstatuses).into(),
+        Series::new("total_orders".into(), total_orders).into(),
+        Series::new("total_sales".into(), total_sales).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_joined = df_orders.join(
+        df_customers,
+        [col("customerid")],
+        [col("customerid")],
+        JoinType::Left.into(),
+    );
+    let df = grouping_sets(
+        df_joined,
+        &["country", "orderstatus"],
+        &[vec!["country"], vec!["orderstatus"], vec![]],
+        &[
+            len().cast(DataType::Int64).alias("total_orders"),
+            col("sales")
+                .sum()
+                .cast(DataType::Int64)
+                .alias("total_sales"),
+        ],
+    )?
+    .select([
+        when(col("grouping_country").eq(lit(1)))
+            .then(lit("All countries"))
+            .otherwise(col("country"))
+            .alias("country"),
+        when(col("grouping_orderstatus").eq(lit(1)))
+            .then(lit("All statuses"))
+            .otherwise(col("orderstatus"))
+            .alias("orderstatus"),
+        col("total_orders"),
+        col("total_sales"),
+    ])
+    .collect()
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && compare_dfs_unordered(&df, &get_df_results(&sqlx_results)?)?
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
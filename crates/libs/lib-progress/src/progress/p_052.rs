@@ -0,0 +1,193 @@
+use polars::prelude::pivot::pivot_stable;
+use polars::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{orders, products};
+
+use crate::utils::compare::{compare_dfs_by_columns, compare_vecs_unordered};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::{get_df_orders, get_df_products};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Pivot the sales of each product into one column per month, `jan`, `feb` and `mar`, with 0
+for a month without sales.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    p.product,
+    SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 1 THEN o.sales ELSE 0 END) AS jan,
+    SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 2 THEN o.sales ELSE 0 END) AS feb,
+    SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 3 THEN o.sales ELSE 0 END) AS mar
+FROM sales.orders AS o
+LEFT JOIN sales.products AS p
+ON o.productid = p.productid
+GROUP BY p.product;
+*/
+
+/*
+shape: (4, 4)
+┌─────────┬─────┬─────┬─────┐
+│ product ┆ feb ┆ jan ┆ mar │
+│ ---     ┆ --- ┆ --- ┆ --- │
+│ str     ┆ i64 ┆ i64 ┆ i64 │
+╞═════════╪═════╪═════╪═════╡
+│ Tire    ┆ 30  ┆ 15  ┆ 60  │
+│ Bottle  ┆ 90  ┆ 30  ┆ 20  │
+│ Gloves  ┆ 0   ┆ 60  ┆ 0   │
+│ Caps    ┆ 75  ┆ 0   ┆ 0   │
+└─────────┴─────┴─────┴─────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct ProductSales {
+    product: Option<String>,
+    jan: Option<i64>,
+    feb: Option<i64>,
+    mar: Option<i64>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<ProductSales>> {
+    let results = orders::Entity::find()
+        .select_only()
+        .column(products::Column::Product)
+        .column_as(
+            Expr::cust("SUM(CASE WHEN EXTRACT(MONTH FROM orderdate) = 1 THEN sales ELSE 0 END)"),
+            "jan",
+        )
+        .column_as(
+            Expr::cust("SUM(CASE WHEN EXTRACT(MONTH FROM orderdate) = 2 THEN sales ELSE 0 END)"),
+            "feb",
+        )
+        .column_as(
+            Expr::cust("SUM(CASE WHEN EXTRACT(MONTH FROM orderdate) = 3 THEN sales ELSE 0 END)"),
+            "mar",
+        )
+        .join(
+            sea_orm::JoinType::LeftJoin,
+            orders::Relation::Products.def(),
+        )
+        .group_by(products::Column::Product)
+        .into_model::<ProductSales>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<ProductSales>> {
+    let query = "
+    SELECT
+        p.product,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 1 THEN o.sales ELSE 0 END) AS jan,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 2 THEN o.sales ELSE 0 END) AS feb,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 3 THEN o.sales ELSE 0 END) AS mar
+    FROM sales.orders AS o
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid
+    GROUP BY p.product;
+    ";
+    let results = sqlx::query_as::<_, ProductSales>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[ProductSales]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let products: Vec<Option<String>> = iter.clone().map(|r| r.product.clone()).collect();
+    let jans: Vec<Option<i64>> = iter.clone().map(|r| r.jan).collect();
+    let febs: Vec<Option<i64>> = iter.clone().map(|r| r.feb).collect();
+    let mars: Vec<Option<i64>> = iter.clone().map(|r| r.mar).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("product".into(), products).into(),
+        Series::new("jan".into(), jans).into(),
+        Series::new("feb".into(), febs).into(),
+        Series::new("mar".into(), mars).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df_long = df_orders
+        .join(
+            df_products,
+            [col("productid")],
+            [col("productid")],
+            JoinType::Left.into(),
+        )
+        // Note: the months become columns in the order they first appear.
+        .sort(["orderdate"], SortMultipleOptions::default())
+        .group_by_stable([
+            col("product"),
+            col("orderdate")
+                .dt()
+                .strftime("%b")
+                .str()
+                .to_lowercase()
+                .alias("month"),
+        ])
+        .agg([col("sales").sum().cast(DataType::Int64)])
+        .collect()
+        .map_err(AppError::Polars)?;
+    // Note: pivot is eager only, the output columns depend on the data.
+    let df = pivot_stable(
+        &df_long,
+        ["month"],
+        Some(["product"]),
+        Some(["sales"]),
+        false,
+        None,
+        None,
+    )
+    .map_err(AppError::Polars)?
+    .lazy()
+    .with_columns([
+        col("jan").fill_null(0),
+        col("feb").fill_null(0),
+        col("mar").fill_null(0),
+    ])
+    .collect()
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: the pivoted column order follows the data, so only the column set is compared
+    if compare_vecs_unordered(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && compare_dfs_by_columns(&df, &get_df_results(&sqlx_results)?)?
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use polars::prelude::pivot::pivot_stable;
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, DbBackend, FromQueryResult, Statement};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_dfs_by_columns, compare_vecs_unordered};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::{get_df_orders, get_df_products};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Pivot the monthly sales of each product into `jan`, `feb` and `mar` columns with the
+`crosstab` function of the tablefunc extension.
+*/
+
+/*
+# QUERY:
+
+SELECT *
+FROM crosstab(
+    $$
+    SELECT
+        p.product,
+        EXTRACT(MONTH FROM o.orderdate)::INT AS month,
+        SUM(o.sales) AS sales
+    FROM sales.orders AS o
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid
+    GROUP BY p.product, month
+    ORDER BY 1, 2
+    $$,
+    $$ SELECT m FROM generate_series(1, 3) AS m $$
+) AS ct(product VARCHAR, jan BIGINT, feb BIGINT, mar BIGINT);
+*/
+
+/*
+shape: (4, 4)
+┌─────────┬──────┬──────┬──────┐
+│ product ┆ jan  ┆ feb  ┆ mar  │
+│ ---     ┆ ---  ┆ ---  ┆ ---  │
+│ str     ┆ i64  ┆ i64  ┆ i64  │
+╞═════════╪══════╪══════╪══════╡
+│ Bottle  ┆ 30   ┆ 90   ┆ 20   │
+│ Caps    ┆ null ┆ 75   ┆ null │
+│ Gloves  ┆ 60   ┆ null ┆ null │
+│ Tire    ┆ 15   ┆ 30   ┆ 60   │
+└─────────┴──────┴──────┴──────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct ProductSales {
+    product: Option<String>,
+    jan: Option<i64>,
+    feb: Option<i64>,
+    mar: Option<i64>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<ProductSales>> {
+    // Note: crosstab is a table function, sea-orm can only run it as a raw statement.
+    let query = "
+    SELECT *
+    FROM crosstab(
+        $$
+        SELECT
+            p.product,
+            EXTRACT(MONTH FROM o.orderdate)::INT AS month,
+            SUM(o.sales) AS sales
+        FROM sales.orders AS o
+        LEFT JOIN sales.products AS p
+        ON o.productid = p.productid
+        GROUP BY p.product, month
+        ORDER BY 1, 2
+        $$,
+        $$ SELECT m FROM generate_series(1, 3) AS m $$
+    ) AS ct(product VARCHAR, jan BIGINT, feb BIGINT, mar BIGINT);
+    ";
+    let results =
+        ProductSales::find_by_statement(Statement::from_string(DbBackend::Postgres, query))
+            .all(db)
+            .await
+            .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<ProductSales>> {
+    let query = "
+    SELECT *
+    FROM crosstab(
+        $$
+        SELECT
+            p.product,
+            EXTRACT(MONTH FROM o.orderdate)::INT AS month,
+            SUM(o.sales) AS sales
+        FROM sales.orders AS o
+        LEFT JOIN sales.products AS p
+        ON o.productid = p.productid
+        GROUP BY p.product, month
+        ORDER BY 1, 2
+        $$,
+        $$ SELECT m FROM generate_series(1, 3) AS m $$
+    ) AS ct(product VARCHAR, jan BIGINT, feb BIGINT, mar BIGINT);
+    ";
+    let results = sqlx::query_as::<_, ProductSales>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[ProductSales]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let products: Vec<Option<String>> = iter.clone().map(|r| r.product.clone()).collect();
+    let jans: Vec<Option<i64>> = iter.clone().map(|r| r.jan).collect();
+    let febs: Vec<Option<i64>> = iter.clone().map(|r| r.feb).collect();
+    let mars: Vec<Option<i64>> = iter.clone().map(|r| r.mar).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("product".into(), products).into(),
+        Series::new("jan".into(), jans).into(),
+        Series::new("feb".into(), febs).into(),
+        Series::new("mar".into(), mars).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df_long = df_orders
+        .join(
+            df_products,
+            [col("productid")],
+            [col("productid")],
+            JoinType::Left.into(),
+        )
+        // Note: the months become columns in the order they first appear.
+        .sort(["orderdate"], SortMultipleOptions::default())
+        .group_by_stable([
+            col("product"),
+            col("orderdate")
+                .dt()
+                .strftime("%b")
+                .str()
+                .to_lowercase()
+                .alias("month"),
+        ])
+        .agg([col("sales").sum().cast(DataType::Int64)])
+        .collect()
+        .map_err(AppError::Polars)?;
+    // Note: like crosstab, missing product/month pairs stay NULL.
+    let df = pivot_stable(
+        &df_long,
+        ["month"],
+        Some(["product"]),
+        Some(["sales"]),
+        false,
+        None,
+        None,
+    )
+    .map_err(AppError::Polars)?
+    // Note: the columns and rows in the order of the `ct(product, jan, feb, mar)` list.
+    .lazy()
+    .select([col("product"), col("jan"), col("feb"), col("mar")])
+    .sort(["product"], SortMultipleOptions::default())
+    .collect()
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: the columns are matched by name and the rows unordered
+    if compare_vecs_unordered(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && compare_dfs_by_columns(&df, &get_df_results(&sqlx_results)?)?
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use polars::prelude::pivot::pivot_stable;
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, DbBackend, FromQueryResult, Statement};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_dfs_unordered, compare_vecs_unordered};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::{get_df_orders, get_df_products};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Pivot the sales of each product into `jan`, `feb` and `mar`, then unpivot them back into
+rows of product, month name and sales.
+*/
+
+/*
+# QUERY:
+
+WITH pivoted AS (
+    SELECT
+        p.product,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 1 THEN o.sales ELSE 0 END) AS jan,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 2 THEN o.sales ELSE 0 END) AS feb,
+        SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 3 THEN o.sales ELSE 0 END) AS mar
+    FROM sales.orders AS o
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid
+    GROUP BY p.product
+)
+SELECT
+    pv.product,
+    u.month,
+    u.sales
+FROM pivoted AS pv
+CROSS JOIN LATERAL (
+    VALUES ('jan', pv.jan), ('feb', pv.feb), ('mar', pv.mar)
+) AS u(month, sales);
+*/
+
+/*
+shape: (12, 3)
+┌─────────┬───────┬───────┐
+│ product ┆ month ┆ sales │
+│ ---     ┆ ---   ┆ ---   │
+│ str     ┆ str   ┆ i64   │
+╞═════════╪═══════╪═══════╡
+│ Tire    ┆ jan   ┆ 15    │
+│ Bottle  ┆ jan   ┆ 30    │
+│ Gloves  ┆ jan   ┆ 60    │
+│ Caps    ┆ jan   ┆ 0     │
+│ Tire    ┆ feb   ┆ 30    │
+│ …       ┆ …     ┆ …     │
+│ Caps    ┆ feb   ┆ 75    │
+│ Tire    ┆ mar   ┆ 60    │
+│ Bottle  ┆ mar   ┆ 20    │
+│ Gloves  ┆ mar   ┆ 0     │
+│ Caps    ┆ mar   ┆ 0     │
+└─────────┴───────┴───────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct MonthSales {
+    product: Option<String>,
+    month: Option<String>,
+    sales: Option<i64>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<MonthSales>> {
+    // Note: sea-orm has no LATERAL VALUES builder, the unpivot runs as a raw statement.
+    let query = "
+    WITH pivoted AS (
+        SELECT
+            p.product,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 1 THEN o.sales ELSE 0 END) AS jan,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 2 THEN o.sales ELSE 0 END) AS feb,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 3 THEN o.sales ELSE 0 END) AS mar
+        FROM sales.orders AS o
+        LEFT JOIN sales.products AS p
+        ON o.productid = p.productid
+        GROUP BY p.product
+    )
+    SELECT
+        pv.product,
+        u.month,
+        u.sales
+    FROM pivoted AS pv
+    CROSS JOIN LATERAL (
+        VALUES ('jan', pv.jan), ('feb', pv.feb), ('mar', pv.mar)
+    ) AS u(month, sales);
+    ";
+    let results = MonthSales::find_by_statement(Statement::from_string(DbBackend::Postgres, query))
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<MonthSales>> {
+    let query = "
+    WITH pivoted AS (
+        SELECT
+            p.product,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 1 THEN o.sales ELSE 0 END) AS jan,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 2 THEN o.sales ELSE 0 END) AS feb,
+            SUM(CASE WHEN EXTRACT(MONTH FROM o.orderdate) = 3 THEN o.sales ELSE 0 END) AS mar
+        FROM sales.orders AS o
+        LEFT JOIN sales.products AS p
+        ON o.productid = p.productid
+        GROUP BY p.product
+    )
+    SELECT
+        pv.product,
+        u.month,
+        u.sales
+    FROM pivoted AS pv
+    CROSS JOIN LATERAL (
+        VALUES ('jan', pv.jan), ('feb', pv.feb), ('mar', pv.mar)
+    ) AS u(month, sales);
+    ";
+    let results = sqlx::query_as::<_, MonthSales>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[MonthSales]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let products: Vec<Option<String>> = iter.clone().map(|r| r.product.clone()).collect();
+    let months: Vec<Option<String>> = iter.clone().map(|r| r.month.clone()).collect();
+    let sales: Vec<Option<i64>> = iter.clone().map(|r| r.sales).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("product".into(), products).into(),
+        Series::new("month".into(), months).into(),
+        Series::new("sales".into(), sales).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df_long = df_orders
+        .join(
+            df_products,
+            [col("productid")],
+            [col("productid")],
+            JoinType::Left.into(),
+        )
+        .group_by([
+            col("product"),
+            col("orderdate")
+                .dt()
+                .strftime("%b")
+                .str()
+                .to_lowercase()
+                .alias("month"),
+        ])
+        .agg([col("sales").sum().cast(DataType::Int64)])
+        .collect()
+        .map_err(AppError::Polars)?;
+    // Note: pivot is eager only, unpivot works on the LazyFrame again.
+    let df = pivot_stable(
+        &df_long,
+        ["month"],
+        Some(["product"]),
+        Some(["sales"]),
+        false,
+        None,
+        None,
+    )
+    .map_err(AppError::Polars)?
+    .lazy()
+    .with_columns([
+        col("jan").fill_null(0),
+        col("feb").fill_null(0),
+        col("mar").fill_null(0),
+    ])
+    .unpivot(UnpivotArgsDSL {
+        on: cols(["jan", "feb", "mar"]),
+        index: cols(["product"]),
+        variable_name: Some("month".into()),
+        value_name: Some("sales".into()),
+    })
+    .collect()
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && compare_dfs_unordered(&df, &get_df_results(&sqlx_results)?)?
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
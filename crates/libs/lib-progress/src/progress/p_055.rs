@@ -0,0 +1,144 @@
+use std::sync::atomic::Ordering;
+
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{customers, orders};
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::{count_queries, get_database};
+use crate::utils::dataframe::sales::{get_df_customers, get_df_orders};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Retrieve every order with its sales and the first and last name of its customer.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    o.orderid,
+    o.sales,
+    c.firstname,
+    c.lastname
+FROM sales.orders AS o
+LEFT JOIN sales.customers AS c
+ON o.customerid = c.customerid;
+*/
+
+/*
+shape: (10, 4)
+┌─────────┬───────┬───────────┬──────────┐
+│ orderid ┆ sales ┆ firstname ┆ lastname │
+│ ---     ┆ ---   ┆ ---       ┆ ---      │
+│ i32     ┆ i32   ┆ str       ┆ str      │
+╞═════════╪═══════╪═══════════╪══════════╡
+│ 1       ┆ 10    ┆ Kevin     ┆ Brown    │
+│ 2       ┆ 15    ┆ Mary      ┆ null     │
+│ 3       ┆ 20    ┆ Jossef    ┆ Goldberg │
+│ 4       ┆ 60    ┆ Jossef    ┆ Goldberg │
+│ 5       ┆ 25    ┆ Kevin     ┆ Brown    │
+│ 6       ┆ 50    ┆ Mary      ┆ null     │
+│ 7       ┆ 30    ┆ Jossef    ┆ Goldberg │
+│ 8       ┆ 90    ┆ Mark      ┆ Schwarz  │
+│ 9       ┆ 20    ┆ Kevin     ┆ Brown    │
+│ 10      ┆ 60    ┆ Mary      ┆ null     │
+└─────────┴───────┴───────────┴──────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromRow, Hash)]
+pub struct OrderCustomer {
+    orderid: i32,
+    sales: Option<i32>,
+    firstname: Option<String>,
+    lastname: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderCustomer>> {
+    // Note: find_also_related is a single LEFT JOIN, every order comes with an Option<customer>.
+    let results = orders::Entity::find()
+        .find_also_related(customers::Entity)
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .into_iter()
+        .map(|(order, customer)| OrderCustomer {
+            orderid: order.orderid,
+            sales: order.sales,
+            firstname: customer.as_ref().and_then(|c| c.firstname.clone()),
+            lastname: customer.as_ref().and_then(|c| c.lastname.clone()),
+        })
+        .collect::<Vec<OrderCustomer>>();
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderCustomer>> {
+    let query = "
+    SELECT
+        o.orderid,
+        o.sales,
+        c.firstname,
+        c.lastname
+    FROM sales.orders AS o
+    LEFT JOIN sales.customers AS c
+    ON o.customerid = c.customerid;
+    ";
+    let results = sqlx::query_as::<_, OrderCustomer>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df = df_orders
+        .join(
+            df_customers,
+            [col("customerid")],
+            [col("customerid")],
+            JoinType::Left.into(),
+        )
+        .select([
+            col("orderid"),
+            col("sales"),
+            col("firstname"),
+            col("lastname"),
+        ])
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (mut db_sea_orm, db_sqlx) = get_database().await?;
+    let queries = count_queries(&mut db_sea_orm);
+    let sea_orm_results = sea_orm_query(&db_sea_orm).await?;
+
+    log_debug("SEA ORM QUERIES", &queries.load(Ordering::Relaxed), None);
+
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_results, &sqlx_query(&db_sqlx).await?) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::sync::atomic::Ordering;
+
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{customers, orders};
+
+use crate::utils::compare::compare_vecs;
+use crate::utils::database::{count_queries, get_database};
+use crate::utils::dataframe::sales::{get_df_customers, get_df_orders};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+List each customer with the ids of their orders as a sorted array, an empty one for the
+customers without orders, by customer id.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    c.customerid,
+    c.firstname,
+    COALESCE(ARRAY_AGG(o.orderid ORDER BY o.orderid) FILTER (WHERE o.orderid IS NOT NULL), '{}') AS orderids
+FROM sales.customers AS c
+LEFT JOIN sales.orders AS o
+ON c.customerid = o.customerid
+GROUP BY c.customerid, c.firstname
+ORDER BY c.customerid;
+*/
+
+/*
+shape: (5, 3)
+┌────────────┬───────────┬────────────┐
+│ customerid ┆ firstname ┆ orderids   │
+│ ---        ┆ ---       ┆ ---        │
+│ i32        ┆ str       ┆ list[i32]  │
+╞════════════╪═══════════╪════════════╡
+│ 1          ┆ Jossef    ┆ [3, 4, 7]  │
+│ 2          ┆ Kevin     ┆ [1, 5, 9]  │
+│ 3          ┆ Mary      ┆ [2, 6, 10] │
+│ 4          ┆ Mark      ┆ [8]        │
+│ 5          ┆ Anna      ┆ []         │
+└────────────┴───────────┴────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromRow, Hash)]
+pub struct CustomerOrders {
+    customerid: i32,
+    firstname: Option<String>,
+    orderids: Vec<i32>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<CustomerOrders>> {
+    // Note: find_with_related is still one LEFT JOIN, sea-orm folds the rows per customer.
+    let results = customers::Entity::find()
+        .find_with_related(orders::Entity)
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .into_iter()
+        .map(|(customer, orders)| {
+            let mut orderids: Vec<i32> = orders.iter().map(|o| o.orderid).collect();
+
+            orderids.sort();
+
+            CustomerOrders {
+                customerid: customer.customerid,
+                firstname: customer.firstname,
+                orderids,
+            }
+        })
+        .collect::<Vec<CustomerOrders>>();
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<CustomerOrders>> {
+    let query = "
+    SELECT
+        c.customerid,
+        c.firstname,
+        COALESCE(ARRAY_AGG(o.orderid ORDER BY o.orderid) FILTER (WHERE o.orderid IS NOT NULL), '{}') AS orderids
+    FROM sales.customers AS c
+    LEFT JOIN sales.orders AS o
+    ON c.customerid = o.customerid
+    GROUP BY c.customerid, c.firstname
+    ORDER BY c.customerid;
+    ";
+    let results = sqlx::query_as::<_, CustomerOrders>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df = df_customers
+        .join(
+            df_orders,
+            [col("customerid")],
+            [col("customerid")],
+            JoinType::Left.into(),
+        )
+        .group_by([col("customerid"), col("firstname")])
+        .agg([col("orderid")
+            .drop_nulls()
+            .sort(SortOptions::default())
+            .alias("orderids")])
+        .sort(["customerid"], SortMultipleOptions::default())
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (mut db_sea_orm, db_sqlx) = get_database().await?;
+    let queries = count_queries(&mut db_sea_orm);
+    let sea_orm_results = sea_orm_query(&db_sea_orm).await?;
+
+    log_debug("SEA ORM QUERIES", &queries.load(Ordering::Relaxed), None);
+
+    let df = polars_query(&db_sea_orm).await?;
+
+    if compare_vecs(&sea_orm_results, &sqlx_query(&db_sqlx).await?) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, EntityTrait, Linked, RelationDef, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{customers, orders, products};
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::{count_queries, get_database};
+use crate::utils::dataframe::sales::{get_df_customers, get_df_orders, get_df_products};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+List the distinct products each customer has ordered, keeping the customers without
+orders.
+*/
+
+/*
+# QUERY:
+
+SELECT DISTINCT
+    c.customerid,
+    c.firstname,
+    p.product
+FROM sales.customers AS c
+LEFT JOIN sales.orders AS o
+ON c.customerid = o.customerid
+LEFT JOIN sales.products AS p
+ON o.productid = p.productid;
+*/
+
+/*
+shape: (9, 3)
+┌────────────┬───────────┬─────────┐
+│ customerid ┆ firstname ┆ product │
+│ ---        ┆ ---       ┆ ---     │
+│ i32        ┆ str       ┆ str     │
+╞════════════╪═══════════╪═════════╡
+│ 3          ┆ Mary      ┆ Tire    │
+│ 3          ┆ Mary      ┆ Caps    │
+│ 2          ┆ Kevin     ┆ Bottle  │
+│ 1          ┆ Jossef    ┆ Gloves  │
+│ 4          ┆ Mark      ┆ Bottle  │
+│ 1          ┆ Jossef    ┆ Bottle  │
+│ 5          ┆ Anna      ┆ null    │
+│ 2          ┆ Kevin     ┆ Caps    │
+│ 1          ┆ Jossef    ┆ Tire    │
+└────────────┴───────────┴─────────┘
+*/
+
+const DEBUG: bool = false;
+
+// Note: customers and products have no direct relation, the Linked chain goes through orders.
+pub struct CustomerToProduct;
+
+impl Linked for CustomerToProduct {
+    type FromEntity = customers::Entity;
+    type ToEntity = products::Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![
+            customers::Relation::Orders.def(),
+            orders::Relation::Products.def(),
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, FromRow, Hash)]
+pub struct CustomerProduct {
+    customerid: i32,
+    firstname: Option<String>,
+    product: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<CustomerProduct>> {
+    let mut results = customers::Entity::find()
+        .find_also_linked(CustomerToProduct)
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .into_iter()
+        .map(|(customer, product)| CustomerProduct {
+            customerid: customer.customerid,
+            firstname: customer.firstname,
+            product: product.and_then(|p| p.product),
+        })
+        .collect::<Vec<CustomerProduct>>();
+
+    // Note: the linked join returns one row per order, DISTINCT is applied here.
+    let mut seen = HashSet::new();
+
+    results.retain(|row| seen.insert(row.clone()));
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<CustomerProduct>> {
+    let query = "
+    SELECT DISTINCT
+        c.customerid,
+        c.firstname,
+        p.product
+    FROM sales.customers AS c
+    LEFT JOIN sales.orders AS o
+    ON c.customerid = o.customerid
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid;
+    ";
+    let results = sqlx::query_as::<_, CustomerProduct>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df = df_customers
+        .join(
+            df_orders,
+            [col("customerid")],
+            [col("customerid")],
+            JoinType::Left.into(),
+        )
+        .join(
+            df_products,
+            [col("productid")],
+            [col("productid")],
+            JoinType::Left.into(),
+        )
+        .select([col("customerid"), col("firstname"), col("product")])
+        .unique(None, UniqueKeepStrategy::First)
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (mut db_sea_orm, db_sqlx) = get_database().await?;
+    let queries = count_queries(&mut db_sea_orm);
+    let sea_orm_results = sea_orm_query(&db_sea_orm).await?;
+
+    log_debug("SEA ORM QUERIES", &queries.load(Ordering::Relaxed), None);
+
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_results, &sqlx_query(&db_sqlx).await?) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+use std::sync::atomic::Ordering;
+
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, EntityTrait, LoaderTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::{customers, employees, orders, products};
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::{count_queries, get_database};
+use crate::utils::dataframe::sales::{
+    get_df_customers, get_df_employees, get_df_orders, get_df_products,
+};
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Retrieve every order with its sales, the name of its customer, its product and price, and
+the name of its salesperson, keeping the orders with missing details.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    o.orderid,
+    o.sales,
+    c.firstname AS customer_firstname,
+    c.lastname AS customer_lastname,
+    p.product,
+    p.price,
+    e.firstname AS employee_firstname,
+    e.lastname AS employee_lastname
+FROM sales.orders AS o
+LEFT JOIN sales.customers AS c
+ON o.customerid = c.customerid
+LEFT JOIN sales.products AS p
+ON o.productid = p.productid
+LEFT JOIN sales.employees AS e
+ON o.salespersonid = e.employeeid;
+*/
+
+/*
+shape: (10, 8)
+┌─────────┬───────┬───────────────┬───────────────┬─────────┬───────┬───────────────┬──────────────┐
+│ orderid ┆ sales ┆ customer_firs ┆ customer_last ┆ product ┆ price ┆ employee_firs ┆ employee_las │
+│ ---     ┆ ---   ┆ tname         ┆ name          ┆ ---     ┆ ---   ┆ tname         ┆ tname        │
+│ i32     ┆ i32   ┆ ---           ┆ ---           ┆ str     ┆ i32   ┆ ---           ┆ ---          │
+│         ┆       ┆ str           ┆ str           ┆         ┆       ┆ str           ┆ str          │
+╞═════════╪═══════╪═══════════════╪═══════════════╪═════════╪═══════╪═══════════════╪══════════════╡
+│ 1       ┆ 10    ┆ Kevin         ┆ Brown         ┆ Bottle  ┆ 10    ┆ Mary          ┆ null         │
+│ 2       ┆ 15    ┆ Mary          ┆ null          ┆ Tire    ┆ 15    ┆ Mary          ┆ null         │
+│ 3       ┆ 20    ┆ Jossef        ┆ Goldberg      ┆ Bottle  ┆ 10    ┆ Carol         ┆ Baker        │
+│ 4       ┆ 60    ┆ Jossef        ┆ Goldberg      ┆ Gloves  ┆ 30    ┆ Mary          ┆ null         │
+│ 5       ┆ 25    ┆ Kevin         ┆ Brown         ┆ Caps    ┆ 25    ┆ Carol         ┆ Baker        │
+│ 6       ┆ 50    ┆ Mary          ┆ null          ┆ Caps    ┆ 25    ┆ Carol         ┆ Baker        │
+│ 7       ┆ 30    ┆ Jossef        ┆ Goldberg      ┆ Tire    ┆ 15    ┆ Frank         ┆ Lee          │
+│ 8       ┆ 90    ┆ Mark          ┆ Schwarz       ┆ Bottle  ┆ 10    ┆ Mary          ┆ null         │
+│ 9       ┆ 20    ┆ Kevin         ┆ Brown         ┆ Bottle  ┆ 10    ┆ Mary          ┆ null         │
+│ 10      ┆ 60    ┆ Mary          ┆ null          ┆ Tire    ┆ 15    ┆ Carol         ┆ Baker        │
+└─────────┴───────┴───────────────┴───────────────┴─────────┴───────┴───────────────┴──────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromRow, Hash)]
+pub struct OrderDetails {
+    orderid: i32,
+    sales: Option<i32>,
+    customer_firstname: Option<String>,
+    customer_lastname: Option<String>,
+    product: Option<String>,
+    price: Option<i32>,
+    employee_firstname: Option<String>,
+    employee_lastname: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderDetails>> {
+    // Note: every load_one is one `WHERE ... IN (...)` query, 1 + 3 queries in total.
+    let orders = orders::Entity::find()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+    let customers = orders
+        .load_one(customers::Entity, db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+    let products = orders
+        .load_one(products::Entity, db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+    let employees = orders
+        .load_one(employees::Entity, db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+    let results = orders
+        .into_iter()
+        .zip(customers)
+        .zip(products)
+        .zip(employees)
+        .map(|(((order, customer), product), employee)| OrderDetails {
+            orderid: order.orderid,
+            sales: order.sales,
+            customer_firstname: customer.as_ref().and_then(|c| c.firstname.clone()),
+            customer_lastname: customer.as_ref().and_then(|c| c.lastname.clone()),
+            product: product.as_ref().and_then(|p| p.product.clone()),
+            price: product.as_ref().and_then(|p| p.price),
+            employee_firstname: employee.as_ref().and_then(|e| e.firstname.clone()),
+            employee_lastname: employee.as_ref().and_then(|e| e.lastname.clone()),
+        })
+        .collect::<Vec<OrderDetails>>();
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderDetails>> {
+    let query = "
+    SELECT
+        o.orderid,
+        o.sales,
+        c.firstname AS customer_firstname,
+        c.lastname AS customer_lastname,
+        p.product,
+        p.price,
+        e.firstname AS employee_firstname,
+        e.lastname AS employee_lastname
+    FROM sales.orders AS o
+    LEFT JOIN sales.customers AS c
+    ON o.customerid = c.customerid
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid
+    LEFT JOIN sales.employees AS e
+    ON o.salespersonid = e.employeeid;
+    ";
+    let results = sqlx::query_as::<_, OrderDetails>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_orders = get_df_orders(db).await?.lazy();
+    let df_customers = get_df_customers(db).await?.lazy();
+    let df_products = get_df_products(db).await?.lazy();
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df = df_orders
+        .join(
+            df_customers,
+            [col("customerid")],
+            [col("customerid")],
+            JoinType::Left.into(),
+        )
+        .join(
+            df_products,
+            [col("productid")],
+            [col("productid")],
+            JoinType::Left.into(),
+        )
+        .join(
+            df_employees,
+            [col("salespersonid")],
+            [col("employeeid")],
+            JoinArgs::new(JoinType::Left).with_suffix(Some("_employee".into())),
+        )
+        .select([
+            col("orderid"),
+            col("sales"),
+            col("firstname").alias("customer_firstname"),
+            col("lastname").alias("customer_lastname"),
+            col("product"),
+            col("price"),
+            col("firstname_employee").alias("employee_firstname"),
+            col("lastname_employee").alias("employee_lastname"),
+        ])
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (mut db_sea_orm, db_sqlx) = get_database().await?;
+    let queries = count_queries(&mut db_sea_orm);
+    let sea_orm_results = sea_orm_query(&db_sea_orm).await?;
+
+    log_debug("SEA ORM QUERIES", &queries.load(Ordering::Relaxed), None);
+
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(&sea_orm_results, &sqlx_query(&db_sqlx).await?) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use polars::prelude::*;
+use sea_orm::sea_query::{Alias, Expr};
+use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::employees;
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::get_df_employees;
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+List each employee with the first and last name of their manager, keeping the employees
+without one.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    e.employeeid,
+    e.firstname,
+    e.lastname,
+    m.firstname AS manager_firstname,
+    m.lastname AS manager_lastname
+FROM sales.employees AS e
+LEFT JOIN sales.employees AS m
+ON e.managerid = m.employeeid;
+*/
+
+/*
+shape: (5, 5)
+┌────────────┬───────────┬──────────┬───────────────────┬──────────────────┐
+│ employeeid ┆ firstname ┆ lastname ┆ manager_firstname ┆ manager_lastname │
+│ ---        ┆ ---       ┆ ---      ┆ ---               ┆ ---              │
+│ i32        ┆ str       ┆ str      ┆ str               ┆ str              │
+╞════════════╪═══════════╪══════════╪═══════════════════╪══════════════════╡
+│ 1          ┆ Frank     ┆ Lee      ┆ null              ┆ null             │
+│ 2          ┆ Kevin     ┆ Brown    ┆ Frank             ┆ Lee              │
+│ 3          ┆ Mary      ┆ null     ┆ Frank             ┆ Lee              │
+│ 4          ┆ Michael   ┆ Ray      ┆ Kevin             ┆ Brown            │
+│ 5          ┆ Carol     ┆ Baker    ┆ Mary              ┆ null             │
+└────────────┴───────────┴──────────┴───────────────────┴──────────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct EmployeeManager {
+    employeeid: i32,
+    firstname: Option<String>,
+    lastname: Option<String>,
+    manager_firstname: Option<String>,
+    manager_lastname: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<EmployeeManager>> {
+    // Note: both sides are the same table, the manager side needs its own alias.
+    let manager = Alias::new("m");
+    let results = employees::Entity::find()
+        .select_only()
+        .column(employees::Column::Employeeid)
+        .column(employees::Column::Firstname)
+        .column(employees::Column::Lastname)
+        .column_as(
+            Expr::col((manager.clone(), employees::Column::Firstname)),
+            "manager_firstname",
+        )
+        .column_as(
+            Expr::col((manager.clone(), employees::Column::Lastname)),
+            "manager_lastname",
+        )
+        .join_as(
+            sea_orm::JoinType::LeftJoin,
+            employees::Relation::Manager.def(),
+            manager,
+        )
+        .into_model::<EmployeeManager>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<EmployeeManager>> {
+    let query = "
+    SELECT
+        e.employeeid,
+        e.firstname,
+        e.lastname,
+        m.firstname AS manager_firstname,
+        m.lastname AS manager_lastname
+    FROM sales.employees AS e
+    LEFT JOIN sales.employees AS m
+    ON e.managerid = m.employeeid;
+    ";
+    let results = sqlx::query_as::<_, EmployeeManager>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df_managers = df_employees.clone();
+    let df = df_employees
+        .join(
+            df_managers,
+            [col("managerid")],
+            [col("employeeid")],
+            JoinArgs::new(JoinType::Left).with_suffix(Some("_manager".into())),
+        )
+        .select([
+            col("employeeid"),
+            col("firstname"),
+            col("lastname"),
+            col("firstname_manager").alias("manager_firstname"),
+            col("lastname_manager").alias("manager_lastname"),
+        ])
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(
+        &sea_orm_query(&db_sea_orm).await?,
+        &sqlx_query(&db_sqlx).await?,
+    ) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
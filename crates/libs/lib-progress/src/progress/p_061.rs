@@ -0,0 +1,143 @@
+use polars::prelude::*;
+use sea_orm::sea_query::{Alias, Expr};
+use sea_orm::{DatabaseConnection, EntityTrait, FromQueryResult, QuerySelect, RelationTrait};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::employees;
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::get_df_employees;
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Count the direct reports of each employee as `direct_reports`.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    m.employeeid,
+    m.firstname,
+    COUNT(e.employeeid) AS direct_reports
+FROM sales.employees AS m
+LEFT JOIN sales.employees AS e
+ON e.managerid = m.employeeid
+GROUP BY m.employeeid, m.firstname;
+*/
+
+/*
+shape: (5, 3)
+┌────────────┬───────────┬────────────────┐
+│ employeeid ┆ firstname ┆ direct_reports │
+│ ---        ┆ ---       ┆ ---            │
+│ i32        ┆ str       ┆ i64            │
+╞════════════╪═══════════╪════════════════╡
+│ 5          ┆ Carol     ┆ 0              │
+│ 3          ┆ Mary      ┆ 1              │
+│ 2          ┆ Kevin     ┆ 1              │
+│ 4          ┆ Michael   ┆ 0              │
+│ 1          ┆ Frank     ┆ 2              │
+└────────────┴───────────┴────────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct ManagerReports {
+    employeeid: i32,
+    firstname: Option<String>,
+    direct_reports: i64,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<ManagerReports>> {
+    // Note: the reversed Manager relation joins every employee that reports to the row.
+    let report = Alias::new("e");
+    let results = employees::Entity::find()
+        .select_only()
+        .column(employees::Column::Employeeid)
+        .column(employees::Column::Firstname)
+        .column_as(
+            Expr::col((report.clone(), employees::Column::Employeeid)).count(),
+            "direct_reports",
+        )
+        .join_as(
+            sea_orm::JoinType::LeftJoin,
+            employees::Relation::Manager.def().rev(),
+            report,
+        )
+        .group_by(employees::Column::Employeeid)
+        .group_by(employees::Column::Firstname)
+        .into_model::<ManagerReports>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<ManagerReports>> {
+    let query = "
+    SELECT
+        m.employeeid,
+        m.firstname,
+        COUNT(e.employeeid) AS direct_reports
+    FROM sales.employees AS m
+    LEFT JOIN sales.employees AS e
+    ON e.managerid = m.employeeid
+    GROUP BY m.employeeid, m.firstname;
+    ";
+    let results = sqlx::query_as::<_, ManagerReports>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df_reports = df_employees
+        .clone()
+        .select([col("employeeid").alias("reportid"), col("managerid")]);
+    let df = df_employees
+        .join(
+            df_reports,
+            [col("employeeid")],
+            [col("managerid")],
+            JoinType::Left.into(),
+        )
+        .group_by([col("employeeid"), col("firstname")])
+        .agg([col("reportid")
+            .count()
+            .cast(DataType::Int64)
+            .alias("direct_reports")])
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(
+        &sea_orm_query(&db_sea_orm).await?,
+        &sqlx_query(&db_sqlx).await?,
+    ) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
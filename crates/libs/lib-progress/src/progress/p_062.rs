@@ -0,0 +1,157 @@
+use polars::prelude::*;
+use sea_orm::sea_query::{Alias, Expr};
+use sea_orm::{
+    DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, QuerySelect, RelationTrait,
+};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database_sales::employees;
+
+use crate::utils::compare::compare_vecs_unordered;
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sales::get_df_employees;
+use crate::utils::debug::log_debug;
+
+/*
+# TASK:
+
+Find the employees who earn more than their manager, with the first name and the salary of
+the manager.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    e.employeeid,
+    e.firstname,
+    e.salary,
+    m.firstname AS manager_firstname,
+    m.salary AS manager_salary
+FROM sales.employees AS e
+INNER JOIN sales.employees AS m
+ON e.managerid = m.employeeid
+WHERE e.salary > m.salary;
+*/
+
+/*
+shape: (3, 5)
+┌────────────┬───────────┬────────┬───────────────────┬────────────────┐
+│ employeeid ┆ firstname ┆ salary ┆ manager_firstname ┆ manager_salary │
+│ ---        ┆ ---       ┆ ---    ┆ ---               ┆ ---            │
+│ i32        ┆ str       ┆ i32    ┆ str               ┆ i32            │
+╞════════════╪═══════════╪════════╪═══════════════════╪════════════════╡
+│ 2          ┆ Kevin     ┆ 65000  ┆ Frank             ┆ 55000          │
+│ 3          ┆ Mary      ┆ 75000  ┆ Frank             ┆ 55000          │
+│ 4          ┆ Michael   ┆ 90000  ┆ Kevin             ┆ 65000          │
+└────────────┴───────────┴────────┴───────────────────┴────────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct EmployeeSalary {
+    employeeid: i32,
+    firstname: Option<String>,
+    salary: Option<i32>,
+    manager_firstname: Option<String>,
+    manager_salary: Option<i32>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<EmployeeSalary>> {
+    let manager = Alias::new("m");
+    let results = employees::Entity::find()
+        .select_only()
+        .column(employees::Column::Employeeid)
+        .column(employees::Column::Firstname)
+        .column(employees::Column::Salary)
+        .column_as(
+            Expr::col((manager.clone(), employees::Column::Firstname)),
+            "manager_firstname",
+        )
+        .column_as(
+            Expr::col((manager.clone(), employees::Column::Salary)),
+            "manager_salary",
+        )
+        .join_as(
+            sea_orm::JoinType::InnerJoin,
+            employees::Relation::Manager.def(),
+            manager.clone(),
+        )
+        .filter(
+            Expr::col((employees::Entity, employees::Column::Salary))
+                .gt(Expr::col((manager, employees::Column::Salary))),
+        )
+        .into_model::<EmployeeSalary>()
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<EmployeeSalary>> {
+    let query = "
+    SELECT
+        e.employeeid,
+        e.firstname,
+        e.salary,
+        m.firstname AS manager_firstname,
+        m.salary AS manager_salary
+    FROM sales.employees AS e
+    INNER JOIN sales.employees AS m
+    ON e.managerid = m.employeeid
+    WHERE e.salary > m.salary;
+    ";
+    let results = sqlx::query_as::<_, EmployeeSalary>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_employees = get_df_employees(db).await?.lazy();
+    let df_managers = df_employees.clone();
+    let df = df_employees
+        .join(
+            df_managers,
+            [col("managerid")],
+            [col("employeeid")],
+            JoinArgs::new(JoinType::Inner).with_suffix(Some("_manager".into())),
+        )
+        .filter(col("salary").gt(col("salary_manager")))
+        .select([
+            col("employeeid"),
+            col("firstname"),
+            col("salary"),
+            col("firstname_manager").alias("manager_firstname"),
+            col("salary_manager").alias("manager_salary"),
+        ])
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+    let df = polars_query(&db_sea_orm).await?;
+
+    // Note: without ORDER BY, the row order in both results will vary
+    if compare_vecs_unordered(
+        &sea_orm_query(&db_sea_orm).await?,
+        &sqlx_query(&db_sqlx).await?,
+    ) {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: dirty read
+
+A: BEGIN;
+A: UPDATE sandbox.accounts SET balance = 0 WHERE id = 1;
+B: BEGIN ISOLATION LEVEL READ UNCOMMITTED;
+B: SELECT balance FROM sandbox.accounts WHERE id = 1;
+A: ROLLBACK;
+B: COMMIT;
+*/
+
+/*
+shape: (6, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ A       ┆ BEGIN                           ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 3    ┆ B       ┆ BEGIN ISOLATION LEVEL READ UNC… ┆ false   ┆ null  ┆ null  │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 5    ┆ A       ┆ ROLLBACK                        ┆ false   ┆ null  ┆ null  │
+│ 6    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results = run_interleaving(
+        &db_sqlx,
+        &[
+            step(Session::A, "BEGIN"),
+            step(
+                Session::A,
+                "UPDATE sandbox.accounts SET balance = 0 WHERE id = 1",
+            ),
+            step(Session::B, "BEGIN ISOLATION LEVEL READ UNCOMMITTED"),
+            step(
+                Session::B,
+                "SELECT balance FROM sandbox.accounts WHERE id = 1",
+            ),
+            step(Session::A, "ROLLBACK"),
+            step(Session::B, "COMMIT"),
+        ],
+    )
+    .await?;
+
+    // Note: Postgres runs READ UNCOMMITTED as READ COMMITTED, a dirty read never happens.
+    expect(
+        results[3].value() == Some(100),
+        "session B must not see the uncommitted balance",
+    )?;
+
+    log_debug("POLARS", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
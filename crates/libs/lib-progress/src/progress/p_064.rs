@@ -0,0 +1,101 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, Step, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: non-repeatable read
+
+B: BEGIN ISOLATION LEVEL <level>;
+B: SELECT balance FROM sandbox.accounts WHERE id = 1;
+A: UPDATE sandbox.accounts SET balance = 50 WHERE id = 1;
+B: SELECT balance FROM sandbox.accounts WHERE id = 1;
+B: COMMIT;
+*/
+
+/*
+READ COMMITTED:
+
+shape: (5, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ B       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 3    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 50    ┆ null  │
+│ 5    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+/*
+REPEATABLE READ:
+
+shape: (5, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ B       ┆ BEGIN ISOLATION LEVEL REPEATAB… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 3    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 5    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+fn scenario(begin: &'static str) -> Vec<Step> {
+    vec![
+        step(Session::B, begin),
+        step(
+            Session::B,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+        step(
+            Session::A,
+            "UPDATE sandbox.accounts SET balance = 50 WHERE id = 1",
+        ),
+        step(
+            Session::B,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+        step(Session::B, "COMMIT"),
+    ]
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL READ COMMITTED")).await?;
+
+    // Note: every statement takes a new snapshot, the second read sees A's commit.
+    expect(
+        results[1].value() == Some(100) && results[3].value() == Some(50),
+        "READ COMMITTED must see the committed update",
+    )?;
+
+    log_debug("READ COMMITTED", &get_df_timeline(&results)?, None);
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL REPEATABLE READ")).await?;
+
+    // Note: the snapshot is taken once, the second read still sees 100.
+    expect(
+        results[1].value() == Some(100) && results[3].value() == Some(100),
+        "REPEATABLE READ must read the same balance twice",
+    )?;
+
+    log_debug("REPEATABLE READ", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
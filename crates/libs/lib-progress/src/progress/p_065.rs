@@ -0,0 +1,101 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, Step, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: phantom read
+
+B: BEGIN ISOLATION LEVEL <level>;
+B: SELECT COUNT(*) FROM sandbox.accounts WHERE balance >= 100;
+A: INSERT INTO sandbox.accounts VALUES (3, 'Anna', 200);
+B: SELECT COUNT(*) FROM sandbox.accounts WHERE balance >= 100;
+B: COMMIT;
+*/
+
+/*
+READ COMMITTED:
+
+shape: (5, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ B       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ SELECT COUNT(*) FROM sandbox.a… ┆ false   ┆ 2     ┆ null  │
+│ 3    ┆ A       ┆ INSERT INTO sandbox.accounts V… ┆ false   ┆ null  ┆ null  │
+│ 4    ┆ B       ┆ SELECT COUNT(*) FROM sandbox.a… ┆ false   ┆ 3     ┆ null  │
+│ 5    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+/*
+REPEATABLE READ:
+
+shape: (5, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ B       ┆ BEGIN ISOLATION LEVEL REPEATAB… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ SELECT COUNT(*) FROM sandbox.a… ┆ false   ┆ 2     ┆ null  │
+│ 3    ┆ A       ┆ INSERT INTO sandbox.accounts V… ┆ false   ┆ null  ┆ null  │
+│ 4    ┆ B       ┆ SELECT COUNT(*) FROM sandbox.a… ┆ false   ┆ 2     ┆ null  │
+│ 5    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+fn scenario(begin: &'static str) -> Vec<Step> {
+    vec![
+        step(Session::B, begin),
+        step(
+            Session::B,
+            "SELECT COUNT(*) FROM sandbox.accounts WHERE balance >= 100",
+        ),
+        step(
+            Session::A,
+            "INSERT INTO sandbox.accounts VALUES (3, 'Anna', 200)",
+        ),
+        step(
+            Session::B,
+            "SELECT COUNT(*) FROM sandbox.accounts WHERE balance >= 100",
+        ),
+        step(Session::B, "COMMIT"),
+    ]
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL READ COMMITTED")).await?;
+
+    // Note: the inserted row shows up in the second count (phantom).
+    expect(
+        results[1].value() == Some(2) && results[3].value() == Some(3),
+        "READ COMMITTED must see the inserted row",
+    )?;
+
+    log_debug("READ COMMITTED", &get_df_timeline(&results)?, None);
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL REPEATABLE READ")).await?;
+
+    // Note: unlike the SQL standard minimum, Postgres REPEATABLE READ also hides phantoms.
+    expect(
+        results[1].value() == Some(2) && results[3].value() == Some(2),
+        "REPEATABLE READ must not see the phantom row",
+    )?;
+
+    log_debug("REPEATABLE READ", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
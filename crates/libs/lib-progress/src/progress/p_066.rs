@@ -0,0 +1,129 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, Step, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: lost update
+
+A: BEGIN ISOLATION LEVEL <level>;
+B: BEGIN ISOLATION LEVEL <level>;
+A: SELECT balance FROM sandbox.accounts WHERE id = 1;
+B: SELECT balance FROM sandbox.accounts WHERE id = 1;
+A: UPDATE sandbox.accounts SET balance = 110 WHERE id = 1;
+B: UPDATE sandbox.accounts SET balance = 120 WHERE id = 1;
+A: COMMIT;
+B: COMMIT;
+A: SELECT balance FROM sandbox.accounts WHERE id = 1;
+*/
+
+/*
+READ COMMITTED:
+
+shape: (9, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ A       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 3    ┆ A       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 5    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 6    ┆ B       ┆ UPDATE sandbox.accounts SET ba… ┆ true    ┆ null  ┆ null  │
+│ 7    ┆ A       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 8    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 9    ┆ A       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 120   ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+/*
+REPEATABLE READ:
+
+shape: (9, 6)
+┌──────┬─────────┬──────────────────────────────────┬─────────┬───────┬────────────────────────────┐
+│ step ┆ session ┆ sql                              ┆ blocked ┆ value ┆ error                      │
+│ ---  ┆ ---     ┆ ---                              ┆ ---     ┆ ---   ┆ ---                        │
+│ u32  ┆ str     ┆ str                              ┆ bool    ┆ i64   ┆ str                        │
+╞══════╪═════════╪══════════════════════════════════╪═════════╪═══════╪════════════════════════════╡
+│ 1    ┆ A       ┆ BEGIN ISOLATION LEVEL REPEATAB…  ┆ false   ┆ null  ┆ null                       │
+│ 2    ┆ B       ┆ BEGIN ISOLATION LEVEL REPEATAB…  ┆ false   ┆ null  ┆ null                       │
+│ 3    ┆ A       ┆ SELECT balance FROM sandbox.ac…  ┆ false   ┆ 100   ┆ null                       │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac…  ┆ false   ┆ 100   ┆ null                       │
+│ 5    ┆ A       ┆ UPDATE sandbox.accounts SET ba…  ┆ false   ┆ null  ┆ null                       │
+│ 6    ┆ B       ┆ UPDATE sandbox.accounts SET ba…  ┆ true    ┆ null  ┆ 40001: could not serialize │
+│      ┆         ┆                                  ┆         ┆       ┆ acc…                       │
+│ 7    ┆ A       ┆ COMMIT                           ┆ false   ┆ null  ┆ null                       │
+│ 8    ┆ B       ┆ COMMIT                           ┆ false   ┆ null  ┆ null                       │
+│ 9    ┆ A       ┆ SELECT balance FROM sandbox.ac…  ┆ false   ┆ 110   ┆ null                       │
+└──────┴─────────┴──────────────────────────────────┴─────────┴───────┴────────────────────────────┘
+*/
+
+fn scenario(begin: &'static str) -> Vec<Step> {
+    vec![
+        step(Session::A, begin),
+        step(Session::B, begin),
+        step(
+            Session::A,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+        step(
+            Session::B,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+        step(
+            Session::A,
+            "UPDATE sandbox.accounts SET balance = 110 WHERE id = 1",
+        ),
+        step(
+            Session::B,
+            "UPDATE sandbox.accounts SET balance = 120 WHERE id = 1",
+        ),
+        step(Session::A, "COMMIT"),
+        step(Session::B, "COMMIT"),
+        step(
+            Session::A,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+    ]
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL READ COMMITTED")).await?;
+
+    // Note: each session writes a balance computed from its own read, B overwrites A.
+    expect(results[5].blocked, "B's update must wait for A's row lock")?;
+    expect(
+        results[8].value() == Some(120),
+        "READ COMMITTED must lose A's +10",
+    )?;
+
+    log_debug("READ COMMITTED", &get_df_timeline(&results)?, None);
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL REPEATABLE READ")).await?;
+
+    // Note: B's update waits for A, then fails with a serialization error instead of overwriting.
+    expect(
+        results[5].failed_with("40001"),
+        "REPEATABLE READ must reject B's update",
+    )?;
+    expect(
+        results[8].value() == Some(110),
+        "REPEATABLE READ must keep A's update",
+    )?;
+
+    log_debug("REPEATABLE READ", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, Step, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: SELECT ... FOR UPDATE
+
+A: BEGIN ISOLATION LEVEL <level>;
+B: BEGIN ISOLATION LEVEL <level>;
+A: SELECT balance FROM sandbox.accounts WHERE id = 1 FOR UPDATE;
+B: SELECT balance FROM sandbox.accounts WHERE id = 1 FOR UPDATE;
+A: UPDATE sandbox.accounts SET balance = balance + 10 WHERE id = 1;
+A: COMMIT;
+B: UPDATE sandbox.accounts SET balance = balance + 20 WHERE id = 1;
+B: COMMIT;
+A: SELECT balance FROM sandbox.accounts WHERE id = 1;
+*/
+
+/*
+READ COMMITTED:
+
+shape: (9, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ A       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ BEGIN ISOLATION LEVEL READ COM… ┆ false   ┆ null  ┆ null  │
+│ 3    ┆ A       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 100   ┆ null  │
+│ 4    ┆ B       ┆ SELECT balance FROM sandbox.ac… ┆ true    ┆ 110   ┆ null  │
+│ 5    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 6    ┆ A       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 7    ┆ B       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 8    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 9    ┆ A       ┆ SELECT balance FROM sandbox.ac… ┆ false   ┆ 130   ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+fn scenario(begin: &'static str) -> Vec<Step> {
+    vec![
+        step(Session::A, begin),
+        step(Session::B, begin),
+        step(
+            Session::A,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1 FOR UPDATE",
+        ),
+        step(
+            Session::B,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1 FOR UPDATE",
+        ),
+        step(
+            Session::A,
+            "UPDATE sandbox.accounts SET balance = balance + 10 WHERE id = 1",
+        ),
+        step(Session::A, "COMMIT"),
+        step(
+            Session::B,
+            "UPDATE sandbox.accounts SET balance = balance + 20 WHERE id = 1",
+        ),
+        step(Session::B, "COMMIT"),
+        step(
+            Session::A,
+            "SELECT balance FROM sandbox.accounts WHERE id = 1",
+        ),
+    ]
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL READ COMMITTED")).await?;
+
+    // Note: the row lock makes B wait for A, B then reads the committed 110 and no update is lost.
+    expect(
+        results[3].blocked && results[3].value() == Some(110),
+        "B's locking read must wait and see A's commit",
+    )?;
+    expect(
+        results[8].value() == Some(130),
+        "FOR UPDATE must keep both updates",
+    )?;
+
+    log_debug("READ COMMITTED", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use lib_core::error::AppResult;
+
+use crate::utils::database::get_database;
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_accounts;
+use crate::utils::session::{Session, Step, expect, get_df_timeline, run_interleaving, step};
+
+/*
+# SCENARIO: serialization failure (write skew)
+
+A: BEGIN ISOLATION LEVEL <level>;
+B: BEGIN ISOLATION LEVEL <level>;
+A: SELECT SUM(balance)::BIGINT FROM sandbox.accounts;
+B: SELECT SUM(balance)::BIGINT FROM sandbox.accounts;
+A: UPDATE sandbox.accounts SET balance = balance - 200 WHERE id = 1;
+B: UPDATE sandbox.accounts SET balance = balance - 200 WHERE id = 2;
+A: COMMIT;
+B: COMMIT;
+A: SELECT SUM(balance)::BIGINT FROM sandbox.accounts;
+*/
+
+/*
+REPEATABLE READ:
+
+shape: (9, 6)
+┌──────┬─────────┬─────────────────────────────────┬─────────┬───────┬───────┐
+│ step ┆ session ┆ sql                             ┆ blocked ┆ value ┆ error │
+│ ---  ┆ ---     ┆ ---                             ┆ ---     ┆ ---   ┆ ---   │
+│ u32  ┆ str     ┆ str                             ┆ bool    ┆ i64   ┆ str   │
+╞══════╪═════════╪═════════════════════════════════╪═════════╪═══════╪═══════╡
+│ 1    ┆ A       ┆ BEGIN ISOLATION LEVEL REPEATAB… ┆ false   ┆ null  ┆ null  │
+│ 2    ┆ B       ┆ BEGIN ISOLATION LEVEL REPEATAB… ┆ false   ┆ null  ┆ null  │
+│ 3    ┆ A       ┆ SELECT SUM(balance)::BIGINT FR… ┆ false   ┆ 200   ┆ null  │
+│ 4    ┆ B       ┆ SELECT SUM(balance)::BIGINT FR… ┆ false   ┆ 200   ┆ null  │
+│ 5    ┆ A       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 6    ┆ B       ┆ UPDATE sandbox.accounts SET ba… ┆ false   ┆ null  ┆ null  │
+│ 7    ┆ A       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 8    ┆ B       ┆ COMMIT                          ┆ false   ┆ null  ┆ null  │
+│ 9    ┆ A       ┆ SELECT SUM(balance)::BIGINT FR… ┆ false   ┆ -200  ┆ null  │
+└──────┴─────────┴─────────────────────────────────┴─────────┴───────┴───────┘
+*/
+
+/*
+SERIALIZABLE:
+
+shape: (9, 6)
+┌──────┬─────────┬──────────────────────────────────┬─────────┬───────┬────────────────────────────┐
+│ step ┆ session ┆ sql                              ┆ blocked ┆ value ┆ error                      │
+│ ---  ┆ ---     ┆ ---                              ┆ ---     ┆ ---   ┆ ---                        │
+│ u32  ┆ str     ┆ str                              ┆ bool    ┆ i64   ┆ str                        │
+╞══════╪═════════╪══════════════════════════════════╪═════════╪═══════╪════════════════════════════╡
+│ 1    ┆ A       ┆ BEGIN ISOLATION LEVEL SERIALIZ…  ┆ false   ┆ null  ┆ null                       │
+│ 2    ┆ B       ┆ BEGIN ISOLATION LEVEL SERIALIZ…  ┆ false   ┆ null  ┆ null                       │
+│ 3    ┆ A       ┆ SELECT SUM(balance)::BIGINT FR…  ┆ false   ┆ 200   ┆ null                       │
+│ 4    ┆ B       ┆ SELECT SUM(balance)::BIGINT FR…  ┆ false   ┆ 200   ┆ null                       │
+│ 5    ┆ A       ┆ UPDATE sandbox.accounts SET ba…  ┆ false   ┆ null  ┆ null                       │
+│ 6    ┆ B       ┆ UPDATE sandbox.accounts SET ba…  ┆ false   ┆ null  ┆ null                       │
+│ 7    ┆ A       ┆ COMMIT                           ┆ false   ┆ null  ┆ null                       │
+│ 8    ┆ B       ┆ COMMIT                           ┆ false   ┆ null  ┆ 40001: could not serialize │
+│      ┆         ┆                                  ┆         ┆       ┆ acc…                       │
+│ 9    ┆ A       ┆ SELECT SUM(balance)::BIGINT FR…  ┆ false   ┆ 0     ┆ null                       │
+└──────┴─────────┴──────────────────────────────────┴─────────┴───────┴────────────────────────────┘
+*/
+
+fn scenario(begin: &'static str) -> Vec<Step> {
+    vec![
+        step(Session::A, begin),
+        step(Session::B, begin),
+        step(
+            Session::A,
+            "SELECT SUM(balance)::BIGINT FROM sandbox.accounts",
+        ),
+        step(
+            Session::B,
+            "SELECT SUM(balance)::BIGINT FROM sandbox.accounts",
+        ),
+        step(
+            Session::A,
+            "UPDATE sandbox.accounts SET balance = balance - 200 WHERE id = 1",
+        ),
+        step(
+            Session::B,
+            "UPDATE sandbox.accounts SET balance = balance - 200 WHERE id = 2",
+        ),
+        step(Session::A, "COMMIT"),
+        step(Session::B, "COMMIT"),
+        step(
+            Session::A,
+            "SELECT SUM(balance)::BIGINT FROM sandbox.accounts",
+        ),
+    ]
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (_, db_sqlx) = get_database().await?;
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL REPEATABLE READ")).await?;
+
+    // Note: both sessions see a total of 200, then each withdraws 200 from a different row.
+    expect(
+        results[8].value() == Some(-200),
+        "REPEATABLE READ must allow the write skew",
+    )?;
+
+    log_debug("REPEATABLE READ", &get_df_timeline(&results)?, None);
+
+    reset_accounts(&db_sqlx).await?;
+
+    let results =
+        run_interleaving(&db_sqlx, &scenario("BEGIN ISOLATION LEVEL SERIALIZABLE")).await?;
+
+    // Note: the second commit is rejected, retrying B would see the total is already 0.
+    expect(
+        results[5].failed_with("40001") || results[7].failed_with("40001"),
+        "SERIALIZABLE must abort session B",
+    )?;
+    expect(
+        results[8].value() == Some(0),
+        "SERIALIZABLE must keep the total at 0",
+    )?;
+
+    log_debug("SERIALIZABLE", &get_df_timeline(&results)?, None);
+
+    Ok(())
+}
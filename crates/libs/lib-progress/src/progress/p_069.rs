@@ -0,0 +1,170 @@
+use polars::prelude::*;
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Alias, Expr, Order, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection, FromQueryResult};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_vecs, diff_dfs};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sandbox::{get_df_order_payloads, order_payload_dtype};
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_order_payloads;
+
+/*
+# TASK:
+
+Retrieve the id, the customer object and the status of the orders whose customer is from
+the USA, by order id.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    orderid,
+    payload->'customer' AS customer,
+    payload->>'status' AS status
+FROM sandbox.order_payloads
+WHERE payload->'customer'->>'country' = 'USA'
+ORDER BY orderid;
+*/
+
+/*
+shape: (6, 3)
+┌─────────┬───────────────────┬───────────┐
+│ orderid ┆ customer          ┆ status    │
+│ ---     ┆ ---               ┆ ---       │
+│ i32     ┆ struct[3]         ┆ str       │
+╞═════════╪═══════════════════╪═══════════╡
+│ 1       ┆ {2,"Kevin","USA"} ┆ Delivered │
+│ 2       ┆ {3,"Mary","USA"}  ┆ Shipped   │
+│ 5       ┆ {2,"Kevin","USA"} ┆ Delivered │
+│ 6       ┆ {3,"Mary","USA"}  ┆ Delivered │
+│ 9       ┆ {2,"Kevin","USA"} ┆ Shipped   │
+│ 10      ┆ {3,"Mary","USA"}  ┆ Shipped   │
+└─────────┴───────────────────┴───────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, FromQueryResult, FromRow)]
+pub struct OrderCustomer {
+    orderid: i32,
+    customer: serde_json::Value,
+    status: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderCustomer>> {
+    // Note: `->` keeps the JSONB value, `->>` returns it as text.
+    let payload = Alias::new("payload");
+    let query = Query::select()
+        .column(Alias::new("orderid"))
+        .expr_as(
+            Expr::col(payload.clone()).get_json_field("customer"),
+            Alias::new("customer"),
+        )
+        .expr_as(
+            Expr::col(payload.clone()).cast_json_field("status"),
+            Alias::new("status"),
+        )
+        .from((Alias::new("sandbox"), Alias::new("order_payloads")))
+        .and_where(
+            Expr::col(payload)
+                .get_json_field("customer")
+                .cast_json_field("country")
+                .eq("USA"),
+        )
+        .order_by(Alias::new("orderid"), Order::Asc)
+        .to_owned();
+    let results = OrderCustomer::find_by_statement(db.get_database_backend().build(&query))
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderCustomer>> {
+    let query = "
+    SELECT
+        orderid,
+        payload->'customer' AS customer,
+        payload->>'status' AS status
+    FROM sandbox.order_payloads
+    WHERE payload->'customer'->>'country' = 'USA'
+    ORDER BY orderid;
+    ";
+    let results = sqlx::query_as::<_, OrderCustomer>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[OrderCustomer]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let orderids: Vec<i32> = iter.clone().map(|r| r.orderid).collect();
+    let customers: Vec<String> = iter.clone().map(|r| r.customer.to_string()).collect();
+    let statuses: Vec<Option<String>> = iter.clone().map(|r| r.status.clone()).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("orderid".into(), orderids).into(),
+        Series::new("customer".into(), customers).into(),
+        Series::new("status".into(), statuses).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_order_payloads = get_df_order_payloads(db.get_postgres_connection_pool())
+        .await?
+        .lazy();
+    let df = df_order_payloads
+        .with_column(col("payload").str().json_decode(order_payload_dtype()))
+        .filter(
+            col("payload")
+                .struct_()
+                .field_by_name("customer")
+                .struct_()
+                .field_by_name("country")
+                .eq(lit("USA")),
+        )
+        .select([
+            col("orderid"),
+            col("payload").struct_().field_by_name("customer"),
+            col("payload").struct_().field_by_name("status"),
+        ])
+        .sort(["orderid"], SortMultipleOptions::default())
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+
+    reset_order_payloads(&db_sqlx).await?;
+
+    let df = polars_query(&db_sea_orm).await?;
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: the JSON of sqlx is text, it is decoded to the columns of Polars to compare.
+    if compare_vecs(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && diff_dfs(&df, &get_df_results(&sqlx_results)?)?.is_none()
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use polars::prelude::*;
+use sea_orm::sea_query::{Alias, Expr, Order, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection, FromQueryResult};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_vecs, diff_dfs};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sandbox::{get_df_order_payloads, order_payload_dtype};
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_order_payloads;
+
+/*
+# TASK:
+
+Group the orders by customer first name, collecting the orders of each customer as a JSON
+array of objects with their `orderid` and `status`, by order id.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    payload->'customer'->>'firstname' AS firstname,
+    jsonb_agg(
+        jsonb_build_object('orderid', orderid, 'status', payload->>'status')
+        ORDER BY orderid
+    ) AS orders
+FROM sandbox.order_payloads
+GROUP BY payload->'customer'->>'firstname'
+ORDER BY firstname;
+*/
+
+/*
+shape: (4, 2)
+┌───────────┬─────────────────────────────────┐
+│ firstname ┆ orders                          │
+│ ---       ┆ ---                             │
+│ str       ┆ list[struct[2]]                 │
+╞═══════════╪═════════════════════════════════╡
+│ Jossef    ┆ [{3,"Delivered"}, {4,"Shipped"… │
+│ Kevin     ┆ [{1,"Delivered"}, {5,"Delivere… │
+│ Mark      ┆ [{8,"Shipped"}]                 │
+│ Mary      ┆ [{2,"Shipped"}, {6,"Delivered"… │
+└───────────┴─────────────────────────────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, FromQueryResult, FromRow)]
+pub struct CustomerOrders {
+    firstname: Option<String>,
+    orders: serde_json::Value,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<CustomerOrders>> {
+    // Note: sea-query has no jsonb_agg helper, the aggregate is a custom expression.
+    let firstname = Expr::cust("payload->'customer'->>'firstname'");
+    let query = Query::select()
+        .expr_as(firstname.clone(), Alias::new("firstname"))
+        .expr_as(
+            Expr::cust(
+                "jsonb_agg(
+                    jsonb_build_object('orderid', orderid, 'status', payload->>'status')
+                    ORDER BY orderid
+                )",
+            ),
+            Alias::new("orders"),
+        )
+        .from((Alias::new("sandbox"), Alias::new("order_payloads")))
+        .add_group_by([firstname])
+        .order_by(Alias::new("firstname"), Order::Asc)
+        .to_owned();
+    let results = CustomerOrders::find_by_statement(db.get_database_backend().build(&query))
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<CustomerOrders>> {
+    let query = "
+    SELECT
+        payload->'customer'->>'firstname' AS firstname,
+        jsonb_agg(
+            jsonb_build_object('orderid', orderid, 'status', payload->>'status')
+            ORDER BY orderid
+        ) AS orders
+    FROM sandbox.order_payloads
+    GROUP BY payload->'customer'->>'firstname'
+    ORDER BY firstname;
+    ";
+    let results = sqlx::query_as::<_, CustomerOrders>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[CustomerOrders]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let firstnames: Vec<Option<String>> = iter.clone().map(|r| r.firstname.clone()).collect();
+    let orders: Vec<String> = iter.clone().map(|r| r.orders.to_string()).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("firstname".into(), firstnames).into(),
+        Series::new("orders".into(), orders).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_order_payloads = get_df_order_payloads(db.get_postgres_connection_pool())
+        .await?
+        .lazy();
+    // Note: jsonb_build_object maps to a struct, jsonb_agg to a list of structs.
+    let df = df_order_payloads
+        .with_column(col("payload").str().json_decode(order_payload_dtype()))
+        .sort(["orderid"], SortMultipleOptions::default())
+        .group_by_stable([col("payload")
+            .struct_()
+            .field_by_name("customer")
+            .struct_()
+            .field_by_name("firstname")])
+        .agg([as_struct(vec![
+            col("orderid"),
+            col("payload").struct_().field_by_name("status"),
+        ])
+        .alias("orders")])
+        .sort(["firstname"], SortMultipleOptions::default())
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+
+    reset_order_payloads(&db_sqlx).await?;
+
+    let df = polars_query(&db_sea_orm).await?;
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: the JSON of sqlx is text, it is decoded to the columns of Polars to compare.
+    if compare_vecs(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && diff_dfs(&df, &get_df_results(&sqlx_results)?)?.is_none()
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
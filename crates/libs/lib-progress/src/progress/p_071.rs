@@ -0,0 +1,161 @@
+use polars::prelude::*;
+use sea_orm::{DatabaseConnection, DbBackend, FromQueryResult, Statement};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_vecs, diff_dfs};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sandbox::{get_df_order_payloads, order_payload_dtype};
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_order_payloads;
+
+/*
+# TASK:
+
+Expand the items of each order and compute the quantity and the revenue, quantity times
+price, sold per product.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    item->>'product' AS product,
+    SUM((item->>'quantity')::INT) AS quantity,
+    SUM((item->>'quantity')::INT * (item->>'price')::INT) AS revenue
+FROM sandbox.order_payloads
+CROSS JOIN LATERAL jsonb_array_elements(payload->'items') AS item
+GROUP BY item->>'product'
+ORDER BY product;
+*/
+
+/*
+shape: (4, 3)
+┌─────────┬──────────┬─────────┐
+│ product ┆ quantity ┆ revenue │
+│ ---     ┆ ---      ┆ ---     │
+│ str     ┆ i64      ┆ i64     │
+╞═════════╪══════════╪═════════╡
+│ Bottle  ┆ 8        ┆ 80      │
+│ Caps    ┆ 3        ┆ 75      │
+│ Gloves  ┆ 2        ┆ 60      │
+│ Tire    ┆ 3        ┆ 45      │
+└─────────┴──────────┴─────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct ProductQuantity {
+    product: Option<String>,
+    quantity: Option<i64>,
+    revenue: Option<i64>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<ProductQuantity>> {
+    // Note: jsonb_array_elements is a set-returning function, sea-orm runs it as a raw statement.
+    let query = "
+    SELECT
+        item->>'product' AS product,
+        SUM((item->>'quantity')::INT) AS quantity,
+        SUM((item->>'quantity')::INT * (item->>'price')::INT) AS revenue
+    FROM sandbox.order_payloads
+    CROSS JOIN LATERAL jsonb_array_elements(payload->'items') AS item
+    GROUP BY item->>'product'
+    ORDER BY product;
+    ";
+    let results =
+        ProductQuantity::find_by_statement(Statement::from_string(DbBackend::Postgres, query))
+            .all(db)
+            .await
+            .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<ProductQuantity>> {
+    let query = "
+    SELECT
+        item->>'product' AS product,
+        SUM((item->>'quantity')::INT) AS quantity,
+        SUM((item->>'quantity')::INT * (item->>'price')::INT) AS revenue
+    FROM sandbox.order_payloads
+    CROSS JOIN LATERAL jsonb_array_elements(payload->'items') AS item
+    GROUP BY item->>'product'
+    ORDER BY product;
+    ";
+    let results = sqlx::query_as::<_, ProductQuantity>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[ProductQuantity]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let products: Vec<Option<String>> = iter.clone().map(|r| r.product.clone()).collect();
+    let quantities: Vec<Option<i64>> = iter.clone().map(|r| r.quantity).collect();
+    let revenues: Vec<Option<i64>> = iter.clone().map(|r| r.revenue).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("product".into(), products).into(),
+        Series::new("quantity".into(), quantities).into(),
+        Series::new("revenue".into(), revenues).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_order_payloads = get_df_order_payloads(db.get_postgres_connection_pool())
+        .await?
+        .lazy();
+    // Note: explode is the Polars counterpart of jsonb_array_elements, one row per item.
+    let df = df_order_payloads
+        .select([col("payload")
+            .str()
+            .json_decode(order_payload_dtype())
+            .struct_()
+            .field_by_name("items")])
+        .explode(cols(["items"]))
+        .unnest(cols(["items"]))
+        .group_by([col("product")])
+        .agg([
+            col("quantity").sum().cast(DataType::Int64),
+            (col("quantity") * col("price"))
+                .sum()
+                .cast(DataType::Int64)
+                .alias("revenue"),
+        ])
+        .sort(["product"], SortMultipleOptions::default())
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+
+    reset_order_payloads(&db_sqlx).await?;
+
+    let df = polars_query(&db_sea_orm).await?;
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    // Note: the JSON of sqlx is text, it is decoded to the columns of Polars to compare.
+    if compare_vecs(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && diff_dfs(&df, &get_df_results(&sqlx_results)?)?.is_none()
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
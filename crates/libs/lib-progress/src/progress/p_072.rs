@@ -0,0 +1,203 @@
+use polars::prelude::*;
+use sea_orm::sea_query::extension::postgres::PgExpr;
+use sea_orm::sea_query::{Alias, Expr, Order, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection, FromQueryResult};
+use sqlx::Pool;
+use sqlx::prelude::FromRow;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::compare::{compare_vecs, diff_dfs};
+use crate::utils::database::get_database;
+use crate::utils::dataframe::sandbox::{get_df_order_payloads, order_payload_dtype};
+use crate::utils::debug::log_debug;
+use crate::utils::sandbox::reset_order_payloads;
+
+/*
+# TASK:
+
+Retrieve the id and the status of the orders whose payload contains a customer from
+Germany, by order id.
+*/
+
+/*
+# QUERY:
+
+SELECT
+    orderid,
+    payload->>'status' AS status
+FROM sandbox.order_payloads
+WHERE payload @> '{"customer": {"country": "Germany"}}'
+ORDER BY orderid;
+*/
+
+/*
+# PLAN (enable_seqscan = off, enable_indexscan = off):
+
+Sort
+  Sort Key: orderid
+  ->  Bitmap Heap Scan on order_payloads
+        Recheck Cond: (payload @> '{"customer": {"country": "Germany"}}'::jsonb)
+        ->  Bitmap Index Scan on idx_order_payloads_payload
+              Index Cond: (payload @> '{"customer": {"country": "Germany"}}'::jsonb)
+*/
+
+/*
+shape: (4, 2)
+┌─────────┬───────────┐
+│ orderid ┆ status    │
+│ ---     ┆ ---       │
+│ i32     ┆ str       │
+╞═════════╪═══════════╡
+│ 3       ┆ Delivered │
+│ 4       ┆ Shipped   │
+│ 7       ┆ Delivered │
+│ 8       ┆ Shipped   │
+└─────────┴───────────┘
+*/
+
+const DEBUG: bool = false;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, FromRow, Hash)]
+pub struct OrderStatus {
+    orderid: i32,
+    status: Option<String>,
+}
+
+pub async fn sea_orm_query(db: &DatabaseConnection) -> AppResult<Vec<OrderStatus>> {
+    // Note: `@>` is containment, the only operator here that the GIN index can serve.
+    let payload = Alias::new("payload");
+    let query = Query::select()
+        .column(Alias::new("orderid"))
+        .expr_as(
+            Expr::col(payload.clone()).cast_json_field("status"),
+            Alias::new("status"),
+        )
+        .from((Alias::new("sandbox"), Alias::new("order_payloads")))
+        .and_where(Expr::col(payload).contains(Expr::cust(
+            r#"'{"customer": {"country": "Germany"}}'::JSONB"#,
+        )))
+        .order_by(Alias::new("orderid"), Order::Asc)
+        .to_owned();
+    let results = OrderStatus::find_by_statement(db.get_database_backend().build(&query))
+        .all(db)
+        .await
+        .map_err(AppError::SeaOrm)?;
+
+    log_debug("SEA ORM", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+pub async fn sqlx_query(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<OrderStatus>> {
+    let query = r#"
+    SELECT
+        orderid,
+        payload->>'status' AS status
+    FROM sandbox.order_payloads
+    WHERE payload @> '{"customer": {"country": "Germany"}}'
+    ORDER BY orderid;
+    "#;
+    let results = sqlx::query_as::<_, OrderStatus>(query)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    log_debug("SQLX", &results, Some(DEBUG));
+
+    Ok(results)
+}
+
+async fn sqlx_plan(db: &Pool<sqlx::Postgres>) -> AppResult<Vec<String>> {
+    let query = r#"
+    EXPLAIN (COSTS OFF)
+    SELECT
+        orderid,
+        payload->>'status' AS status
+    FROM sandbox.order_payloads
+    WHERE payload @> '{"customer": {"country": "Germany"}}'
+    ORDER BY orderid;
+    "#;
+    let mut conn = db.acquire().await.map_err(AppError::Sqlx)?;
+
+    // Note: ten rows fit in one page, without this the planner rightly prefers a seq scan
+    // or walks the primary key to skip the sort.
+    for setting in ["SET enable_seqscan = off", "SET enable_indexscan = off"] {
+        sqlx::query(setting)
+            .execute(&mut *conn)
+            .await
+            .map_err(AppError::Sqlx)?;
+    }
+
+    let results = sqlx::query_scalar::<_, String>(query)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    sqlx::query("RESET ALL")
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(results)
+}
+
+fn get_df_results(results: &[OrderStatus]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let orderids: Vec<i32> = iter.clone().map(|r| r.orderid).collect();
+    let statuses: Vec<Option<String>> = iter.clone().map(|r| r.status.clone()).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("orderid".into(), orderids).into(),
+        Series::new("status".into(), statuses).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn polars_query(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let df_order_payloads = get_df_order_payloads(db.get_postgres_connection_pool())
+        .await?
+        .lazy();
+    // Note: Polars has no index, the filter always decodes and scans every payload.
+    let df = df_order_payloads
+        .with_column(col("payload").str().json_decode(order_payload_dtype()))
+        .filter(
+            col("payload")
+                .struct_()
+                .field_by_name("customer")
+                .struct_()
+                .field_by_name("country")
+                .eq(lit("Germany")),
+        )
+        .select([
+            col("orderid"),
+            col("payload").struct_().field_by_name("status"),
+        ])
+        .sort(["orderid"], SortMultipleOptions::default())
+        .collect()
+        .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub async fn display_table() -> AppResult<()> {
+    let (db_sea_orm, db_sqlx) = get_database().await?;
+
+    reset_order_payloads(&db_sqlx).await?;
+
+    let df = polars_query(&db_sea_orm).await?;
+    let sqlx_results = sqlx_query(&db_sqlx).await?;
+
+    log_debug("PLAN", &sqlx_plan(&db_sqlx).await?, Some(true));
+
+    // Note: the JSON of sqlx is text, it is decoded to the columns of Polars to compare.
+    if compare_vecs(&sea_orm_query(&db_sea_orm).await?, &sqlx_results)
+        && diff_dfs(&df, &get_df_results(&sqlx_results)?)?.is_none()
+    {
+        log_debug("POLARS", &df, None);
+    }
+
+    Ok(())
+}
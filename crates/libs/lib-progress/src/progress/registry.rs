@@ -0,0 +1,312 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use polars::prelude::*;
+use sea_orm::DatabaseConnection;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::AppResult;
+
+use crate::utils::compare::compare_vecs_any_order;
+use crate::utils::sandbox::Sandbox;
+
+pub type PolarsQuery<'a> = Pin<Box<dyn Future<Output = AppResult<DataFrame>> + 'a>>;
+// Note: only the number of rows is returned, every lesson has its own result struct.
+pub type SeaOrmQuery<'a> = Pin<Box<dyn Future<Output = AppResult<usize>> + 'a>>;
+pub type SqlxQuery<'a> = Pin<Box<dyn Future<Output = AppResult<usize>> + 'a>>;
+pub type CompareQuery<'a> = Pin<Box<dyn Future<Output = AppResult<bool>> + 'a>>;
+pub type DisplayTable = Pin<Box<dyn Future<Output = AppResult<()>>>>;
+
+pub struct Lesson {
+    pub id: &'static str,
+    pub source: &'static str,
+    pub display_table: fn() -> DisplayTable,
+    pub polars_query: Option<fn(&DatabaseConnection) -> PolarsQuery<'_>>,
+    pub sea_orm_query: Option<fn(&DatabaseConnection) -> SeaOrmQuery<'_>>,
+    pub sqlx_query: Option<fn(&Pool<Postgres>) -> SqlxQuery<'_>>,
+    // Runs the SeaORM and the sqlx query and compares their rows, in any order.
+    pub compare_sea_orm:
+        Option<for<'a> fn(&'a DatabaseConnection, &'a Pool<Postgres>) -> CompareQuery<'a>>,
+    // The sandbox table the queries read, `display_table` creates it on its own.
+    pub sandbox: Option<Sandbox>,
+    // The SeaORM query loads related rows, its statements depend on the rows it gets.
+    pub loader: bool,
+}
+
+impl Lesson {
+    pub fn chapter(&self) -> &'static str {
+        CHAPTERS
+            .iter()
+            .find(|(_, first, last)| (*first..=*last).contains(&self.id))
+            .map(|(title, _, _)| *title)
+            .unwrap_or("Other")
+    }
+
+    pub fn dataset(&self) -> &'static str {
+        DATASETS
+            .iter()
+            .find(|(_, first, last)| (*first..=*last).contains(&self.id))
+            .map(|(dataset, _, _)| *dataset)
+            .unwrap_or("MyDatabase")
+    }
+
+    // The SQL of the `# QUERY:` comment at the top of the lesson.
+    pub fn query(&self) -> Option<String> {
+        self.comment("# QUERY:")
+    }
+
+    // The exercise of the `# TASK:` comment, in one line.
+    pub fn task(&self) -> Option<String> {
+        let task = self.comment("# TASK:")?;
+
+        Some(task.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    fn comment(&self, header: &str) -> Option<String> {
+        let start = self.source.find(header)? + header.len();
+        let end = start + self.source[start..].find("*/")?;
+        let comment = self.source[start..end]
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(comment.trim().to_string())
+    }
+
+    // The SQL string literal executed by `sqlx_query`.
+    pub fn sqlx_sql(&self) -> Option<String> {
+        let start = self.source.find("fn sqlx_query(")?;
+        let start = start + self.source[start..].find("let query = ")? + "let query = ".len();
+        let rest = &self.source[start..];
+        let (rest, delimiter) = match rest.strip_prefix("r#\"") {
+            Some(rest) => (rest, "\"#"),
+            None => (rest.strip_prefix('"')?, "\""),
+        };
+        let query = &rest[..rest.find(delimiter)?];
+
+        // Note: the indentation of the Rust code is removed, as in the `# QUERY:` comment.
+        let indent = query
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let query = query
+            .lines()
+            .map(|line| line.get(indent..).unwrap_or(line.trim_start()).trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(query.trim().to_string())
+    }
+
+    // The source of `polars_query`, up to the closing brace of the function.
+    pub fn polars_code(&self) -> Option<&'static str> {
+        let start = self.source.find("pub async fn polars_query(")?;
+        let end = start + self.source[start..].find("\n}")? + "\n}".len();
+
+        Some(&self.source[start..end])
+    }
+}
+
+// Chapters of the course, with the first and the last lesson of each.
+const CHAPTERS: [(&str, &str, &str); 12] = [
+    ("Querying data", "p_001", "p_015"),
+    ("Filtering data", "p_016", "p_027"),
+    ("Joins", "p_028", "p_035"),
+    ("Sales dataset", "p_036", "p_041"),
+    ("Set operators", "p_042", "p_046"),
+    ("String functions", "p_047", "p_048"),
+    ("Grouping sets", "p_049", "p_051"),
+    ("Pivoting", "p_052", "p_054"),
+    ("Loading related rows", "p_055", "p_059"),
+    ("Self joins", "p_060", "p_062"),
+    ("Transactions", "p_063", "p_068"),
+    ("JSONB", "p_069", "p_072"),
+];
+
+// Databases of the lessons, see `docker/`.
+const DATASETS: [(&str, &str, &str); 2] = [
+    ("MyDatabase", "p_001", "p_035"),
+    ("salesdb", "p_036", "p_072"),
+];
+
+macro_rules! lesson {
+    ($id:ident) => {
+        Lesson {
+            id: stringify!($id),
+            source: include_str!(concat!(stringify!($id), ".rs")),
+            display_table: {
+                fn display_table() -> DisplayTable {
+                    Box::pin(super::$id::display_table())
+                }
+
+                display_table
+            },
+            polars_query: None,
+            sea_orm_query: None,
+            sqlx_query: None,
+            compare_sea_orm: None,
+            sandbox: None,
+            loader: false,
+        }
+    };
+    ($id:ident, loader, $($engine:ident),+) => {{
+        let mut lesson = lesson!($id, $($engine),+);
+        lesson.loader = true;
+        lesson
+    }};
+    ($id:ident, sandbox = $sandbox:ident, $($engine:ident),+) => {{
+        let mut lesson = lesson!($id, $($engine),+);
+        lesson.sandbox = Some(Sandbox::$sandbox);
+        lesson
+    }};
+    // Note: a lesson with both SeaORM and sqlx compares their results.
+    ($id:ident, polars, sea_orm, sqlx) => {
+        lesson!($id, polars, sea_orm, sqlx, compare)
+    };
+    ($id:ident, sea_orm, sqlx) => {
+        lesson!($id, sea_orm, sqlx, compare)
+    };
+    ($id:ident, $($engine:ident),+) => {{
+        let mut lesson = lesson!($id);
+        $(lesson!(@$engine lesson, $id);)+
+        lesson
+    }};
+    (@polars $lesson:ident, $id:ident) => {{
+        fn polars_query(db: &DatabaseConnection) -> PolarsQuery<'_> {
+            Box::pin(super::$id::polars_query(db))
+        }
+
+        $lesson.polars_query = Some(polars_query);
+    }};
+    (@sea_orm $lesson:ident, $id:ident) => {{
+        fn sea_orm_query(db: &DatabaseConnection) -> SeaOrmQuery<'_> {
+            Box::pin(async move { Ok(super::$id::sea_orm_query(db).await?.len()) })
+        }
+
+        $lesson.sea_orm_query = Some(sea_orm_query);
+    }};
+    (@sqlx $lesson:ident, $id:ident) => {{
+        fn sqlx_query(db: &Pool<Postgres>) -> SqlxQuery<'_> {
+            Box::pin(async move { Ok(super::$id::sqlx_query(db).await?.len()) })
+        }
+
+        $lesson.sqlx_query = Some(sqlx_query);
+    }};
+    (@compare $lesson:ident, $id:ident) => {{
+        fn compare_sea_orm<'a>(
+            db_sea_orm: &'a DatabaseConnection,
+            db_sqlx: &'a Pool<Postgres>,
+        ) -> CompareQuery<'a> {
+            Box::pin(async move {
+                let sea_orm = super::$id::sea_orm_query(db_sea_orm).await?;
+                let sqlx = super::$id::sqlx_query(db_sqlx).await?;
+
+                Ok(compare_vecs_any_order(&sea_orm, &sqlx))
+            })
+        }
+
+        $lesson.compare_sea_orm = Some(compare_sea_orm);
+    }};
+}
+
+pub fn get_lessons() -> Vec<Lesson> {
+    vec![
+        lesson!(p_001, polars, sea_orm, sqlx),
+        lesson!(p_002, polars, sea_orm, sqlx),
+        lesson!(p_003, polars, sea_orm, sqlx),
+        lesson!(p_004, polars, sea_orm, sqlx),
+        lesson!(p_005, polars, sea_orm, sqlx),
+        lesson!(p_006, polars, sea_orm, sqlx),
+        lesson!(p_007, polars, sea_orm, sqlx),
+        lesson!(p_008, polars, sea_orm, sqlx),
+        lesson!(p_009, polars, sea_orm, sqlx),
+        lesson!(p_010, polars, sea_orm, sqlx),
+        lesson!(p_011, polars, sea_orm, sqlx),
+        lesson!(p_012, polars, sea_orm, sqlx),
+        lesson!(p_013, polars, sea_orm, sqlx),
+        lesson!(p_014, polars, sea_orm, sqlx),
+        lesson!(p_015, polars, sea_orm, sqlx),
+        lesson!(p_016, polars, sea_orm, sqlx),
+        lesson!(p_017, polars, sea_orm, sqlx),
+        lesson!(p_018, polars, sea_orm, sqlx),
+        lesson!(p_019, polars, sea_orm, sqlx),
+        lesson!(p_020, polars, sea_orm, sqlx),
+        lesson!(p_021, polars, sea_orm, sqlx),
+        lesson!(p_022, polars, sea_orm, sqlx),
+        lesson!(p_023, polars, sea_orm, sqlx),
+        lesson!(p_024, polars, sea_orm, sqlx),
+        lesson!(p_025, polars, sea_orm, sqlx),
+        lesson!(p_026, polars, sea_orm, sqlx),
+        lesson!(p_027, polars, sea_orm, sqlx),
+        lesson!(p_028, polars, sea_orm, sqlx),
+        lesson!(p_029, polars, sea_orm, sqlx),
+        lesson!(p_030, polars, sea_orm, sqlx),
+        lesson!(p_031, polars, sea_orm, sqlx),
+        lesson!(p_032, polars, sea_orm, sqlx),
+        lesson!(p_033, polars, sea_orm, sqlx),
+        lesson!(p_034, polars, sea_orm, sqlx),
+        lesson!(p_035, polars),
+        lesson!(p_036, polars, sea_orm, sqlx),
+        lesson!(p_037, polars, sea_orm, sqlx),
+        lesson!(p_038, polars, sea_orm, sqlx),
+        lesson!(p_039, polars, sea_orm, sqlx),
+        lesson!(p_040, polars, sea_orm, sqlx),
+        lesson!(p_041, polars, sea_orm, sqlx),
+        lesson!(p_042, polars, sea_orm, sqlx),
+        lesson!(p_043, polars, sea_orm, sqlx),
+        lesson!(p_044, polars, sea_orm, sqlx),
+        lesson!(p_045, polars, sea_orm, sqlx),
+        lesson!(p_046, polars, sea_orm, sqlx),
+        lesson!(p_047, polars, sea_orm, sqlx),
+        lesson!(p_048, polars, sea_orm, sqlx),
+        lesson!(p_049, polars, sea_orm, sqlx),
+        lesson!(p_050, polars, sea_orm, sqlx),
+        lesson!(p_051, polars, sea_orm, sqlx),
+        lesson!(p_052, polars, sea_orm, sqlx),
+        lesson!(p_053, polars, sea_orm, sqlx),
+        lesson!(p_054, polars, sea_orm, sqlx),
+        lesson!(p_055, polars, sea_orm, sqlx),
+        lesson!(p_056, polars, sea_orm, sqlx),
+        lesson!(p_057, polars, sea_orm, sqlx),
+        lesson!(p_058, loader, polars, sea_orm, sqlx),
+        lesson!(p_059, loader, polars, sea_orm, sqlx),
+        lesson!(p_060, polars, sea_orm, sqlx),
+        lesson!(p_061, polars, sea_orm, sqlx),
+        lesson!(p_062, polars, sea_orm, sqlx),
+        lesson!(p_063),
+        lesson!(p_064),
+        lesson!(p_065),
+        lesson!(p_066),
+        lesson!(p_067),
+        lesson!(p_068),
+        lesson!(p_069, sandbox = OrderPayloads, polars, sea_orm, sqlx),
+        lesson!(p_070, sandbox = OrderPayloads, polars, sea_orm, sqlx),
+        lesson!(p_071, sandbox = OrderPayloads, polars, sea_orm, sqlx),
+        lesson!(p_072, sandbox = OrderPayloads, polars, sea_orm, sqlx),
+    ]
+}
+
+// Creates the sandbox tables the lessons of a dataset read, so their queries and the catalog
+// find them on a fresh database.
+pub async fn reset_sandboxes(db: &Pool<Postgres>, dataset: &str) -> AppResult<()> {
+    let mut sandboxes = Vec::new();
+
+    for lesson in get_lessons() {
+        if lesson.dataset() == dataset
+            && let Some(sandbox) = lesson.sandbox
+            && !sandboxes.contains(&sandbox)
+        {
+            sandbox.reset(db).await?;
+            sandboxes.push(sandbox);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_lesson(id: &str) -> Option<Lesson> {
+    get_lessons().into_iter().find(|lesson| lesson.id == id)
+}
@@ -0,0 +1,290 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use polars::prelude::*;
+use sea_orm::DatabaseConnection;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, PolarsQuery, get_lesson, get_lessons};
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::{self, get_df_copy_table, sales, scan_table};
+use crate::utils::debug::log_debug;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+// Counts the allocations of the process, a binary opts in with `#[global_allocator]`.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+type Loader = fn(&DatabaseConnection) -> PolarsQuery<'_>;
+
+// Note: a loader is found in the Polars code by its call, e.g. `get_df_orders(` or
+// `scan_table(db, "customers")`, a scan is timed as a `collect` of the whole table.
+macro_rules! loader {
+    ($dataset:literal, $table:literal, scan_table) => {{
+        fn load(db: &DatabaseConnection) -> PolarsQuery<'_> {
+            Box::pin(async move {
+                scan_table(db, $table)
+                    .await?
+                    .collect()
+                    .map_err(AppError::Polars)
+            })
+        }
+
+        (
+            $dataset,
+            concat!("scan_table(db, \"", $table, "\")"),
+            load as Loader,
+            $table,
+        )
+    }};
+    ($dataset:literal, $table:literal, $module:ident :: $name:ident) => {{
+        fn load(db: &DatabaseConnection) -> PolarsQuery<'_> {
+            Box::pin($module::$name(db))
+        }
+
+        (
+            $dataset,
+            concat!(stringify!($name), "("),
+            load as Loader,
+            $table,
+        )
+    }};
+}
+
+// The full-table loads the Polars pipelines start with and their table, by dataset.
+fn get_loaders() -> Vec<(&'static str, &'static str, Loader, &'static str)> {
+    vec![
+        loader!("MyDatabase", "customers", dataframe::get_df_customers),
+        loader!("MyDatabase", "orders", dataframe::get_df_orders),
+        loader!("MyDatabase", "customers", scan_table),
+        loader!("MyDatabase", "orders", scan_table),
+        loader!("salesdb", "sales.customers", sales::get_df_customers),
+        loader!("salesdb", "sales.employees", sales::get_df_employees),
+        loader!("salesdb", "sales.orders", sales::get_df_orders),
+        loader!(
+            "salesdb",
+            "sales.ordersarchive",
+            sales::get_df_ordersarchive
+        ),
+        loader!("salesdb", "sales.products", sales::get_df_products),
+        loader!("salesdb", "sales.customers", scan_table),
+        loader!("salesdb", "sales.employees", scan_table),
+        loader!("salesdb", "sales.orders", scan_table),
+        loader!("salesdb", "sales.ordersarchive", scan_table),
+        loader!("salesdb", "sales.products", scan_table),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub duration: Duration,
+    pub allocations: usize,
+    pub allocated: usize,
+}
+
+pub struct Benchmark {
+    pub lesson: &'static str,
+    pub engine: &'static str,
+    pub samples: Vec<Sample>,
+}
+
+// Nearest-rank percentile of values sorted in ascending order.
+fn percentile<T: Copy>(sorted: &[T], percent: usize) -> T {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+
+    sorted[rank - 1]
+}
+
+impl Benchmark {
+    fn durations(&self) -> Vec<Duration> {
+        let mut durations: Vec<Duration> = self.samples.iter().map(|s| s.duration).collect();
+        durations.sort();
+        durations
+    }
+
+    pub fn median(&self) -> Duration {
+        percentile(&self.durations(), 50)
+    }
+
+    pub fn p95(&self) -> Duration {
+        percentile(&self.durations(), 95)
+    }
+
+    // Note: the allocations of a run barely change, the median is reported.
+    pub fn allocations(&self) -> (usize, usize) {
+        let mut allocations: Vec<usize> = self.samples.iter().map(|s| s.allocations).collect();
+        let mut allocated: Vec<usize> = self.samples.iter().map(|s| s.allocated).collect();
+        allocations.sort();
+        allocated.sort();
+
+        (percentile(&allocations, 50), percentile(&allocated, 50))
+    }
+}
+
+// Note: the first run warms up the pool and the statement cache and is not sampled.
+async fn measure<F, Fut, T>(runs: usize, mut run: F) -> AppResult<Vec<Sample>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AppResult<T>>,
+{
+    let mut samples = Vec::with_capacity(runs);
+
+    run().await?;
+
+    for _ in 0..runs {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        let start = Instant::now();
+
+        run().await?;
+
+        samples.push(Sample {
+            duration: start.elapsed(),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        });
+    }
+
+    Ok(samples)
+}
+
+// Times the SeaORM, sqlx and Polars queries of the lesson, and apart from the pipeline
+// the full-table loads Polars starts with, through the models and with a binary COPY.
+pub async fn bench_lesson(
+    db_sea_orm: &DatabaseConnection,
+    db_sqlx: &Pool<Postgres>,
+    lesson: &Lesson,
+    runs: usize,
+) -> AppResult<Vec<Benchmark>> {
+    let mut benchmarks = Vec::new();
+    let mut push = |engine, samples| {
+        benchmarks.push(Benchmark {
+            lesson: lesson.id,
+            engine,
+            samples,
+        })
+    };
+
+    if let Some(sea_orm_query) = lesson.sea_orm_query {
+        push(
+            "sea_orm",
+            measure(runs, || sea_orm_query(db_sea_orm)).await?,
+        );
+    }
+    if let Some(sqlx_query) = lesson.sqlx_query {
+        push("sqlx", measure(runs, || sqlx_query(db_sqlx)).await?);
+    }
+    if let Some(polars_query) = lesson.polars_query {
+        push("polars", measure(runs, || polars_query(db_sea_orm)).await?);
+
+        let code = lesson.polars_code().unwrap_or_default();
+        let loaders: Vec<_> = get_loaders()
+            .into_iter()
+            .filter(|(dataset, call, _, _)| *dataset == lesson.dataset() && code.contains(call))
+            .map(|(_, _, load, table)| (load, table))
+            .collect();
+
+        if !loaders.is_empty() {
+            let samples = measure(runs, || async {
+                for (load, _) in &loaders {
+                    load(db_sea_orm).await?;
+                }
+                AppResult::Ok(())
+            })
+            .await?;
+
+            push("polars_load", samples);
+
+            let samples = measure(runs, || async {
+                for (_, table) in &loaders {
+                    get_df_copy_table(db_sqlx, table).await?;
+                }
+                AppResult::Ok(())
+            })
+            .await?;
+
+            push("copy_load", samples);
+        }
+    }
+
+    Ok(benchmarks)
+}
+
+pub fn get_df_benchmarks(benchmarks: &[Benchmark]) -> AppResult<DataFrame> {
+    let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let iter = benchmarks.iter();
+
+    let df = df!(
+        "lesson" => iter.clone().map(|b| b.lesson).collect::<Vec<_>>(),
+        "engine" => iter.clone().map(|b| b.engine).collect::<Vec<_>>(),
+        "runs" => iter.clone().map(|b| b.samples.len() as u32).collect::<Vec<_>>(),
+        "median_ms" => iter.clone().map(|b| millis(b.median())).collect::<Vec<_>>(),
+        "p95_ms" => iter.clone().map(|b| millis(b.p95())).collect::<Vec<_>>(),
+        "allocations" => iter.clone().map(|b| b.allocations().0 as u64).collect::<Vec<_>>(),
+        "allocated_kb" => iter.clone().map(|b| b.allocations().1 as u64 / 1024).collect::<Vec<_>>(),
+    )
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+// Benchmarks the given lessons, or every lesson when there is none, each on its dataset.
+// Note: `sales_dataset` replaces `salesdb`, e.g. to run on the data of `generate`.
+pub async fn run_benchmarks(
+    ids: &[&str],
+    runs: usize,
+    sales_dataset: Option<&str>,
+) -> AppResult<DataFrame> {
+    let lessons = if ids.is_empty() {
+        get_lessons()
+    } else {
+        ids.iter()
+            .map(|id| get_lesson(id).ok_or_else(|| AppError::Assertion(format!("no lesson {id}"))))
+            .collect::<AppResult<Vec<_>>>()?
+    };
+    let mut databases: HashMap<&str, (DatabaseConnection, Pool<Postgres>)> = HashMap::new();
+    let mut benchmarks = Vec::new();
+
+    for lesson in &lessons {
+        let dataset = match sales_dataset {
+            Some(dataset) if lesson.dataset() == "salesdb" => dataset,
+            _ => lesson.dataset(),
+        };
+        let (db_sea_orm, db_sqlx) = match databases.entry(dataset) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(get_database_dataset(dataset).await?),
+        };
+
+        let lesson_benchmarks = bench_lesson(db_sea_orm, db_sqlx, lesson, runs).await?;
+
+        log_debug(lesson.id, &get_df_benchmarks(&lesson_benchmarks)?, None);
+        benchmarks.extend(lesson_benchmarks);
+    }
+
+    get_df_benchmarks(&benchmarks)
+}
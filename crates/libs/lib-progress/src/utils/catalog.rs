@@ -0,0 +1,275 @@
+use polars::prelude::*;
+use sqlx::{Column, Executor, Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::Lesson;
+use crate::utils::dataframe::{get_column_type, quote_table};
+use crate::utils::transpiler::{get_table_names, parse_query};
+
+// Note: the schemas the lessons read, `public` of MyDatabase, `sales` of salesdb and
+// `sandbox`, which `get_database_dataset` creates for the lessons of the dataset.
+const SCHEMAS: &[&str] = &["public", "sales", "sandbox"];
+
+const TABLES: &str = "
+SELECT table_schema::text, table_name::text,
+    obj_description(format('%I.%I', table_schema, table_name)::regclass, 'pg_class')
+FROM information_schema.tables
+WHERE table_schema::text = ANY($1)
+ORDER BY table_schema, table_name;
+";
+
+const COLUMNS: &str = "
+SELECT table_schema::text, table_name::text, column_name::text, data_type::text,
+    is_nullable::text = 'YES', column_default::text,
+    col_description(format('%I.%I', table_schema, table_name)::regclass, ordinal_position::int)
+FROM information_schema.columns
+WHERE table_schema::text = ANY($1)
+ORDER BY table_schema, table_name, ordinal_position;
+";
+
+// The schema, table, column, type, nullability, default and comment of a row of `COLUMNS`.
+type ColumnRow = (
+    String,
+    String,
+    String,
+    String,
+    bool,
+    Option<String>,
+    Option<String>,
+);
+
+const PRIMARY_KEYS: &str = "
+SELECT k.table_schema::text, k.table_name::text, k.column_name::text
+FROM information_schema.table_constraints c
+JOIN information_schema.key_column_usage k
+    ON k.constraint_schema = c.constraint_schema
+    AND k.constraint_name = c.constraint_name
+    AND k.table_name = c.table_name
+WHERE c.constraint_type = 'PRIMARY KEY' AND k.table_schema::text = ANY($1)
+ORDER BY k.table_schema, k.table_name, k.ordinal_position;
+";
+
+// Note: the referenced column of each column of a foreign key is the one at the same
+// position in the primary key or unique constraint it references.
+const FOREIGN_KEYS: &str = "
+SELECT k.constraint_name::text, k.table_schema::text, k.table_name::text, k.column_name::text,
+    u.table_schema::text, u.table_name::text, u.column_name::text
+FROM information_schema.referential_constraints r
+JOIN information_schema.key_column_usage k
+    ON k.constraint_schema = r.constraint_schema
+    AND k.constraint_name = r.constraint_name
+JOIN information_schema.key_column_usage u
+    ON u.constraint_schema = r.unique_constraint_schema
+    AND u.constraint_name = r.unique_constraint_name
+    AND u.ordinal_position = k.position_in_unique_constraint
+WHERE k.table_schema::text = ANY($1)
+ORDER BY k.table_schema, k.table_name, k.constraint_name, k.ordinal_position;
+";
+
+#[derive(Clone, Debug)]
+pub struct CatalogColumn {
+    pub name: String,
+    // The type as `information_schema` spells it, e.g. `character varying`.
+    pub data_type: String,
+    pub nullable: bool,
+    // The default expression, e.g. `nextval('orders_id_seq'::regclass)` for a serial.
+    pub default: Option<String>,
+    // `None` when no Polars type is known for the Postgres type.
+    pub dtype: Option<DataType>,
+    pub comment: Option<String>,
+}
+
+// A foreign key, `columns` of `table` reference `foreign_columns` of `foreign_table`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relation {
+    pub name: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub foreign_table: String,
+    pub foreign_columns: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Table {
+    // Named as the lessons name it, `sales.orders`, or `customers` for a table of `public`.
+    pub name: String,
+    pub columns: Vec<CatalogColumn>,
+    pub primary_key: Vec<String>,
+    pub comment: Option<String>,
+}
+
+impl Table {
+    // The schema of the DataFrame the loaders read the table into.
+    pub fn schema(&self) -> AppResult<Schema> {
+        self.columns
+            .iter()
+            .map(|column| {
+                let dtype = column.dtype.clone().ok_or_else(|| {
+                    AppError::Assertion(format!(
+                        "no Polars type for the column {} of {} of type {}",
+                        column.name, self.name, column.data_type
+                    ))
+                })?;
+
+                Ok(Field::new(column.name.as_str().into(), dtype))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    pub tables: Vec<Table>,
+    pub relations: Vec<Relation>,
+}
+
+impl Catalog {
+    // Note: names are matched as Postgres matches unquoted names, `public.` is optional.
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        let name = name.to_lowercase();
+        let name = name.strip_prefix("public.").unwrap_or(&name);
+
+        self.tables.iter().find(|table| table.name == name)
+    }
+}
+
+fn get_table_name(schema: &str, table: &str) -> String {
+    match schema {
+        "public" => table.to_string(),
+        _ => format!("{schema}.{table}"),
+    }
+}
+
+// Note: the dtypes come from the columns of a `SELECT *`, the same mapping the loaders use.
+async fn get_dtypes(
+    db: &Pool<Postgres>,
+    table: &str,
+) -> AppResult<Vec<(String, Option<DataType>)>> {
+    let describe = db
+        .describe(&format!("SELECT * FROM {}", quote_table(table)))
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(describe
+        .columns()
+        .iter()
+        .map(|column| {
+            let dtype = get_column_type(column.type_info()).ok();
+
+            (column.name().to_string(), dtype.map(|dtype| dtype.dtype()))
+        })
+        .collect())
+}
+
+// The tables of the `public` and `sales` schemas of the database, with their columns, keys
+// and comments.
+pub async fn get_catalog(db: &Pool<Postgres>) -> AppResult<Catalog> {
+    let tables: Vec<(String, String, Option<String>)> = sqlx::query_as(TABLES)
+        .bind(SCHEMAS)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+    let columns: Vec<ColumnRow> = sqlx::query_as(COLUMNS)
+        .bind(SCHEMAS)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+    let primary_keys: Vec<(String, String, String)> = sqlx::query_as(PRIMARY_KEYS)
+        .bind(SCHEMAS)
+        .fetch_all(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+    let foreign_keys: Vec<(String, String, String, String, String, String, String)> =
+        sqlx::query_as(FOREIGN_KEYS)
+            .bind(SCHEMAS)
+            .fetch_all(db)
+            .await
+            .map_err(AppError::Sqlx)?;
+
+    let mut catalog = Catalog::default();
+
+    for (schema, name, comment) in tables {
+        let name = get_table_name(&schema, &name);
+        let dtypes = get_dtypes(db, &name).await?;
+        let columns = columns
+            .iter()
+            .filter(|column| get_table_name(&column.0, &column.1) == name)
+            .map(
+                |(_, _, column, data_type, nullable, default, comment)| CatalogColumn {
+                    name: column.clone(),
+                    data_type: data_type.clone(),
+                    nullable: *nullable,
+                    default: default.clone(),
+                    dtype: dtypes
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .and_then(|(_, dtype)| dtype.clone()),
+                    comment: comment.clone(),
+                },
+            )
+            .collect();
+        let primary_key = primary_keys
+            .iter()
+            .filter(|key| get_table_name(&key.0, &key.1) == name)
+            .map(|key| key.2.clone())
+            .collect();
+
+        catalog.tables.push(Table {
+            name,
+            columns,
+            primary_key,
+            comment,
+        });
+    }
+
+    for (constraint, schema, table, column, foreign_schema, foreign_table, foreign_column) in
+        foreign_keys
+    {
+        let table = get_table_name(&schema, &table);
+
+        match catalog
+            .relations
+            .iter_mut()
+            .find(|relation| relation.name == constraint && relation.table == table)
+        {
+            Some(relation) => {
+                relation.columns.push(column);
+                relation.foreign_columns.push(foreign_column);
+            }
+            None => catalog.relations.push(Relation {
+                name: constraint,
+                table,
+                columns: vec![column],
+                foreign_table: get_table_name(&foreign_schema, &foreign_table),
+                foreign_columns: vec![foreign_column],
+            }),
+        }
+    }
+
+    Ok(catalog)
+}
+
+// Fails when the lesson reads a table the database does not have, e.g. a lesson of salesdb
+// run on MyDatabase. Tables of `sandbox` are created by the lessons that read them.
+pub fn check_tables(catalog: &Catalog, lesson: &Lesson) -> AppResult<()> {
+    let Some(query) = lesson.query() else {
+        return Ok(());
+    };
+    let query = parse_query(&query)?;
+    let missing: Vec<String> = get_table_names(&query)
+        .into_iter()
+        .filter(|name| !name.starts_with("sandbox.") && catalog.table(name).is_none())
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::Assertion(format!(
+        "{} reads {}, which this database does not have, the lesson runs on {}",
+        lesson.id,
+        missing.join(", "),
+        lesson.dataset()
+    )))
+}
@@ -0,0 +1,566 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use lib_core::connection::get_db_sqlx_dataset;
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::catalog::{CatalogColumn, Relation, Table, get_catalog};
+use crate::utils::dataframe::PgType;
+use crate::utils::debug::log_debug;
+
+const GENERATED: &str = "@generated by `cargo run --bin codegen`";
+
+// Note: a generated file in the directories that the generator no longer writes is removed.
+struct Target {
+    schema: &'static str,
+    module: &'static str,
+    entities: &'static str,
+    loaders: &'static str,
+}
+
+const PUBLIC: Target = Target {
+    schema: "public",
+    module: "database",
+    entities: "crates/libs/lib-data/src/database",
+    loaders: "crates/libs/lib-progress/src/utils/dataframe/public",
+};
+
+const SALES: Target = Target {
+    schema: "sales",
+    module: "database_sales",
+    entities: "crates/libs/lib-data/src/database_sales",
+    loaders: "crates/libs/lib-progress/src/utils/dataframe/sales",
+};
+
+// The `information_schema` types, by the Postgres type a column of them is loaded as.
+const PG_TYPES: &[(&str, PgType)] = &[
+    ("boolean", PgType::Bool),
+    ("smallint", PgType::Int2),
+    ("integer", PgType::Int4),
+    ("bigint", PgType::Int8),
+    ("real", PgType::Float4),
+    ("double precision", PgType::Float8),
+    ("numeric", PgType::Numeric),
+    ("character varying", PgType::Text),
+    ("character", PgType::Text),
+    ("text", PgType::Text),
+    ("uuid", PgType::Uuid),
+    ("json", PgType::Json),
+    ("jsonb", PgType::Jsonb),
+    ("date", PgType::Date),
+    ("timestamp without time zone", PgType::Timestamp),
+    ("timestamp with time zone", PgType::Timestamptz),
+];
+
+// The type of the entity field and the type of the values of the loader column.
+// Note: a numeric, a UUID, JSON and a timestamp with a time zone need features of SeaORM
+// the entities do not enable.
+fn get_rust_types(pg_type: PgType) -> Option<(&'static str, &'static str)> {
+    match pg_type {
+        PgType::Bool => Some(("bool", "bool")),
+        PgType::Int2 => Some(("i16", "i16")),
+        PgType::Int4 => Some(("i32", "i32")),
+        PgType::Int8 => Some(("i64", "i64")),
+        PgType::Float4 => Some(("f32", "f32")),
+        PgType::Float8 => Some(("f64", "f64")),
+        PgType::Text => Some(("String", "String")),
+        PgType::Date => Some(("Date", "NaiveDate")),
+        PgType::Timestamp => Some(("DateTime", "NaiveDateTime")),
+        PgType::Numeric | PgType::Uuid | PgType::Json | PgType::Jsonb | PgType::Timestamptz => None,
+    }
+}
+
+// Note: the names the entities and loaders had before they were generated, split in words
+// where the table name is not, e.g. the relation `OrdersArchive` and the loader
+// `orders_archive` of `ordersarchive`.
+const WORDS: &[(&str, &str)] = &[("ordersarchive", "orders_archive")];
+
+fn get_words(module: &str) -> &str {
+    WORDS
+        .iter()
+        .find(|(name, _)| *name == module)
+        .map_or(module, |(_, words)| words)
+}
+
+struct Field<'a> {
+    column: &'a CatalogColumn,
+    entity_type: &'static str,
+    value_type: &'static str,
+    pg_type: PgType,
+}
+
+impl Field<'_> {
+    fn wrap(&self, rust_type: &str) -> String {
+        match self.column.nullable {
+            true => format!("Option<{rust_type}>"),
+            false => rust_type.to_string(),
+        }
+    }
+
+    // Note: `String` is the only type of `get_rust_types` that is not `Copy`.
+    fn value(&self) -> String {
+        match self.entity_type {
+            "String" => format!("row.{}.clone()", self.column.name),
+            _ => format!("row.{}", self.column.name),
+        }
+    }
+}
+
+// A variant of the `Relation` enum of an entity.
+struct EntityRelation {
+    name: String,
+    entity: String,
+    attribute: String,
+}
+
+fn get_module(table: &str) -> &str {
+    table.rsplit('.').next().unwrap_or(table)
+}
+
+// Note: `first_name` is `FirstName` and `customerid` is `Customerid`, as SeaORM names the
+// variants of `Column`.
+fn get_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn get_columns(entity: &str, columns: &[String]) -> String {
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|column| format!("{entity}Column::{}", get_camel_case(column)))
+        .collect();
+
+    match columns.as_slice() {
+        [column] => column.clone(),
+        _ => format!("({})", columns.join(", ")),
+    }
+}
+
+fn get_fields(table: &Table) -> AppResult<Vec<Field<'_>>> {
+    table
+        .columns
+        .iter()
+        .map(|column| {
+            let (pg_type, (entity_type, value_type)) = PG_TYPES
+                .iter()
+                .find(|(data_type, _)| *data_type == column.data_type)
+                .and_then(|(_, pg_type)| Some((*pg_type, get_rust_types(*pg_type)?)))
+                .ok_or_else(|| {
+                    AppError::Assertion(format!(
+                        "no Rust type for the column {} of {} of type {}",
+                        column.name, table.name, column.data_type
+                    ))
+                })?;
+
+            Ok(Field {
+                column,
+                entity_type,
+                value_type,
+                pg_type,
+            })
+        })
+        .collect()
+}
+
+// The relations of a table, `has_many` for the foreign keys that reference it and
+// `belongs_to` for its own. A foreign key to the table itself is named after its column,
+// `managerid` is `Manager`, as are several foreign keys to the same table.
+fn get_relations(table: &Table, relations: &[&Relation]) -> Vec<EntityRelation> {
+    let count = |from: &str, to: &str| {
+        relations
+            .iter()
+            .filter(|relation| relation.table == from && relation.foreign_table == to)
+            .count()
+    };
+    let mut entity_relations = Vec::new();
+
+    // Note: several foreign keys of one table to this one make `has_many` ambiguous.
+    for relation in relations {
+        if relation.foreign_table == table.name
+            && relation.table != table.name
+            && count(&relation.table, &table.name) == 1
+        {
+            let module = get_module(&relation.table);
+
+            entity_relations.push(EntityRelation {
+                name: get_camel_case(get_words(module)),
+                entity: format!("super::{module}::Entity"),
+                attribute: format!("has_many = \"super::{module}::Entity\""),
+            });
+        }
+    }
+
+    for relation in relations
+        .iter()
+        .filter(|relation| relation.table == table.name)
+    {
+        let (entity, prefix) = match relation.foreign_table == table.name {
+            true => ("Entity".to_string(), String::new()),
+            false => {
+                let module = get_module(&relation.foreign_table);
+
+                (
+                    format!("super::{module}::Entity"),
+                    format!("super::{module}::"),
+                )
+            }
+        };
+        let name = match relation.foreign_table != table.name
+            && count(&table.name, &relation.foreign_table) == 1
+        {
+            true => get_camel_case(get_words(get_module(&relation.foreign_table))),
+            false => {
+                let column = relation.columns.join("_");
+                let column = column.strip_suffix("id").unwrap_or(&column);
+
+                get_camel_case(column.trim_end_matches('_'))
+            }
+        };
+
+        entity_relations.push(EntityRelation {
+            name,
+            attribute: format!(
+                "belongs_to = \"{entity}\", from = \"{}\", to = \"{}\"",
+                get_columns("", &relation.columns),
+                get_columns(&prefix, &relation.foreign_columns)
+            ),
+            entity,
+        });
+    }
+
+    entity_relations
+}
+
+fn get_entity(table: &Table, fields: &[Field], relations: &[EntityRelation]) -> String {
+    let mut code = format!("//! `SeaORM` Entity, {GENERATED}\n\n");
+    let eq = match fields
+        .iter()
+        .any(|field| matches!(field.entity_type, "f32" | "f64"))
+    {
+        true => "",
+        false => ", Eq",
+    };
+
+    code.push_str("use sea_orm::entity::prelude::*;\nuse sqlx::prelude::FromRow;\n\n");
+    code.push_str(&format!(
+        "#[derive(Clone, Debug, PartialEq, DeriveEntityModel{eq}, FromRow)]\n"
+    ));
+    code.push_str(&match table.name.split_once('.') {
+        Some((schema, name)) => {
+            format!("#[sea_orm(schema_name = \"{schema}\", table_name = \"{name}\")]\n")
+        }
+        None => format!("#[sea_orm(table_name = \"{}\")]\n", table.name),
+    });
+    code.push_str("pub struct Model {\n");
+
+    // Note: only a single integer key with a sequence is generated by the database.
+    let auto_increment = match table.primary_key.as_slice() {
+        [key] => fields.iter().any(|field| {
+            &field.column.name == key
+                && matches!(field.entity_type, "i16" | "i32" | "i64")
+                && field
+                    .column
+                    .default
+                    .as_deref()
+                    .is_some_and(|default| default.starts_with("nextval("))
+        }),
+        _ => false,
+    };
+
+    for field in fields {
+        if table.primary_key.contains(&field.column.name) {
+            code.push_str(match auto_increment {
+                true => "    #[sea_orm(primary_key)]\n",
+                false => "    #[sea_orm(primary_key, auto_increment = false)]\n",
+            });
+        }
+
+        code.push_str(&format!(
+            "    pub {}: {},\n",
+            field.column.name,
+            field.wrap(field.entity_type)
+        ));
+    }
+
+    code.push_str("}\n\n#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n");
+
+    if relations.is_empty() {
+        code.push_str("pub enum Relation {}\n");
+    } else {
+        code.push_str("pub enum Relation {\n");
+
+        for relation in relations {
+            code.push_str(&format!(
+                "    #[sea_orm({})]\n    {},\n",
+                relation.attribute, relation.name
+            ));
+        }
+
+        code.push_str("}\n");
+    }
+
+    // Note: `Related` is implemented once per entity, so only for an entity with one relation.
+    for relation in relations {
+        let count = relations
+            .iter()
+            .filter(|other| other.entity == relation.entity)
+            .count();
+
+        if count == 1 {
+            code.push_str(&format!(
+                "\nimpl Related<{}> for Entity {{\n    fn to() -> RelationDef {{\n        Relation::{}.def()\n    }}\n}}\n",
+                relation.entity, relation.name
+            ));
+        }
+    }
+
+    code.push_str("\nimpl ActiveModelBehavior for ActiveModel {}\n");
+
+    code
+}
+
+fn get_loader(target: &Target, table: &Table, fields: &[Field]) -> String {
+    let module = get_module(&table.name);
+    let mut code = format!("//! Polars loader of `{}`, {GENERATED}\n\n", table.name);
+    let chrono: Vec<&str> = ["NaiveDate", "NaiveDateTime"]
+        .into_iter()
+        .filter(|value_type| fields.iter().any(|field| field.value_type == *value_type))
+        .collect();
+
+    match chrono.as_slice() {
+        [] => {}
+        [value_type] => code.push_str(&format!("use chrono::{value_type};\n")),
+        _ => code.push_str(&format!("use chrono::{{{}}};\n", chrono.join(", "))),
+    }
+
+    code.push_str(&format!(
+        "use futures::TryStreamExt;
+use polars::{{frame::DataFrame, prelude::NamedFrom, series::Series}};
+use sea_orm::{{DatabaseConnection, EntityTrait}};
+
+use lib_core::error::{{AppError, AppResult}};
+use lib_data::{}::{module};
+
+use crate::utils::dataframe::{{PgType, cast_column, get_df_stream}};
+
+pub async fn get_df_{module}(db: &DatabaseConnection) -> AppResult<DataFrame> {{
+    let stream = {module}::Entity::find()
+        .stream(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}}
+
+fn get_df_batch(data: &[{module}::Model]) -> AppResult<DataFrame> {{
+    let iter = data.iter();
+",
+        target.module
+    ));
+
+    for field in fields {
+        code.push_str(&format!(
+            "    let {}: Vec<{}> = iter.clone().map(|row| {}).collect();\n",
+            field.column.name,
+            field.wrap(field.value_type),
+            field.value()
+        ));
+    }
+
+    code.push_str("\n    let df = DataFrame::new(vec![\n");
+
+    for field in fields {
+        code.push_str(&format!(
+            "        cast_column(Series::new(\"{0}\".into(), {0}), PgType::{1:?})?,\n",
+            field.column.name, field.pg_type
+        ));
+    }
+
+    code.push_str("    ])\n    .map_err(AppError::Polars)?;\n\n    Ok(df)\n}\n");
+
+    code
+}
+
+fn get_entity_mod(modules: &[&str]) -> (String, String) {
+    let mut code = format!("//! `SeaORM` Entity, {GENERATED}\n\npub mod prelude;\n\n");
+    let mut prelude = format!("//! `SeaORM` Entity, {GENERATED}\n\n");
+
+    for module in modules {
+        code.push_str(&format!("pub mod {module};\n"));
+        prelude.push_str(&format!(
+            "pub use super::{module}::Entity as {};\n",
+            get_camel_case(module)
+        ));
+    }
+
+    (code, prelude)
+}
+
+fn get_loader_mod(target: &Target, modules: &[&str]) -> String {
+    let mut code = format!(
+        "//! Polars loaders of the `{}` tables, {GENERATED}\n\n",
+        target.schema
+    );
+
+    for module in modules {
+        code.push_str(&format!("mod {};\n", get_words(module)));
+    }
+
+    code.push('\n');
+
+    for module in modules {
+        code.push_str(&format!(
+            "pub use {}::get_df_{module};\n",
+            get_words(module)
+        ));
+    }
+
+    code
+}
+
+// Note: the generator writes the code unformatted, rustfmt formats it as `cargo fmt` would.
+fn write_files(root: &Path, files: &BTreeMap<PathBuf, String>) -> AppResult<()> {
+    for (path, code) in files {
+        let path = root.join(path);
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(AppError::Io)?;
+        }
+
+        std::fs::write(&path, code).map_err(AppError::Io)?;
+    }
+
+    let status = Command::new("rustfmt")
+        .args(["--edition", "2024"])
+        .args(files.keys().map(|path| root.join(path)))
+        .status()
+        .map_err(AppError::Io)?;
+
+    if !status.success() {
+        return Err(AppError::Assertion(format!(
+            "rustfmt failed on the generated code, {status}"
+        )));
+    }
+
+    Ok(())
+}
+
+// Note: a file written by hand has no `@generated` header, it is kept.
+fn is_generated(path: &Path) -> AppResult<bool> {
+    let code = std::fs::read_to_string(path).map_err(AppError::Io)?;
+
+    Ok(code
+        .lines()
+        .next()
+        .is_some_and(|line| line.starts_with("//!") && line.contains("@generated")))
+}
+
+fn remove_files(root: &Path, dir: &str, files: &BTreeMap<PathBuf, String>) -> AppResult<()> {
+    for entry in std::fs::read_dir(root.join(dir)).map_err(AppError::Io)? {
+        let path = entry.map_err(AppError::Io)?.path();
+        let written = path
+            .strip_prefix(root)
+            .is_ok_and(|path| files.contains_key(path));
+
+        if path.extension().is_some_and(|extension| extension == "rs")
+            && !written
+            && is_generated(&path)?
+        {
+            log_debug("Removed", &path, None);
+            std::fs::remove_file(&path).map_err(AppError::Io)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn generate_target(root: &Path, target: &Target, dataset: &str) -> AppResult<()> {
+    let db = get_db_sqlx_dataset(dataset).await?;
+    let catalog = get_catalog(&db).await?;
+    let tables: Vec<&Table> = catalog
+        .tables
+        .iter()
+        .filter(|table| match table.name.split_once('.') {
+            Some((schema, _)) => schema == target.schema,
+            None => target.schema == "public",
+        })
+        .collect();
+    let relations: Vec<&Relation> = catalog
+        .relations
+        .iter()
+        .filter(|relation| {
+            tables.iter().any(|table| table.name == relation.table)
+                && tables
+                    .iter()
+                    .any(|table| table.name == relation.foreign_table)
+        })
+        .collect();
+
+    let mut files = BTreeMap::new();
+    let mut modules = Vec::new();
+
+    for table in &tables {
+        // Note: SeaORM needs a primary key to find the rows of a table.
+        if table.primary_key.is_empty() {
+            log_debug("Skipped, no primary key", &table.name, None);
+            continue;
+        }
+
+        let module = get_module(&table.name);
+        let fields = get_fields(table)?;
+        let entity_relations = get_relations(table, &relations);
+
+        files.insert(
+            Path::new(target.entities).join(format!("{module}.rs")),
+            get_entity(table, &fields, &entity_relations),
+        );
+        files.insert(
+            Path::new(target.loaders).join(format!("{}.rs", get_words(module))),
+            get_loader(target, table, &fields),
+        );
+        modules.push(module);
+    }
+
+    if modules.is_empty() {
+        return Err(AppError::Assertion(format!(
+            "{dataset} has no table with a primary key in the schema {}",
+            target.schema
+        )));
+    }
+
+    let (entity_mod, prelude) = get_entity_mod(&modules);
+
+    files.insert(Path::new(target.entities).join("mod.rs"), entity_mod);
+    files.insert(Path::new(target.entities).join("prelude.rs"), prelude);
+    files.insert(
+        Path::new(target.loaders).join("mod.rs"),
+        get_loader_mod(target, &modules),
+    );
+
+    write_files(root, &files)?;
+    remove_files(root, target.entities, &files)?;
+    remove_files(root, target.loaders, &files)?;
+
+    log_debug(target.schema, &files.keys().collect::<Vec<_>>(), Some(true));
+
+    Ok(())
+}
+
+// Writes the SeaORM entities of `lib_data` and their `get_df_*` loaders from the tables of
+// the `public` schema of one dataset and the `sales` schema of another, `root` is the
+// workspace. The entities derive `FromRow` for sqlx and the relations follow the foreign keys.
+pub async fn generate_code(root: &Path, public: &str, sales: &str) -> AppResult<()> {
+    generate_target(root, &PUBLIC, public).await?;
+    generate_target(root, &SALES, sales).await?;
+
+    Ok(())
+}
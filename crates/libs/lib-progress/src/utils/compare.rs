@@ -1,13 +1,125 @@
 use std::collections::HashSet;
 use std::hash::Hash;
 
+use polars::prelude::*;
+
+use lib_core::error::{AppError, AppResult};
+
 pub fn compare_vecs<T: PartialEq>(vec1: &[T], vec2: &[T]) -> bool {
     vec1.len() == vec2.len() && vec1.iter().zip(vec2.iter()).all(|(a, b)| a == b)
 }
 
+// Note: each row is matched with one row of the other, in any order, with only `PartialEq`.
+pub fn compare_vecs_any_order<T: PartialEq>(vec1: &[T], vec2: &[T]) -> bool {
+    let mut unmatched: Vec<&T> = vec2.iter().collect();
+
+    vec1.len() == vec2.len()
+        && vec1.iter().all(
+            |row| match unmatched.iter().position(|other| row == *other) {
+                Some(index) => {
+                    unmatched.swap_remove(index);
+                    true
+                }
+                None => false,
+            },
+        )
+}
+
 pub fn compare_vecs_unordered<T: Eq + Hash + Clone>(vec1: &[T], vec2: &[T]) -> bool {
     let set1: HashSet<_> = vec1.iter().cloned().collect();
     let set2: HashSet<_> = vec2.iter().cloned().collect();
 
     set1 == set2
 }
+
+pub fn compare_dfs_unordered(df1: &DataFrame, df2: &DataFrame) -> AppResult<bool> {
+    if df1.get_column_names() != df2.get_column_names() || df1.height() != df2.height() {
+        return Ok(false);
+    }
+
+    let by = df1.get_column_names_owned();
+    let options = SortMultipleOptions::default().with_nulls_last(true);
+    let sorted1 = df1
+        .sort(by.clone(), options.clone())
+        .map_err(AppError::Polars)?;
+    let sorted2 = df2.sort(by, options).map_err(AppError::Polars)?;
+
+    Ok(sorted1.equals_missing(&sorted2))
+}
+
+pub fn compare_dfs_by_columns(df1: &DataFrame, df2: &DataFrame) -> AppResult<bool> {
+    let mut names1 = df1.get_column_names();
+    let mut names2 = df2.get_column_names();
+
+    names1.sort();
+    names2.sort();
+
+    if names1 != names2 {
+        return Ok(false);
+    }
+
+    let df2 = df2
+        .select(df1.get_column_names_owned())
+        .map_err(AppError::Polars)?;
+
+    compare_dfs_unordered(df1, &df2)
+}
+
+// Note: Postgres gives JSON as text, it is decoded to the struct or list an engine built.
+fn decode_json(df: &DataFrame, expected: &DataFrame) -> AppResult<DataFrame> {
+    let decodes: Vec<Expr> = expected
+        .get_columns()
+        .iter()
+        .zip(df.get_columns())
+        .map(
+            |(expected, column)| match (expected.dtype(), column.dtype()) {
+                (DataType::String, dtype @ (DataType::Struct(_) | DataType::List(_))) => {
+                    col(expected.name().clone())
+                        .str()
+                        .json_decode(dtype.clone())
+                }
+                _ => col(expected.name().clone()),
+            },
+        )
+        .collect();
+
+    expected
+        .clone()
+        .lazy()
+        .select(decodes)
+        .collect()
+        .map_err(AppError::Polars)
+}
+
+// Note: engines pick their own integer widths, the columns are cast to the expected types
+// before the values are compared. Returns a description of the first difference found.
+pub fn diff_dfs(df: &DataFrame, expected: &DataFrame) -> AppResult<Option<String>> {
+    if df.get_column_names() != expected.get_column_names() {
+        return Ok(Some(format!(
+            "columns {:?} != {:?}",
+            df.get_column_names(),
+            expected.get_column_names()
+        )));
+    }
+
+    let expected = &match decode_json(df, expected) {
+        Ok(expected) => expected,
+        Err(err) => return Ok(Some(err.to_string())),
+    };
+
+    let casts: Vec<Expr> = expected
+        .get_columns()
+        .iter()
+        .map(|c| col(c.name().clone()).cast(c.dtype().clone()))
+        .collect();
+    let df = match df.clone().lazy().select(casts).collect() {
+        Ok(df) => df,
+        Err(err) => return Ok(Some(err.to_string())),
+    };
+
+    if compare_dfs_unordered(&df, expected)? {
+        Ok(None)
+    } else {
+        Ok(Some(format!("rows differ\n{df}\n{expected}")))
+    }
+}
@@ -1,13 +1,41 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use lib_core::{
-    connection::{get_db_sea_orm, get_db_sqlx},
+    connection::{get_db_sea_orm, get_db_sea_orm_dataset, get_db_sqlx, get_db_sqlx_dataset},
     error::AppResult,
 };
 use sea_orm::DatabaseConnection;
 use sqlx::{Pool, Postgres};
 
+use crate::progress::reset_sandboxes;
+
 pub async fn get_database() -> AppResult<(DatabaseConnection, Pool<Postgres>)> {
     let db_sea_orm = get_db_sea_orm().await?;
     let db_sqlx = get_db_sqlx().await?;
 
     Ok((db_sea_orm, db_sqlx))
 }
+
+// Note: the sandbox tables of the lessons of the dataset are created, the lessons run on it.
+pub async fn get_database_dataset(
+    dataset: &str,
+) -> AppResult<(DatabaseConnection, Pool<Postgres>)> {
+    let db_sea_orm = get_db_sea_orm_dataset(dataset).await?;
+    let db_sqlx = get_db_sqlx_dataset(dataset).await?;
+
+    reset_sandboxes(&db_sqlx, dataset).await?;
+
+    Ok((db_sea_orm, db_sqlx))
+}
+
+pub fn count_queries(db: &mut DatabaseConnection) -> Arc<AtomicUsize> {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let callback_counter = counter.clone();
+
+    db.set_metric_callback(move |_| {
+        callback_counter.fetch_add(1, Ordering::Relaxed);
+    });
+
+    counter
+}
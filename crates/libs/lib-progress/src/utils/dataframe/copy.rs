@@ -0,0 +1,291 @@
+use futures::TryStreamExt;
+use polars::prelude::*;
+use sqlx::{Executor, Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::dataframe::stream::BATCH_SIZE;
+use crate::utils::dataframe::types::{Builder, ColumnType, get_column_types, read};
+
+const SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+struct CopyReader {
+    columns: Vec<(PlSmallStr, Builder)>,
+    buffer: Vec<u8>,
+    header: bool,
+    rows: usize,
+    df: DataFrame,
+}
+
+impl CopyReader {
+    fn new(columns: Vec<(PlSmallStr, ColumnType)>) -> AppResult<Self> {
+        let columns = columns
+            .into_iter()
+            .map(|(name, column_type)| (name, Builder::new(column_type)))
+            .collect();
+        let mut reader = Self {
+            columns,
+            buffer: Vec::new(),
+            header: false,
+            rows: 0,
+            df: DataFrame::empty(),
+        };
+
+        // Note: the columns come from an empty batch, so an empty result still has its schema.
+        reader.df = reader.take_batch()?;
+
+        Ok(reader)
+    }
+
+    fn take_batch(&mut self) -> AppResult<DataFrame> {
+        let columns = self
+            .columns
+            .iter_mut()
+            .map(|(name, builder)| {
+                Series::from_arrow(name.clone(), builder.take()?)
+                    .map(Series::into_column)
+                    .map_err(AppError::Polars)
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        self.rows = 0;
+
+        DataFrame::new(columns).map_err(AppError::Polars)
+    }
+
+    fn flush(&mut self) -> AppResult<()> {
+        let batch = self.take_batch()?;
+
+        self.df.vstack_mut_owned(batch).map_err(AppError::Polars)?;
+
+        Ok(())
+    }
+
+    // Reads the header, then every complete row of the buffer, a row cut between two
+    // messages waits for the next one.
+    fn feed(&mut self, bytes: &[u8]) -> AppResult<()> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut offset = 0;
+
+        if !self.header {
+            let Some(extension) = self.buffer.get(15..19) else {
+                return Ok(());
+            };
+            let length = SIGNATURE.len() + 8 + u32::from_be_bytes(read(extension)?) as usize;
+
+            if !self.buffer.starts_with(SIGNATURE) {
+                return Err(AppError::Assertion("not a binary COPY".to_string()));
+            }
+            if self.buffer.len() < length {
+                return Ok(());
+            }
+
+            self.header = true;
+            offset = length;
+        }
+
+        while let Some(length) = self.row_length(offset)? {
+            self.push_row(offset)?;
+            offset += length;
+
+            if self.rows == BATCH_SIZE {
+                self.flush()?;
+            }
+        }
+
+        self.buffer.drain(..offset);
+
+        Ok(())
+    }
+
+    // The length of the row at `offset`, `None` until the whole row is in the buffer or for
+    // the trailer.
+    fn row_length(&self, offset: usize) -> AppResult<Option<usize>> {
+        let Some(count) = self.buffer.get(offset..offset + 2) else {
+            return Ok(None);
+        };
+        let count = i16::from_be_bytes(read(count)?);
+
+        if count == -1 {
+            return Ok(None);
+        }
+        if count as usize != self.columns.len() {
+            return Err(AppError::Assertion(format!(
+                "expected {} fields, got {count}",
+                self.columns.len()
+            )));
+        }
+
+        let mut length = 2;
+        for _ in 0..count {
+            let Some(field) = self.buffer.get(offset + length..offset + length + 4) else {
+                return Ok(None);
+            };
+            length += 4 + i32::from_be_bytes(read(field)?).max(0) as usize;
+        }
+
+        Ok((self.buffer.len() >= offset + length).then_some(length))
+    }
+
+    fn push_row(&mut self, offset: usize) -> AppResult<()> {
+        let mut position = offset + 2;
+
+        for (_, builder) in &mut self.columns {
+            let length = i32::from_be_bytes(read(&self.buffer[position..position + 4])?);
+            position += 4;
+
+            let value = if length < 0 {
+                None
+            } else {
+                let value = &self.buffer[position..position + length as usize];
+                position += length as usize;
+                Some(value)
+            };
+
+            builder.push_binary(value)?;
+        }
+
+        self.rows += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> AppResult<DataFrame> {
+        if self.rows > 0 {
+            self.flush()?;
+        }
+
+        Ok(self.df)
+    }
+}
+
+// Note: the binary format skips the text parsing of `get_df_query` and the models of the
+// `get_df_*` loaders, the values are copied into Arrow arrays as they arrive.
+pub async fn get_df_copy(db: &Pool<Postgres>, query: &str) -> AppResult<DataFrame> {
+    let query = query.trim().trim_end_matches(';');
+    let mut conn = db.acquire().await.map_err(AppError::Sqlx)?;
+    let describe = (&mut *conn).describe(query).await.map_err(AppError::Sqlx)?;
+    let mut reader = CopyReader::new(get_column_types(describe.columns())?)?;
+    let mut stream = conn
+        .copy_out_raw(&format!("COPY ({query}) TO STDOUT (FORMAT binary)"))
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    while let Some(bytes) = stream.try_next().await.map_err(AppError::Sqlx)? {
+        reader.feed(&bytes)?;
+    }
+
+    reader.finish()
+}
+
+pub(crate) fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Note: `sales.orders` is the table `orders` of the schema `sales`.
+pub(crate) fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+// Same as `get_df_copy` for every column of a table, e.g. `sales.orders`.
+pub async fn get_df_copy_table(db: &Pool<Postgres>, table: &str) -> AppResult<DataFrame> {
+    get_df_copy(db, &format!("SELECT * FROM {}", quote_table(table))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::dataframe::PgType;
+
+    // Two rows of an INT4, a TEXT and a TIMESTAMP column, the second has a null and the
+    // timestamp 2000-01-01, the epoch of Postgres.
+    fn get_copy() -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0, 3]);
+        bytes.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 7]);
+        bytes.extend_from_slice(&[0, 0, 0, 2, b'a', b'b']);
+        bytes.extend_from_slice(&[0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 1]);
+        bytes.extend_from_slice(&[0, 3]);
+        bytes.extend_from_slice(&[0, 0, 0, 4, 0xFF, 0xFF, 0xFF, 0xFE]);
+        bytes.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+        bytes.extend_from_slice(&[0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[0xFF, 0xFF]);
+
+        bytes
+    }
+
+    fn get_reader() -> AppResult<CopyReader> {
+        let column = |name: &str, pg_type| {
+            (
+                PlSmallStr::from(name),
+                ColumnType {
+                    pg_type,
+                    array: false,
+                },
+            )
+        };
+
+        CopyReader::new(vec![
+            column("id", PgType::Int4),
+            column("name", PgType::Text),
+            column("created", PgType::Timestamp),
+        ])
+    }
+
+    #[test]
+    fn copy_reader_decodes_rows_cut_anywhere() -> AppResult<()> {
+        let bytes = get_copy();
+        let expected = df!(
+            "id" => [7, -2],
+            "name" => [Some("ab"), None],
+            "created" => [946_684_800_000_001i64, 946_684_800_000_000],
+        )
+        .and_then(|df| {
+            df.lazy()
+                .with_column(col("created").cast(PgType::Timestamp.dtype()))
+                .collect()
+        })
+        .map_err(AppError::Polars)?;
+
+        // Note: a cut at 0 is one message, any other cut is a row or the header split in two.
+        for cut in 0..bytes.len() {
+            let mut reader = get_reader()?;
+
+            reader.feed(&bytes[..cut])?;
+            reader.feed(&bytes[cut..])?;
+
+            let df = reader.finish()?;
+            assert!(df.equals_missing(&expected), "cut at {cut}: {df}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy_reader_rejects_bad_input() -> AppResult<()> {
+        // Note: a wrong signature, a row of 2 fields and an INT4 of 2 bytes.
+        let cases: &[&[u8]] = &[
+            b"PGCOPY\n\xff\r\n\x01\0\0\0\0\0\0\0\0",
+            &[SIGNATURE, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 2]].concat(),
+            &[
+                SIGNATURE,
+                &[0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0],
+                &[0xFF, 0xFF, 0xFF, 0xFF],
+            ]
+            .concat(),
+        ];
+
+        for bytes in cases {
+            assert!(get_reader()?.feed(bytes).is_err(), "{bytes:?}");
+        }
+
+        Ok(())
+    }
+}
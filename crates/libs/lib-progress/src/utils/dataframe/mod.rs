@@ -1,6 +1,19 @@
-mod customers;
-mod orders;
+mod copy;
+mod public;
+mod query;
 pub mod sales;
+pub mod sandbox;
+mod scan;
+mod stream;
+mod types;
 
-pub use customers::get_df_customers;
-pub use orders::get_df_orders;
+pub(crate) use copy::quote_table;
+pub use copy::{get_df_copy, get_df_copy_table};
+// Note: the loaders of the `public` tables are the ones of `dataframe`, of `sales` the ones
+// of `dataframe::sales`.
+pub use public::*;
+pub use query::{get_df_query, get_df_query_conn};
+pub use scan::{TableScan, scan_table};
+pub use stream::get_df_stream;
+pub(crate) use types::cast_column;
+pub use types::{ColumnType, PgType, get_column_type};
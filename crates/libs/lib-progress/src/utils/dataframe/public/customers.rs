@@ -0,0 +1,38 @@
+//! Polars loader of `customers`, @generated by `cargo run --bin codegen`
+
+use futures::TryStreamExt;
+use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database::customers;
+
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
+pub async fn get_df_customers(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let stream = customers::Entity::find()
+        .stream(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
+
+fn get_df_batch(data: &[customers::Model]) -> AppResult<DataFrame> {
+    let iter = data.iter();
+    let id: Vec<i32> = iter.clone().map(|row| row.id).collect();
+    let first_name: Vec<String> = iter.clone().map(|row| row.first_name.clone()).collect();
+    let country: Vec<Option<String>> = iter.clone().map(|row| row.country.clone()).collect();
+    let score: Vec<Option<i32>> = iter.clone().map(|row| row.score).collect();
+
+    let df = DataFrame::new(vec![
+        cast_column(Series::new("id".into(), id), PgType::Int4)?,
+        cast_column(Series::new("first_name".into(), first_name), PgType::Text)?,
+        cast_column(Series::new("country".into(), country), PgType::Text)?,
+        cast_column(Series::new("score".into(), score), PgType::Int4)?,
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
@@ -0,0 +1,7 @@
+//! Polars loaders of the `public` tables, @generated by `cargo run --bin codegen`
+
+mod customers;
+mod orders;
+
+pub use customers::get_df_customers;
+pub use orders::get_df_orders;
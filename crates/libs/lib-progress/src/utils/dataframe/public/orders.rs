@@ -0,0 +1,39 @@
+//! Polars loader of `orders`, @generated by `cargo run --bin codegen`
+
+use chrono::NaiveDate;
+use futures::TryStreamExt;
+use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+use lib_core::error::{AppError, AppResult};
+use lib_data::database::orders;
+
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
+pub async fn get_df_orders(db: &DatabaseConnection) -> AppResult<DataFrame> {
+    let stream = orders::Entity::find()
+        .stream(db)
+        .await
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
+
+fn get_df_batch(data: &[orders::Model]) -> AppResult<DataFrame> {
+    let iter = data.iter();
+    let order_id: Vec<i32> = iter.clone().map(|row| row.order_id).collect();
+    let customer_id: Vec<i32> = iter.clone().map(|row| row.customer_id).collect();
+    let order_date: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.order_date).collect();
+    let sales: Vec<Option<i32>> = iter.clone().map(|row| row.sales).collect();
+
+    let df = DataFrame::new(vec![
+        cast_column(Series::new("order_id".into(), order_id), PgType::Int4)?,
+        cast_column(Series::new("customer_id".into(), customer_id), PgType::Int4)?,
+        cast_column(Series::new("order_date".into(), order_date), PgType::Date)?,
+        cast_column(Series::new("sales".into(), sales), PgType::Int4)?,
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
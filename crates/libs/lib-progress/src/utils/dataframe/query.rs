@@ -0,0 +1,49 @@
+use futures::TryStreamExt;
+use polars::prelude::*;
+use sqlx::postgres::PgRow;
+use sqlx::{Executor, PgConnection, Pool, Postgres, Row};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::dataframe::get_df_stream;
+use crate::utils::dataframe::types::{Builder, ColumnType, get_column_types};
+
+// Note: the simple query protocol returns every value as text, so any result can be read
+// without a struct and is then parsed into the Polars type of its Postgres column.
+pub async fn get_df_query(db: &Pool<Postgres>, query: &str) -> AppResult<DataFrame> {
+    let mut conn = db.acquire().await.map_err(AppError::Sqlx)?;
+
+    get_df_query_conn(&mut conn, query).await
+}
+
+// Same as `get_df_query` on a connection the caller holds, e.g. inside a transaction.
+pub async fn get_df_query_conn(conn: &mut PgConnection, query: &str) -> AppResult<DataFrame> {
+    let describe = (&mut *conn).describe(query).await.map_err(AppError::Sqlx)?;
+    let columns = get_column_types(describe.columns())?;
+    let stream = sqlx::raw_sql(query)
+        .fetch(&mut *conn)
+        .map_err(AppError::Sqlx);
+
+    get_df_stream(stream, |rows| get_df_rows(&columns, rows)).await
+}
+
+fn get_df_rows(columns: &[(PlSmallStr, ColumnType)], rows: &[PgRow]) -> AppResult<DataFrame> {
+    let mut series_list = Vec::with_capacity(columns.len());
+
+    for (index, (name, column_type)) in columns.iter().enumerate() {
+        let mut builder = Builder::new(*column_type);
+
+        for row in rows {
+            let value = row
+                .try_get_unchecked::<Option<&str>, _>(index)
+                .map_err(AppError::Sqlx)?;
+
+            builder.push_text(value)?;
+        }
+
+        let series = Series::from_arrow(name.clone(), builder.take()?).map_err(AppError::Polars)?;
+        series_list.push(series.into_column());
+    }
+
+    DataFrame::new(series_list).map_err(AppError::Polars)
+}
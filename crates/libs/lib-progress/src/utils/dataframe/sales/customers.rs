@@ -1,28 +1,38 @@
+//! Polars loader of `sales.customers`, @generated by `cargo run --bin codegen`
+
+use futures::TryStreamExt;
 use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 use lib_core::error::{AppError, AppResult};
 use lib_data::database_sales::customers;
 
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
 pub async fn get_df_customers(db: &DatabaseConnection) -> AppResult<DataFrame> {
-    let data = customers::Entity::find()
-        .all(db)
+    let stream = customers::Entity::find()
+        .stream(db)
         .await
-        .map_err(AppError::SeaOrm)?;
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
 
+fn get_df_batch(data: &[customers::Model]) -> AppResult<DataFrame> {
     let iter = data.iter();
-    let customerids: Vec<i32> = iter.clone().map(|c| c.customerid).collect();
-    let firstnames: Vec<Option<String>> = iter.clone().map(|c| c.firstname.clone()).collect();
-    let lastnames: Vec<Option<String>> = iter.clone().map(|c| c.lastname.clone()).collect();
-    let countries: Vec<Option<String>> = iter.clone().map(|c| c.country.clone()).collect();
-    let scores: Vec<Option<i32>> = iter.clone().map(|c| c.score).collect();
+    let customerid: Vec<i32> = iter.clone().map(|row| row.customerid).collect();
+    let firstname: Vec<Option<String>> = iter.clone().map(|row| row.firstname.clone()).collect();
+    let lastname: Vec<Option<String>> = iter.clone().map(|row| row.lastname.clone()).collect();
+    let country: Vec<Option<String>> = iter.clone().map(|row| row.country.clone()).collect();
+    let score: Vec<Option<i32>> = iter.clone().map(|row| row.score).collect();
 
     let df = DataFrame::new(vec![
-        Series::new("customerid".into(), customerids).into(),
-        Series::new("firstname".into(), firstnames).into(),
-        Series::new("lastname".into(), lastnames).into(),
-        Series::new("country".into(), countries).into(),
-        Series::new("score".into(), scores).into(),
+        cast_column(Series::new("customerid".into(), customerid), PgType::Int4)?,
+        cast_column(Series::new("firstname".into(), firstname), PgType::Text)?,
+        cast_column(Series::new("lastname".into(), lastname), PgType::Text)?,
+        cast_column(Series::new("country".into(), country), PgType::Text)?,
+        cast_column(Series::new("score".into(), score), PgType::Int4)?,
     ])
     .map_err(AppError::Polars)?;
 
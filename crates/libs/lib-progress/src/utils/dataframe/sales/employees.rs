@@ -1,35 +1,45 @@
+//! Polars loader of `sales.employees`, @generated by `cargo run --bin codegen`
+
 use chrono::NaiveDate;
+use futures::TryStreamExt;
 use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 use lib_core::error::{AppError, AppResult};
 use lib_data::database_sales::employees;
 
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
 pub async fn get_df_employees(db: &DatabaseConnection) -> AppResult<DataFrame> {
-    let data = employees::Entity::find()
-        .all(db)
+    let stream = employees::Entity::find()
+        .stream(db)
         .await
-        .map_err(AppError::SeaOrm)?;
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
 
+fn get_df_batch(data: &[employees::Model]) -> AppResult<DataFrame> {
     let iter = data.iter();
-    let employeeids: Vec<i32> = iter.clone().map(|e| e.employeeid).collect();
-    let firstnames: Vec<Option<String>> = iter.clone().map(|e| e.firstname.clone()).collect();
-    let lastnames: Vec<Option<String>> = iter.clone().map(|e| e.lastname.clone()).collect();
-    let departments: Vec<Option<String>> = iter.clone().map(|e| e.department.clone()).collect();
-    let birthdates: Vec<Option<NaiveDate>> = iter.clone().map(|e| e.birthdate).collect();
-    let genders: Vec<Option<String>> = iter.clone().map(|e| e.gender.clone()).collect();
-    let salaries: Vec<Option<i32>> = iter.clone().map(|e| e.salary).collect();
-    let managerids: Vec<Option<i32>> = iter.clone().map(|e| e.managerid).collect();
+    let employeeid: Vec<i32> = iter.clone().map(|row| row.employeeid).collect();
+    let firstname: Vec<Option<String>> = iter.clone().map(|row| row.firstname.clone()).collect();
+    let lastname: Vec<Option<String>> = iter.clone().map(|row| row.lastname.clone()).collect();
+    let department: Vec<Option<String>> = iter.clone().map(|row| row.department.clone()).collect();
+    let birthdate: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.birthdate).collect();
+    let gender: Vec<Option<String>> = iter.clone().map(|row| row.gender.clone()).collect();
+    let salary: Vec<Option<i32>> = iter.clone().map(|row| row.salary).collect();
+    let managerid: Vec<Option<i32>> = iter.clone().map(|row| row.managerid).collect();
 
     let df = DataFrame::new(vec![
-        Series::new("employeeid".into(), employeeids).into(),
-        Series::new("firstname".into(), firstnames).into(),
-        Series::new("lastname".into(), lastnames).into(),
-        Series::new("department".into(), departments).into(),
-        Series::new("birthdate".into(), birthdates).into(),
-        Series::new("gender".into(), genders).into(),
-        Series::new("salary".into(), salaries).into(),
-        Series::new("managerid".into(), managerids).into(),
+        cast_column(Series::new("employeeid".into(), employeeid), PgType::Int4)?,
+        cast_column(Series::new("firstname".into(), firstname), PgType::Text)?,
+        cast_column(Series::new("lastname".into(), lastname), PgType::Text)?,
+        cast_column(Series::new("department".into(), department), PgType::Text)?,
+        cast_column(Series::new("birthdate".into(), birthdate), PgType::Date)?,
+        cast_column(Series::new("gender".into(), gender), PgType::Text)?,
+        cast_column(Series::new("salary".into(), salary), PgType::Int4)?,
+        cast_column(Series::new("managerid".into(), managerid), PgType::Int4)?,
     ])
     .map_err(AppError::Polars)?;
 
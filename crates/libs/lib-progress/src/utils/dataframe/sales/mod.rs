@@ -1,3 +1,5 @@
+//! Polars loaders of the `sales` tables, @generated by `cargo run --bin codegen`
+
 mod customers;
 mod employees;
 mod orders;
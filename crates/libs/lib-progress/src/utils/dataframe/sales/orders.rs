@@ -1,43 +1,63 @@
+//! Polars loader of `sales.orders`, @generated by `cargo run --bin codegen`
+
 use chrono::{NaiveDate, NaiveDateTime};
+use futures::TryStreamExt;
 use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 use lib_core::error::{AppError, AppResult};
 use lib_data::database_sales::orders;
 
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
 pub async fn get_df_orders(db: &DatabaseConnection) -> AppResult<DataFrame> {
-    let data = orders::Entity::find()
-        .all(db)
+    let stream = orders::Entity::find()
+        .stream(db)
         .await
-        .map_err(AppError::SeaOrm)?;
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
 
+fn get_df_batch(data: &[orders::Model]) -> AppResult<DataFrame> {
     let iter = data.iter();
-    let orderids: Vec<i32> = iter.clone().map(|o| o.orderid).collect();
-    let productids: Vec<Option<i32>> = iter.clone().map(|o| o.productid).collect();
-    let customerids: Vec<Option<i32>> = iter.clone().map(|o| o.customerid).collect();
-    let salespersonids: Vec<Option<i32>> = iter.clone().map(|o| o.salespersonid).collect();
-    let orderdates: Vec<Option<NaiveDate>> = iter.clone().map(|o| o.orderdate).collect();
-    let shipdates: Vec<Option<NaiveDate>> = iter.clone().map(|o| o.shipdate).collect();
-    let orderstatuses: Vec<Option<String>> = iter.clone().map(|o| o.orderstatus.clone()).collect();
-    let shipaddresses: Vec<Option<String>> = iter.clone().map(|o| o.shipaddress.clone()).collect();
-    let billaddresses: Vec<Option<String>> = iter.clone().map(|o| o.billaddress.clone()).collect();
-    let quantities: Vec<Option<i32>> = iter.clone().map(|o| o.quantity).collect();
-    let sales: Vec<Option<i32>> = iter.clone().map(|o| o.sales).collect();
-    let creationtimes: Vec<Option<NaiveDateTime>> = iter.clone().map(|o| o.creationtime).collect();
+    let orderid: Vec<i32> = iter.clone().map(|row| row.orderid).collect();
+    let productid: Vec<Option<i32>> = iter.clone().map(|row| row.productid).collect();
+    let customerid: Vec<Option<i32>> = iter.clone().map(|row| row.customerid).collect();
+    let salespersonid: Vec<Option<i32>> = iter.clone().map(|row| row.salespersonid).collect();
+    let orderdate: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.orderdate).collect();
+    let shipdate: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.shipdate).collect();
+    let orderstatus: Vec<Option<String>> =
+        iter.clone().map(|row| row.orderstatus.clone()).collect();
+    let shipaddress: Vec<Option<String>> =
+        iter.clone().map(|row| row.shipaddress.clone()).collect();
+    let billaddress: Vec<Option<String>> =
+        iter.clone().map(|row| row.billaddress.clone()).collect();
+    let quantity: Vec<Option<i32>> = iter.clone().map(|row| row.quantity).collect();
+    let sales: Vec<Option<i32>> = iter.clone().map(|row| row.sales).collect();
+    let creationtime: Vec<Option<NaiveDateTime>> =
+        iter.clone().map(|row| row.creationtime).collect();
 
     let df = DataFrame::new(vec![
-        Series::new("orderid".into(), orderids).into(),
-        Series::new("productid".into(), productids).into(),
-        Series::new("customerid".into(), customerids).into(),
-        Series::new("salespersonid".into(), salespersonids).into(),
-        Series::new("orderdate".into(), orderdates).into(),
-        Series::new("shipdate".into(), shipdates).into(),
-        Series::new("orderstatus".into(), orderstatuses).into(),
-        Series::new("shipaddress".into(), shipaddresses).into(),
-        Series::new("billaddress".into(), billaddresses).into(),
-        Series::new("quantity".into(), quantities).into(),
-        Series::new("sales".into(), sales).into(),
-        Series::new("creationtime".into(), creationtimes).into(),
+        cast_column(Series::new("orderid".into(), orderid), PgType::Int4)?,
+        cast_column(Series::new("productid".into(), productid), PgType::Int4)?,
+        cast_column(Series::new("customerid".into(), customerid), PgType::Int4)?,
+        cast_column(
+            Series::new("salespersonid".into(), salespersonid),
+            PgType::Int4,
+        )?,
+        cast_column(Series::new("orderdate".into(), orderdate), PgType::Date)?,
+        cast_column(Series::new("shipdate".into(), shipdate), PgType::Date)?,
+        cast_column(Series::new("orderstatus".into(), orderstatus), PgType::Text)?,
+        cast_column(Series::new("shipaddress".into(), shipaddress), PgType::Text)?,
+        cast_column(Series::new("billaddress".into(), billaddress), PgType::Text)?,
+        cast_column(Series::new("quantity".into(), quantity), PgType::Int4)?,
+        cast_column(Series::new("sales".into(), sales), PgType::Int4)?,
+        cast_column(
+            Series::new("creationtime".into(), creationtime),
+            PgType::Timestamp,
+        )?,
     ])
     .map_err(AppError::Polars)?;
 
@@ -1,45 +1,65 @@
+//! Polars loader of `sales.ordersarchive`, @generated by `cargo run --bin codegen`
+
 use chrono::{NaiveDate, NaiveDateTime};
+use futures::TryStreamExt;
 use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 use lib_core::error::{AppError, AppResult};
 use lib_data::database_sales::ordersarchive;
 
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
 pub async fn get_df_ordersarchive(db: &DatabaseConnection) -> AppResult<DataFrame> {
-    let data = ordersarchive::Entity::find()
-        .all(db)
+    let stream = ordersarchive::Entity::find()
+        .stream(db)
         .await
-        .map_err(AppError::SeaOrm)?;
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
 
+fn get_df_batch(data: &[ordersarchive::Model]) -> AppResult<DataFrame> {
     let iter = data.iter();
-    let archiveids: Vec<i32> = iter.clone().map(|c| c.archiveid).collect();
-    let orderids: Vec<Option<i32>> = iter.clone().map(|c| c.orderid).collect();
-    let productids: Vec<Option<i32>> = iter.clone().map(|c| c.productid).collect();
-    let customerids: Vec<Option<i32>> = iter.clone().map(|c| c.customerid).collect();
-    let salespersonids: Vec<Option<i32>> = iter.clone().map(|c| c.salespersonid).collect();
-    let orderdates: Vec<Option<NaiveDate>> = iter.clone().map(|c| c.orderdate).collect();
-    let shipdates: Vec<Option<NaiveDate>> = iter.clone().map(|c| c.shipdate).collect();
-    let orderstatuses: Vec<Option<String>> = iter.clone().map(|c| c.orderstatus.clone()).collect();
-    let shipaddresses: Vec<Option<String>> = iter.clone().map(|c| c.shipaddress.clone()).collect();
-    let billaddresses: Vec<Option<String>> = iter.clone().map(|c| c.billaddress.clone()).collect();
-    let quantities: Vec<Option<i32>> = iter.clone().map(|c| c.quantity).collect();
-    let sales: Vec<Option<i32>> = iter.clone().map(|c| c.sales).collect();
-    let creationtimes: Vec<Option<NaiveDateTime>> = iter.clone().map(|c| c.creationtime).collect();
+    let archiveid: Vec<i32> = iter.clone().map(|row| row.archiveid).collect();
+    let orderid: Vec<Option<i32>> = iter.clone().map(|row| row.orderid).collect();
+    let productid: Vec<Option<i32>> = iter.clone().map(|row| row.productid).collect();
+    let customerid: Vec<Option<i32>> = iter.clone().map(|row| row.customerid).collect();
+    let salespersonid: Vec<Option<i32>> = iter.clone().map(|row| row.salespersonid).collect();
+    let orderdate: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.orderdate).collect();
+    let shipdate: Vec<Option<NaiveDate>> = iter.clone().map(|row| row.shipdate).collect();
+    let orderstatus: Vec<Option<String>> =
+        iter.clone().map(|row| row.orderstatus.clone()).collect();
+    let shipaddress: Vec<Option<String>> =
+        iter.clone().map(|row| row.shipaddress.clone()).collect();
+    let billaddress: Vec<Option<String>> =
+        iter.clone().map(|row| row.billaddress.clone()).collect();
+    let quantity: Vec<Option<i32>> = iter.clone().map(|row| row.quantity).collect();
+    let sales: Vec<Option<i32>> = iter.clone().map(|row| row.sales).collect();
+    let creationtime: Vec<Option<NaiveDateTime>> =
+        iter.clone().map(|row| row.creationtime).collect();
 
     let df = DataFrame::new(vec![
-        Series::new("archiveid".into(), archiveids).into(),
-        Series::new("orderid".into(), orderids).into(),
-        Series::new("productid".into(), productids).into(),
-        Series::new("customerid".into(), customerids).into(),
-        Series::new("salespersonid".into(), salespersonids).into(),
-        Series::new("orderdate".into(), orderdates).into(),
-        Series::new("shipdate".into(), shipdates).into(),
-        Series::new("orderstatus".into(), orderstatuses).into(),
-        Series::new("shipaddress".into(), shipaddresses).into(),
-        Series::new("billaddress".into(), billaddresses).into(),
-        Series::new("quantity".into(), quantities).into(),
-        Series::new("sales".into(), sales).into(),
-        Series::new("creationtime".into(), creationtimes).into(),
+        cast_column(Series::new("archiveid".into(), archiveid), PgType::Int4)?,
+        cast_column(Series::new("orderid".into(), orderid), PgType::Int4)?,
+        cast_column(Series::new("productid".into(), productid), PgType::Int4)?,
+        cast_column(Series::new("customerid".into(), customerid), PgType::Int4)?,
+        cast_column(
+            Series::new("salespersonid".into(), salespersonid),
+            PgType::Int4,
+        )?,
+        cast_column(Series::new("orderdate".into(), orderdate), PgType::Date)?,
+        cast_column(Series::new("shipdate".into(), shipdate), PgType::Date)?,
+        cast_column(Series::new("orderstatus".into(), orderstatus), PgType::Text)?,
+        cast_column(Series::new("shipaddress".into(), shipaddress), PgType::Text)?,
+        cast_column(Series::new("billaddress".into(), billaddress), PgType::Text)?,
+        cast_column(Series::new("quantity".into(), quantity), PgType::Int4)?,
+        cast_column(Series::new("sales".into(), sales), PgType::Int4)?,
+        cast_column(
+            Series::new("creationtime".into(), creationtime),
+            PgType::Timestamp,
+        )?,
     ])
     .map_err(AppError::Polars)?;
 
@@ -1,26 +1,36 @@
+//! Polars loader of `sales.products`, @generated by `cargo run --bin codegen`
+
+use futures::TryStreamExt;
 use polars::{frame::DataFrame, prelude::NamedFrom, series::Series};
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 use lib_core::error::{AppError, AppResult};
 use lib_data::database_sales::products;
 
+use crate::utils::dataframe::{PgType, cast_column, get_df_stream};
+
 pub async fn get_df_products(db: &DatabaseConnection) -> AppResult<DataFrame> {
-    let data = products::Entity::find()
-        .all(db)
+    let stream = products::Entity::find()
+        .stream(db)
         .await
-        .map_err(AppError::SeaOrm)?;
+        .map_err(AppError::SeaOrm)?
+        .map_err(AppError::SeaOrm);
+
+    get_df_stream(stream, get_df_batch).await
+}
 
+fn get_df_batch(data: &[products::Model]) -> AppResult<DataFrame> {
     let iter = data.iter();
-    let productids: Vec<i32> = iter.clone().map(|p| p.productid).collect();
-    let products: Vec<Option<String>> = iter.clone().map(|p| p.product.clone()).collect();
-    let categories: Vec<Option<String>> = iter.clone().map(|p| p.category.clone()).collect();
-    let prices: Vec<Option<i32>> = iter.clone().map(|p| p.price).collect();
+    let productid: Vec<i32> = iter.clone().map(|row| row.productid).collect();
+    let product: Vec<Option<String>> = iter.clone().map(|row| row.product.clone()).collect();
+    let category: Vec<Option<String>> = iter.clone().map(|row| row.category.clone()).collect();
+    let price: Vec<Option<i32>> = iter.clone().map(|row| row.price).collect();
 
     let df = DataFrame::new(vec![
-        Series::new("productid".into(), productids).into(),
-        Series::new("product".into(), products).into(),
-        Series::new("category".into(), categories).into(),
-        Series::new("price".into(), prices).into(),
+        cast_column(Series::new("productid".into(), productid), PgType::Int4)?,
+        cast_column(Series::new("product".into(), product), PgType::Text)?,
+        cast_column(Series::new("category".into(), category), PgType::Text)?,
+        cast_column(Series::new("price".into(), price), PgType::Int4)?,
     ])
     .map_err(AppError::Polars)?;
 
@@ -0,0 +1,3 @@
+mod order_payloads;
+
+pub use order_payloads::{get_df_order_payloads, order_payload_dtype};
@@ -0,0 +1,51 @@
+use futures::TryStreamExt;
+use polars::prelude::*;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::dataframe::get_df_stream;
+
+// Note: Polars has no JSONB type, the payload is loaded as text and decoded with this dtype.
+pub fn order_payload_dtype() -> DataType {
+    let customer = DataType::Struct(vec![
+        Field::new("id".into(), DataType::Int32),
+        Field::new("firstname".into(), DataType::String),
+        Field::new("country".into(), DataType::String),
+    ]);
+    let item = DataType::Struct(vec![
+        Field::new("product".into(), DataType::String),
+        Field::new("quantity".into(), DataType::Int32),
+        Field::new("price".into(), DataType::Int32),
+    ]);
+
+    DataType::Struct(vec![
+        Field::new("status".into(), DataType::String),
+        Field::new("customer".into(), customer),
+        Field::new("items".into(), DataType::List(Box::new(item))),
+    ])
+}
+
+pub async fn get_df_order_payloads(db: &Pool<Postgres>) -> AppResult<DataFrame> {
+    let stream = sqlx::query_as::<_, (i32, String)>(
+        "SELECT orderid, payload::TEXT FROM sandbox.order_payloads;",
+    )
+    .fetch(db)
+    .map_err(AppError::Sqlx);
+
+    get_df_stream(stream, get_df_batch).await
+}
+
+fn get_df_batch(data: &[(i32, String)]) -> AppResult<DataFrame> {
+    let iter = data.iter();
+    let orderids: Vec<i32> = iter.clone().map(|o| o.0).collect();
+    let payloads: Vec<&str> = iter.clone().map(|o| o.1.as_str()).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("orderid".into(), orderids).into(),
+        Series::new("payload".into(), payloads).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
@@ -0,0 +1,326 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+use chrono::NaiveDate;
+use polars::prelude::*;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection};
+use sqlx::postgres::PgConnectOptions;
+use sqlx::{ConnectOptions, Pool, Postgres};
+use tokio::runtime::Runtime;
+
+use lib_core::connection::{ConnectionConfig, connect_with_retry};
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::dataframe::copy::{get_df_copy, quote_ident, quote_table};
+use crate::utils::dataframe::types::DAYS_TO_1970;
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+// Note: `_` and `%` of the value match themselves, `\` is the default escape of LIKE.
+fn quote_like(value: &str, prefix: &str, suffix: &str) -> String {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    quote_literal(&format!("{prefix}{value}{suffix}"))
+}
+
+fn get_str(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Literal(value) => value.extract_str(),
+        _ => None,
+    }
+}
+
+// Note: strings are compared in the "C" collation, the byte order Polars compares them in.
+fn literal_to_sql(value: &LiteralValue) -> Option<String> {
+    if let Some(value) = value.extract_str() {
+        return Some(format!("{} COLLATE \"C\"", quote_literal(value)));
+    }
+    if let Ok(value) = value.extract_i64() {
+        return Some(value.to_string());
+    }
+
+    let LiteralValue::Scalar(scalar) = value else {
+        return None;
+    };
+    let sql = match scalar.value() {
+        AnyValue::Boolean(value) => value.to_string().to_uppercase(),
+        AnyValue::Float32(value) if value.is_finite() => format!("{value:?}"),
+        AnyValue::Float64(value) if value.is_finite() => format!("{value:?}"),
+        AnyValue::Date(days) => {
+            let date = NaiveDate::from_num_days_from_ce_opt(days + DAYS_TO_1970)?;
+            format!("DATE '{date}'")
+        }
+        _ => return None,
+    };
+
+    Some(sql)
+}
+
+// The SQL of a filter with the same result in Postgres and in Polars, `None` for anything
+// else, e.g. casts, arithmetic or a column of another table.
+fn predicate_to_sql(expr: &Expr, schema: &Schema) -> Option<String> {
+    match expr {
+        Expr::Column(name) if schema.contains(name) => Some(quote_ident(name)),
+        Expr::Literal(value) => literal_to_sql(value),
+        Expr::BinaryExpr { left, op, right } => {
+            let op = match op {
+                Operator::Eq => "=",
+                Operator::NotEq => "<>",
+                Operator::Lt => "<",
+                Operator::LtEq => "<=",
+                Operator::Gt => ">",
+                Operator::GtEq => ">=",
+                Operator::And | Operator::LogicalAnd => "AND",
+                Operator::Or | Operator::LogicalOr => "OR",
+                _ => return None,
+            };
+            let left = predicate_to_sql(left, schema)?;
+            let right = predicate_to_sql(right, schema)?;
+
+            Some(format!("({left} {op} {right})"))
+        }
+        Expr::Function { input, function } => match (function, input.as_slice()) {
+            (FunctionExpr::Boolean(BooleanFunction::Not), [input]) => {
+                Some(format!("(NOT {})", predicate_to_sql(input, schema)?))
+            }
+            (FunctionExpr::Boolean(BooleanFunction::IsNull), [input]) => {
+                Some(format!("({} IS NULL)", predicate_to_sql(input, schema)?))
+            }
+            (FunctionExpr::Boolean(BooleanFunction::IsNotNull), [input]) => Some(format!(
+                "({} IS NOT NULL)",
+                predicate_to_sql(input, schema)?
+            )),
+            (FunctionExpr::StringExpr(StringFunction::StartsWith), [input, prefix]) => {
+                let pattern = quote_like(get_str(prefix)?, "", "%");
+                Some(format!(
+                    "({} LIKE {pattern})",
+                    predicate_to_sql(input, schema)?
+                ))
+            }
+            (FunctionExpr::StringExpr(StringFunction::EndsWith), [input, suffix]) => {
+                let pattern = quote_like(get_str(suffix)?, "%", "");
+                Some(format!(
+                    "({} LIKE {pattern})",
+                    predicate_to_sql(input, schema)?
+                ))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn split_conjuncts(expr: &Expr, conjuncts: &mut Vec<Expr>) {
+    match expr {
+        Expr::BinaryExpr {
+            left,
+            op: Operator::And | Operator::LogicalAnd,
+            right,
+        } => {
+            split_conjuncts(left, conjuncts);
+            split_conjuncts(right, conjuncts);
+        }
+        _ => conjuncts.push(expr.clone()),
+    }
+}
+
+pub struct TableScan {
+    table: String,
+    options: PgConnectOptions,
+    schema: SchemaRef,
+}
+
+impl TableScan {
+    // The SELECT of the columns and the rows the scan needs, and the part of the filter
+    // left to Polars.
+    pub fn get_query(
+        &self,
+        columns: &[PlSmallStr],
+        predicate: Option<&Expr>,
+        n_rows: Option<usize>,
+    ) -> (String, Option<Expr>) {
+        let mut conjuncts = Vec::new();
+        let mut filters = Vec::new();
+        let mut remaining = Vec::new();
+
+        if let Some(predicate) = predicate {
+            split_conjuncts(predicate, &mut conjuncts);
+        }
+        for conjunct in conjuncts {
+            match predicate_to_sql(&conjunct, &self.schema) {
+                Some(sql) => filters.push(sql),
+                None => remaining.push(conjunct),
+            }
+        }
+
+        let remaining = remaining.into_iter().reduce(|left, right| left.and(right));
+        let filtered = remaining
+            .clone()
+            .map(|expr| expr.meta().root_names())
+            .unwrap_or_default();
+        let needed: Vec<&PlSmallStr> = self
+            .schema
+            .iter_names()
+            .filter(|name| columns.contains(name) || filtered.contains(name))
+            .collect();
+
+        // Note: a scan of no column still needs the number of rows.
+        let select = match needed.as_slice() {
+            [] => self
+                .schema
+                .iter_names()
+                .take(1)
+                .map(|name| quote_ident(name))
+                .collect::<Vec<_>>(),
+            needed => needed.iter().map(|name| quote_ident(name)).collect(),
+        };
+        let mut query = format!("SELECT {} FROM {}", select.join(", "), self.table);
+
+        if !filters.is_empty() {
+            query.push_str(&format!(" WHERE {}", filters.join(" AND ")));
+        }
+        // Note: with a filter left to Polars, the rows it keeps are not known yet.
+        if let (Some(n_rows), None) = (n_rows, &remaining) {
+            query.push_str(&format!(" LIMIT {n_rows}"));
+        }
+
+        (query, remaining)
+    }
+
+    // Note: Polars runs the scan inside `collect`, which is not async, the query runs on the
+    // runtime of the scans with the pool of its database, see `get_pool`.
+    fn load(&self, query: String) -> PolarsResult<DataFrame> {
+        let options = self.options.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        get_runtime()?.spawn(async move {
+            // Note: the errors are sent as text, `AppError` does not cross threads.
+            let df = async {
+                let db = get_pool(options).await.map_err(|err| err.to_string())?;
+                get_df_copy(&db, &query)
+                    .await
+                    .map_err(|err| err.to_string())
+            };
+            let _ = sender.send(df.await);
+        });
+
+        receiver
+            .recv()
+            .map_err(|_| polars_err!(ComputeError: "the scan of the table stopped"))?
+            .map_err(|err| polars_err!(ComputeError: "{err}"))
+    }
+}
+
+// Note: one thread runs every scan of the process, its connections belong to its runtime,
+// whichever runtime or thread calls `collect`.
+fn get_runtime() -> PolarsResult<&'static Runtime> {
+    static RUNTIME: OnceLock<std::io::Result<Runtime>> = OnceLock::new();
+
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("scan_table")
+                .enable_all()
+                .build()
+        })
+        .as_ref()
+        .map_err(|err| polars_err!(ComputeError: "no runtime for the scan: {err}"))
+}
+
+// The pool of the scans of a database, opened by the first scan and kept for the process.
+// Its limits and retries are the ones of `ConnectionConfig`, the options of the connections,
+// e.g. `statement_timeout`, come from the pool of the caller.
+async fn get_pool(options: PgConnectOptions) -> AppResult<Pool<Postgres>> {
+    static POOLS: LazyLock<Mutex<HashMap<String, Pool<Postgres>>>> =
+        LazyLock::new(Default::default);
+
+    let key = options.to_url_lossy().to_string();
+    let lock = || {
+        POOLS
+            .lock()
+            .map_err(|err| AppError::Assertion(err.to_string()))
+    };
+
+    if let Some(pool) = lock()?.get(&key) {
+        return Ok(pool.clone());
+    }
+
+    let config = ConnectionConfig::load()?;
+    let pool = connect_with_retry(options, config.get_pool_options(), &config.retry).await?;
+
+    Ok(lock()?.entry(key).or_insert(pool).clone())
+}
+
+impl AnonymousScan for TableScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let columns: Vec<PlSmallStr> = match (&scan_opts.output_schema, &scan_opts.with_columns) {
+            (Some(schema), _) => schema.iter_names_cloned().collect(),
+            (None, Some(columns)) => columns.to_vec(),
+            (None, None) => self.schema.iter_names_cloned().collect(),
+        };
+        let (query, remaining) =
+            self.get_query(&columns, scan_opts.predicate.as_ref(), scan_opts.n_rows);
+        let mut df = self.load(query)?;
+
+        if let Some(remaining) = remaining {
+            df = df.lazy().filter(remaining).collect()?;
+        }
+        if let Some(n_rows) = scan_opts.n_rows {
+            df = df.head(Some(n_rows));
+        }
+        if columns.is_empty() {
+            return Ok(DataFrame::empty_with_height(df.height()));
+        }
+
+        df.select(columns)
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_predicate_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+}
+
+// A LazyFrame of a table, e.g. `sales.orders`, the columns it selects and the filters it
+// starts with are part of the SELECT sent to Postgres.
+pub async fn scan_table(db: &DatabaseConnection, table: &str) -> AppResult<LazyFrame> {
+    if db.get_database_backend() != DatabaseBackend::Postgres {
+        return Err(AppError::Assertion(format!(
+            "scan_table reads {table} from Postgres only"
+        )));
+    }
+
+    let pool = db.get_postgres_connection_pool();
+    let table = quote_table(table);
+    let df = get_df_copy(pool, &format!("SELECT * FROM {table} WHERE false")).await?;
+    let scan = TableScan {
+        table,
+        options: (*pool.connect_options()).clone(),
+        schema: df.schema().clone(),
+    };
+    let args = ScanArgsAnonymous {
+        schema: Some(scan.schema.clone()),
+        name: "scan_table",
+        ..Default::default()
+    };
+
+    LazyFrame::anonymous_scan(Arc::new(scan), args).map_err(AppError::Polars)
+}
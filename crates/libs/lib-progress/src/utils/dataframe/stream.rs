@@ -0,0 +1,36 @@
+use futures::{Stream, TryStreamExt};
+use polars::prelude::*;
+
+use lib_core::error::{AppError, AppResult};
+
+// Note: only one batch of rows is held at a time, next to the columns already built.
+pub const BATCH_SIZE: usize = 10_000;
+
+// Reads the rows of the stream in batches of `BATCH_SIZE`, each batch is appended to the
+// DataFrame as a chunk of its own.
+pub async fn get_df_stream<S, T, F>(mut stream: S, get_df: F) -> AppResult<DataFrame>
+where
+    S: Stream<Item = AppResult<T>> + Unpin,
+    F: Fn(&[T]) -> AppResult<DataFrame>,
+{
+    // Note: the columns come from an empty batch, so an empty table still has its schema.
+    let mut df = get_df(&[])?;
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+
+    while let Some(row) = stream.try_next().await? {
+        batch.push(row);
+
+        if batch.len() == BATCH_SIZE {
+            df.vstack_mut_owned(get_df(&batch)?)
+                .map_err(AppError::Polars)?;
+            batch.clear();
+        }
+    }
+
+    if !batch.is_empty() {
+        df.vstack_mut_owned(get_df(&batch)?)
+            .map_err(AppError::Polars)?;
+    }
+
+    Ok(df)
+}
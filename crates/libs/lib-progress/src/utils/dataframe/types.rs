@@ -0,0 +1,685 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+use polars_arrow::array::{
+    ListArray, MutableArray, MutableBinaryViewArray, MutableBooleanArray, MutablePrimitiveArray,
+};
+use polars_arrow::bitmap::MutableBitmap;
+use polars_arrow::datatypes::{ArrowDataType, Field};
+use polars_arrow::offset::Offsets;
+use sqlx::postgres::{PgColumn, PgTypeInfo, PgTypeKind};
+use sqlx::{Column, TypeInfo};
+
+use lib_core::error::{AppError, AppResult};
+
+// Note: days from 0001-01-01 to 1970-01-01, Polars dates count from the latter.
+pub(crate) const DAYS_TO_1970: i32 = 719_163;
+
+// Note: Postgres counts days and microseconds from 2000-01-01, Arrow from 1970-01-01.
+const DAYS_TO_2000: i32 = 10_957;
+const MICROS_TO_2000: i64 = 946_684_800_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PgType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Numeric,
+    Text,
+    Uuid,
+    Json,
+    Jsonb,
+    Date,
+    Timestamp,
+    Timestamptz,
+}
+
+// Postgres types a column can be loaded from, by the name sqlx gives them.
+const TYPES: &[(&str, PgType)] = &[
+    ("BOOL", PgType::Bool),
+    ("INT2", PgType::Int2),
+    ("INT4", PgType::Int4),
+    ("INT8", PgType::Int8),
+    ("FLOAT4", PgType::Float4),
+    ("FLOAT8", PgType::Float8),
+    ("NUMERIC", PgType::Numeric),
+    ("TEXT", PgType::Text),
+    ("VARCHAR", PgType::Text),
+    ("CHAR", PgType::Text),
+    ("NAME", PgType::Text),
+    ("UUID", PgType::Uuid),
+    ("JSON", PgType::Json),
+    ("JSONB", PgType::Jsonb),
+    ("DATE", PgType::Date),
+    ("TIMESTAMP", PgType::Timestamp),
+    ("TIMESTAMPTZ", PgType::Timestamptz),
+];
+
+impl PgType {
+    // Note: a numeric has no fixed scale, it is read as a float. UUIDs and JSON are read as
+    // their text.
+    pub fn dtype(self) -> DataType {
+        match self {
+            Self::Bool => DataType::Boolean,
+            Self::Int2 => DataType::Int16,
+            Self::Int4 => DataType::Int32,
+            Self::Int8 => DataType::Int64,
+            Self::Float4 => DataType::Float32,
+            Self::Float8 | Self::Numeric => DataType::Float64,
+            Self::Text | Self::Uuid | Self::Json | Self::Jsonb => DataType::String,
+            Self::Date => DataType::Date,
+            Self::Timestamp => DataType::Datetime(TimeUnit::Microseconds, None),
+            Self::Timestamptz => DataType::Datetime(TimeUnit::Microseconds, Some(TimeZone::UTC)),
+        }
+    }
+}
+
+// Note: a column built from the values of a model has the dtype Polars gives the Rust type,
+// e.g. nanoseconds for a `NaiveDateTime`, it is cast to the dtype of its Postgres type.
+pub(crate) fn cast_column(series: Series, pg_type: PgType) -> AppResult<polars::prelude::Column> {
+    series
+        .cast(&pg_type.dtype())
+        .map(Series::into_column)
+        .map_err(AppError::Polars)
+}
+
+// The type of a column, a Postgres type or a one-dimensional array of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnType {
+    pub pg_type: PgType,
+    pub array: bool,
+}
+
+impl ColumnType {
+    pub fn dtype(self) -> DataType {
+        match self.array {
+            true => DataType::List(Box::new(self.pg_type.dtype())),
+            false => self.pg_type.dtype(),
+        }
+    }
+}
+
+fn unsupported(type_info: &PgTypeInfo) -> AppError {
+    AppError::Assertion(format!(
+        "no Polars type for the Postgres type {}, cast the column to TEXT",
+        type_info.name()
+    ))
+}
+
+// Note: a domain is loaded as its base type and an enum as its labels.
+pub fn get_column_type(type_info: &PgTypeInfo) -> AppResult<ColumnType> {
+    match type_info.kind() {
+        PgTypeKind::Domain(base) => get_column_type(base),
+        PgTypeKind::Enum(_) => Ok(ColumnType {
+            pg_type: PgType::Text,
+            array: false,
+        }),
+        PgTypeKind::Array(element) => match get_column_type(element)? {
+            ColumnType {
+                pg_type,
+                array: false,
+            } => Ok(ColumnType {
+                pg_type,
+                array: true,
+            }),
+            _ => Err(unsupported(type_info)),
+        },
+        _ => TYPES
+            .iter()
+            .find(|(name, _)| *name == type_info.name())
+            .map(|(_, pg_type)| ColumnType {
+                pg_type: *pg_type,
+                array: false,
+            })
+            .ok_or_else(|| unsupported(type_info)),
+    }
+}
+
+// The names and types of the columns of a result, an error names the first column no
+// Polars type is known for.
+pub(crate) fn get_column_types(columns: &[PgColumn]) -> AppResult<Vec<(PlSmallStr, ColumnType)>> {
+    columns
+        .iter()
+        .map(|column| {
+            let column_type = get_column_type(column.type_info()).map_err(|_| {
+                AppError::Assertion(format!(
+                    "no Polars type for the column {} of type {}, cast it to TEXT",
+                    column.name(),
+                    column.type_info().name()
+                ))
+            })?;
+
+            Ok((PlSmallStr::from(column.name()), column_type))
+        })
+        .collect()
+}
+
+pub(crate) fn read<const N: usize>(value: &[u8]) -> AppResult<[u8; N]> {
+    value
+        .try_into()
+        .map_err(|_| AppError::Assertion(format!("expected {N} bytes, got {}", value.len())))
+}
+
+fn read_i32(value: &[u8], offset: usize) -> AppResult<i32> {
+    let bytes = value
+        .get(offset..offset + 4)
+        .ok_or_else(|| AppError::Assertion("array is truncated".to_string()))?;
+
+    Ok(i32::from_be_bytes(read(bytes)?))
+}
+
+// Note: a numeric is base 10000 digits with the weight of the first one, the sign and the
+// scale.
+fn read_numeric(value: &[u8]) -> AppResult<f64> {
+    let header = value
+        .get(..8)
+        .ok_or_else(|| AppError::Assertion("numeric header is truncated".to_string()))?;
+    let count = i16::from_be_bytes(read(&header[0..2])?) as usize;
+    let weight = i16::from_be_bytes(read(&header[2..4])?) as i32;
+    let sign = u16::from_be_bytes(read(&header[4..6])?);
+
+    match sign {
+        0xC000 => return Ok(f64::NAN),
+        0xD000 => return Ok(f64::INFINITY),
+        0xF000 => return Ok(f64::NEG_INFINITY),
+        _ => {}
+    }
+
+    let mut number = 0.0;
+    for index in 0..count {
+        let digit = value
+            .get(8 + index * 2..10 + index * 2)
+            .ok_or_else(|| AppError::Assertion("numeric digits are truncated".to_string()))?;
+        let digit = i16::from_be_bytes(read(digit)?) as f64;
+
+        number += digit * 10_000f64.powi(weight - index as i32);
+    }
+
+    Ok(if sign == 0x4000 { -number } else { number })
+}
+
+fn read_uuid(value: &[u8]) -> AppResult<String> {
+    let value = u128::from_be_bytes(read(value)?);
+    let hex = format!("{value:032x}");
+
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+// Note: 'infinity' and '-infinity' are the largest and the smallest value, a Polars date
+// or datetime has no such value.
+fn read_date(value: &[u8]) -> AppResult<i32> {
+    match i32::from_be_bytes(read(value)?) {
+        i32::MAX | i32::MIN => Err(AppError::Assertion("infinite date".to_string())),
+        days => days
+            .checked_add(DAYS_TO_2000)
+            .ok_or_else(|| AppError::Assertion(format!("date out of range: {days}"))),
+    }
+}
+
+fn read_timestamp(value: &[u8]) -> AppResult<i64> {
+    match i64::from_be_bytes(read(value)?) {
+        i64::MAX | i64::MIN => Err(AppError::Assertion("infinite timestamp".to_string())),
+        micros => micros
+            .checked_add(MICROS_TO_2000)
+            .ok_or_else(|| AppError::Assertion(format!("timestamp out of range: {micros}"))),
+    }
+}
+
+fn read_str(value: &[u8]) -> AppResult<&str> {
+    std::str::from_utf8(value).map_err(|err| AppError::Dynamic(err.into()))
+}
+
+// Note: the binary array is its dimensions, then the length and the bytes of each element,
+// a length of -1 is a null.
+fn read_array(value: &[u8]) -> AppResult<Vec<Option<&[u8]>>> {
+    let dimensions = read_i32(value, 0)?;
+    let mut elements = Vec::new();
+
+    match dimensions {
+        0 => return Ok(elements),
+        1 => {}
+        _ => {
+            return Err(AppError::Assertion(format!(
+                "no Polars type for an array of {dimensions} dimensions"
+            )));
+        }
+    }
+
+    let count = read_i32(value, 12)?;
+    let mut position = 20;
+    for _ in 0..count {
+        let length = read_i32(value, position)?;
+        position += 4;
+
+        if length < 0 {
+            elements.push(None);
+            continue;
+        }
+
+        let element = value
+            .get(position..position + length as usize)
+            .ok_or_else(|| AppError::Assertion("array is truncated".to_string()))?;
+        position += length as usize;
+        elements.push(Some(element));
+    }
+
+    Ok(elements)
+}
+
+// Note: the text of an array is `{a,"b c",NULL}`, a quoted element escapes `"` and `\`
+// with a backslash and only an unquoted NULL is a null.
+fn split_array(value: &str) -> AppResult<Vec<Option<String>>> {
+    let inner = value
+        .strip_prefix('{')
+        .and_then(|value| value.strip_suffix('}'))
+        .ok_or_else(|| AppError::Assertion(format!("{value} is not an array")))?;
+    let mut elements = Vec::new();
+
+    if inner.is_empty() {
+        return Ok(elements);
+    }
+
+    let mut element = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars();
+
+    let mut push = |element: &mut String, quoted: &mut bool| {
+        let value = std::mem::take(element);
+        elements.push((*quoted || value != "NULL").then_some(value));
+        *quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => element.extend(chars.next()),
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            '{' | '}' if !in_quotes => {
+                return Err(AppError::Assertion(
+                    "no Polars type for an array of arrays".to_string(),
+                ));
+            }
+            ',' if !in_quotes => push(&mut element, &mut quoted),
+            c => element.push(c),
+        }
+    }
+    push(&mut element, &mut quoted);
+
+    Ok(elements)
+}
+
+fn parse<T>(value: &str) -> AppResult<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value
+        .parse()
+        .map_err(|err: T::Err| AppError::Dynamic(err.into()))
+}
+
+fn parse_date(value: &str) -> AppResult<i32> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|err| AppError::Dynamic(err.into()))?;
+
+    Ok(date.num_days_from_ce() - DAYS_TO_1970)
+}
+
+fn parse_timestamp(value: &str) -> AppResult<i64> {
+    let timestamp = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .map_err(|err| AppError::Dynamic(err.into()))?;
+
+    Ok(timestamp.and_utc().timestamp_micros())
+}
+
+// Note: the offset follows the TimeZone of the session, e.g. `+00` or `+05:30`.
+fn parse_timestamptz(value: &str) -> AppResult<i64> {
+    let timestamp = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z")
+        .map_err(|err| AppError::Dynamic(err.into()))?;
+
+    Ok(timestamp.timestamp_micros())
+}
+
+enum Values {
+    Bool(MutableBooleanArray),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Text(MutableBinaryViewArray<str>),
+    Date(MutablePrimitiveArray<i32>),
+    Timestamp(MutablePrimitiveArray<i64>),
+}
+
+impl Values {
+    fn new(pg_type: PgType) -> Self {
+        let dtype = pg_type.dtype().to_arrow(CompatLevel::newest());
+
+        match pg_type {
+            PgType::Bool => Self::Bool(MutableBooleanArray::new()),
+            PgType::Int2 => Self::Int16(MutablePrimitiveArray::new()),
+            PgType::Int4 => Self::Int32(MutablePrimitiveArray::new()),
+            PgType::Int8 => Self::Int64(MutablePrimitiveArray::new()),
+            PgType::Float4 => Self::Float32(MutablePrimitiveArray::new()),
+            PgType::Float8 | PgType::Numeric => Self::Float64(MutablePrimitiveArray::new()),
+            PgType::Text | PgType::Uuid | PgType::Json | PgType::Jsonb => {
+                Self::Text(MutableBinaryViewArray::new())
+            }
+            PgType::Date => Self::Date(MutablePrimitiveArray::new().to(dtype)),
+            PgType::Timestamp | PgType::Timestamptz => {
+                Self::Timestamp(MutablePrimitiveArray::new().to(dtype))
+            }
+        }
+    }
+
+    fn push_binary(&mut self, pg_type: PgType, value: &[u8]) -> AppResult<()> {
+        match (self, pg_type) {
+            (Self::Bool(array), _) => array.push_value(read::<1>(value)?[0] != 0),
+            (Self::Int16(array), _) => array.push_value(i16::from_be_bytes(read(value)?)),
+            (Self::Int32(array), _) => array.push_value(i32::from_be_bytes(read(value)?)),
+            (Self::Int64(array), _) => array.push_value(i64::from_be_bytes(read(value)?)),
+            (Self::Float32(array), _) => array.push_value(f32::from_be_bytes(read(value)?)),
+            (Self::Float64(array), PgType::Numeric) => array.push_value(read_numeric(value)?),
+            (Self::Float64(array), _) => array.push_value(f64::from_be_bytes(read(value)?)),
+            (Self::Text(array), PgType::Uuid) => array.push_value(read_uuid(value)?),
+            // Note: a binary jsonb starts with the version of its format, then the text.
+            (Self::Text(array), PgType::Jsonb) => match value.split_first() {
+                Some((1, text)) => array.push_value(read_str(text)?),
+                _ => return Err(AppError::Assertion("unknown jsonb version".to_string())),
+            },
+            (Self::Text(array), _) => array.push_value(read_str(value)?),
+            (Self::Date(array), _) => array.push_value(read_date(value)?),
+            (Self::Timestamp(array), _) => array.push_value(read_timestamp(value)?),
+        }
+
+        Ok(())
+    }
+
+    fn push_text(&mut self, pg_type: PgType, value: &str) -> AppResult<()> {
+        match (self, pg_type) {
+            (Self::Bool(array), _) => match value {
+                "t" => array.push_value(true),
+                "f" => array.push_value(false),
+                _ => return Err(AppError::Assertion(format!("{value} is not a boolean"))),
+            },
+            (Self::Int16(array), _) => array.push_value(parse(value)?),
+            (Self::Int32(array), _) => array.push_value(parse(value)?),
+            (Self::Int64(array), _) => array.push_value(parse(value)?),
+            (Self::Float32(array), _) => array.push_value(parse(value)?),
+            (Self::Float64(array), _) => array.push_value(parse(value)?),
+            (Self::Text(array), _) => array.push_value(value),
+            (Self::Date(array), _) => array.push_value(parse_date(value)?),
+            (Self::Timestamp(array), PgType::Timestamptz) => {
+                array.push_value(parse_timestamptz(value)?)
+            }
+            (Self::Timestamp(array), _) => array.push_value(parse_timestamp(value)?),
+        }
+
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        match self {
+            Self::Bool(array) => array.push_null(),
+            Self::Int16(array) => array.push_null(),
+            Self::Int32(array) | Self::Date(array) => array.push_null(),
+            Self::Int64(array) | Self::Timestamp(array) => array.push_null(),
+            Self::Float32(array) => array.push_null(),
+            Self::Float64(array) => array.push_null(),
+            Self::Text(array) => array.push_null(),
+        }
+    }
+
+    fn take(&mut self) -> ArrayRef {
+        match self {
+            Self::Bool(array) => array.as_box(),
+            Self::Int16(array) => array.as_box(),
+            Self::Int32(array) | Self::Date(array) => array.as_box(),
+            Self::Int64(array) | Self::Timestamp(array) => array.as_box(),
+            Self::Float32(array) => array.as_box(),
+            Self::Float64(array) => array.as_box(),
+            Self::Text(array) => array.as_box(),
+        }
+    }
+}
+
+// Builds the Arrow array of a column from its Postgres values, in the binary format of COPY
+// or in the text format of the simple query protocol.
+pub(crate) struct Builder {
+    column_type: ColumnType,
+    values: Values,
+    // Note: the elements of an array column are in `values`, a list ends at each offset.
+    offsets: Vec<i64>,
+    validity: MutableBitmap,
+}
+
+impl Builder {
+    pub(crate) fn new(column_type: ColumnType) -> Self {
+        Self {
+            column_type,
+            values: Values::new(column_type.pg_type),
+            offsets: vec![0],
+            validity: MutableBitmap::new(),
+        }
+    }
+
+    fn push_list(&mut self, length: usize, valid: bool) {
+        let last = self.offsets.last().copied().unwrap_or_default();
+
+        self.offsets.push(last + length as i64);
+        self.validity.push(valid);
+    }
+
+    pub(crate) fn push_binary(&mut self, value: Option<&[u8]>) -> AppResult<()> {
+        let pg_type = self.column_type.pg_type;
+
+        match (value, self.column_type.array) {
+            (None, false) => self.values.push_null(),
+            (Some(value), false) => self.values.push_binary(pg_type, value)?,
+            (None, true) => self.push_list(0, false),
+            (Some(value), true) => {
+                let elements = read_array(value)?;
+
+                for element in &elements {
+                    match element {
+                        Some(element) => self.values.push_binary(pg_type, element)?,
+                        None => self.values.push_null(),
+                    }
+                }
+                self.push_list(elements.len(), true);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn push_text(&mut self, value: Option<&str>) -> AppResult<()> {
+        let pg_type = self.column_type.pg_type;
+
+        match (value, self.column_type.array) {
+            (None, false) => self.values.push_null(),
+            (Some(value), false) => self.values.push_text(pg_type, value)?,
+            (None, true) => self.push_list(0, false),
+            (Some(value), true) => {
+                let elements = split_array(value)?;
+
+                for element in &elements {
+                    match element {
+                        Some(element) => self.values.push_text(pg_type, element)?,
+                        None => self.values.push_null(),
+                    }
+                }
+                self.push_list(elements.len(), true);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Takes the values pushed so far, the builder starts over empty.
+    pub(crate) fn take(&mut self) -> AppResult<ArrayRef> {
+        let values = self.values.take();
+
+        if !self.column_type.array {
+            return Ok(values);
+        }
+
+        let offsets = std::mem::replace(&mut self.offsets, vec![0]);
+        let offsets = Offsets::try_from(offsets).map_err(AppError::Polars)?;
+        let validity = std::mem::take(&mut self.validity);
+        let dtype = ArrowDataType::LargeList(Box::new(Field::new(
+            PlSmallStr::from_static("item"),
+            values.dtype().clone(),
+            true,
+        )));
+
+        ListArray::<i64>::try_new(dtype, offsets.into(), values, Some(validity.into()))
+            .map(|array| array.boxed())
+            .map_err(AppError::Polars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_numeric_decodes_base_10000_digits() {
+        let cases: &[(&[u8], f64)] = &[
+            (&[0, 0, 0, 0, 0, 0, 0, 0], 0.0),
+            (&[0, 2, 0, 0, 0, 0, 0, 1, 0, 12, 0x13, 0x88], 12.5),
+            (&[0, 1, 0, 1, 0x40, 0, 0, 0, 0, 1], -10_000.0),
+            (&[0, 1, 0xFF, 0xFF, 0, 0, 0, 2, 0x09, 0xC4], 0.25),
+            (&[0, 0, 0, 0, 0xD0, 0, 0, 0], f64::INFINITY),
+            (&[0, 0, 0, 0, 0xF0, 0, 0, 0], f64::NEG_INFINITY),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(read_numeric(value).ok(), Some(*expected), "{value:?}");
+        }
+
+        assert!(read_numeric(&[0, 0, 0, 0, 0xC0, 0, 0, 0]).is_ok_and(f64::is_nan));
+        assert!(read_numeric(&[0, 1, 0, 0]).is_err());
+        assert!(read_numeric(&[0, 1, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    type Elements<'a> = Option<Vec<Option<&'a [u8]>>>;
+
+    #[test]
+    fn read_array_splits_the_elements() {
+        let cases: &[(&[u8], Elements)] = &[
+            (&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23], Some(vec![])),
+            (
+                &[
+                    0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 23, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0,
+                    0, 7, 0xFF, 0xFF, 0xFF, 0xFF,
+                ],
+                Some(vec![Some(&[0, 0, 0, 7]), None]),
+            ),
+            (&[0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 23], None),
+            (
+                &[
+                    0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0,
+                ],
+                None,
+            ),
+            (&[0, 0, 0], None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(read_array(value).ok(), *expected, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn split_array_unquotes_the_elements() {
+        let cases: &[(&str, Option<Vec<Option<&str>>>)] = &[
+            ("{}", Some(vec![])),
+            ("{a,\"b c\",NULL}", Some(vec![Some("a"), Some("b c"), None])),
+            ("{\"NULL\",\"\"}", Some(vec![Some("NULL"), Some("")])),
+            (
+                "{\"a\\\"b\",\"c\\\\d\"}",
+                Some(vec![Some("a\"b"), Some("c\\d")]),
+            ),
+            ("{\"x,y\",\"{z}\"}", Some(vec![Some("x,y"), Some("{z}")])),
+            ("{{1},{2}}", None),
+            ("a,b", None),
+        ];
+
+        for (value, expected) in cases {
+            let expected: Option<Vec<Option<String>>> = expected.as_ref().map(|elements| {
+                elements
+                    .iter()
+                    .map(|element| element.map(str::to_string))
+                    .collect()
+            });
+
+            assert_eq!(split_array(value).ok(), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn read_date_and_timestamp_reject_infinity() {
+        assert_eq!(read_date(&0i32.to_be_bytes()).ok(), Some(DAYS_TO_2000));
+        assert_eq!(
+            read_date(&(-1i32).to_be_bytes()).ok(),
+            Some(DAYS_TO_2000 - 1)
+        );
+        assert!(read_date(&i32::MAX.to_be_bytes()).is_err());
+        assert!(read_date(&i32::MIN.to_be_bytes()).is_err());
+        assert!(read_date(&(i32::MAX - 1).to_be_bytes()).is_err());
+        assert!(read_date(&[0, 0]).is_err());
+
+        assert_eq!(
+            read_timestamp(&0i64.to_be_bytes()).ok(),
+            Some(MICROS_TO_2000)
+        );
+        assert!(read_timestamp(&i64::MAX.to_be_bytes()).is_err());
+        assert!(read_timestamp(&i64::MIN.to_be_bytes()).is_err());
+        assert!(read_timestamp(&(i64::MAX - 1).to_be_bytes()).is_err());
+
+        for pg_type in [PgType::Date, PgType::Timestamp, PgType::Timestamptz] {
+            let mut builder = Builder::new(ColumnType {
+                pg_type,
+                array: false,
+            });
+            let infinity = match pg_type {
+                PgType::Date => i32::MAX.to_be_bytes().to_vec(),
+                _ => i64::MAX.to_be_bytes().to_vec(),
+            };
+
+            assert!(builder.push_binary(Some(&infinity)).is_err(), "{pg_type:?}");
+        }
+    }
+
+    #[test]
+    fn parse_timestamptz_reads_the_offset() {
+        let cases: &[(&str, Option<i64>)] = &[
+            ("2000-01-01 00:00:00+00", Some(MICROS_TO_2000)),
+            (
+                "2000-01-01 05:30:00.5+05:30",
+                Some(MICROS_TO_2000 + 500_000),
+            ),
+            ("1999-12-31 19:00:00-05", Some(MICROS_TO_2000)),
+            ("2000-01-01 00:00:00", None),
+            ("2000-01-01", None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_timestamptz(value).ok(), *expected, "{value}");
+        }
+    }
+}
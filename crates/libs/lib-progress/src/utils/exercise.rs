@@ -0,0 +1,195 @@
+use std::io::Read;
+
+use polars::prelude::*;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lesson};
+use crate::utils::catalog::{Table, get_catalog};
+use crate::utils::compare::compare_dfs_unordered;
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::{get_df_query, get_df_query_conn};
+use crate::utils::debug::log_debug;
+use crate::utils::tracker::Tracker;
+use crate::utils::transpiler::{get_table_names, parse_query};
+
+// Note: the learner's SQL must not run longer than this.
+const STATEMENT_TIMEOUT: &str = "5s";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Grade {
+    Correct,
+    WrongColumns(String),
+    WrongRows(String),
+    SqlError(String),
+}
+
+pub struct Exercise {
+    pub id: &'static str,
+    pub task: String,
+    pub tables: Vec<Table>,
+    // The row order only counts when the reference query has an ORDER BY.
+    pub ordered: bool,
+    pub expected: DataFrame,
+}
+
+// The reference result is the `# QUERY:` of the lesson run on Postgres, the `# TASK:` says
+// what to query without the answer.
+pub async fn get_exercise(db: &Pool<Postgres>, lesson: &Lesson) -> AppResult<Exercise> {
+    let query = lesson
+        .query()
+        .ok_or_else(|| AppError::Assertion(format!("{} has no query", lesson.id)))?;
+    let task = lesson
+        .task()
+        .ok_or_else(|| AppError::Assertion(format!("{} has no task", lesson.id)))?;
+    let parsed = parse_query(&query)?;
+    let catalog = get_catalog(db).await?;
+    let tables = get_table_names(&parsed)
+        .iter()
+        .filter_map(|name| catalog.table(name).cloned())
+        .collect();
+
+    Ok(Exercise {
+        id: lesson.id,
+        task,
+        tables,
+        ordered: parsed.order_by.is_some(),
+        expected: get_df_query(db, &query).await?,
+    })
+}
+
+impl Exercise {
+    pub fn display(&self) {
+        let order = if self.ordered { "ordered" } else { "any order" };
+        let mut task = self.task.clone();
+        task.push_str(&format!(
+            " Return {} rows ({order}) with the columns below.",
+            self.expected.height(),
+        ));
+
+        log_debug(self.id, &task, None);
+
+        for table in &self.tables {
+            if let Ok(schema) = table.schema() {
+                log_debug(&table.name, &DataFrame::empty_with_schema(&schema), None);
+            }
+        }
+        log_debug("EXPECTED COLUMNS", &self.expected.clear(), None);
+    }
+
+    // Note: the SQL runs in a read-only transaction that is rolled back, so a learner
+    // cannot change the lesson data.
+    pub async fn grade(&self, db: &Pool<Postgres>, sql: &str) -> AppResult<Grade> {
+        let mut tx = db.begin().await.map_err(AppError::Sqlx)?;
+
+        sqlx::query("SET TRANSACTION READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        sqlx::query(&format!(
+            "SET LOCAL statement_timeout = '{STATEMENT_TIMEOUT}'"
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+        let df = match get_df_query_conn(&mut tx, sql).await {
+            Ok(df) => df,
+            Err(AppError::Sqlx(sqlx::Error::Database(err))) => {
+                return Ok(Grade::SqlError(err.message().to_string()));
+            }
+            Err(err) => return Err(err),
+        };
+
+        tx.rollback().await.map_err(AppError::Sqlx)?;
+
+        self.compare(&df)
+    }
+
+    fn compare(&self, df: &DataFrame) -> AppResult<Grade> {
+        let expected = &self.expected;
+
+        if df.get_column_names() != expected.get_column_names() {
+            return Ok(Grade::WrongColumns(format!(
+                "expected {:?}, got {:?}",
+                expected.get_column_names(),
+                df.get_column_names()
+            )));
+        }
+
+        // Note: integer and float widths are not graded, the values are compared in the
+        // types of the reference.
+        let casts: Vec<Expr> = expected
+            .get_columns()
+            .iter()
+            .map(|c| col(c.name().clone()).strict_cast(c.dtype().clone()))
+            .collect();
+        let df = match df.clone().lazy().select(casts).collect() {
+            Ok(df) => df,
+            Err(err) => return Ok(Grade::WrongColumns(err.to_string())),
+        };
+
+        if self.ordered && df.equals_missing(expected) {
+            return Ok(Grade::Correct);
+        }
+
+        if compare_dfs_unordered(&df, expected)? {
+            if self.ordered {
+                return Ok(Grade::WrongRows(
+                    "right rows in the wrong order".to_string(),
+                ));
+            }
+
+            return Ok(Grade::Correct);
+        }
+
+        Ok(Grade::WrongRows(format!(
+            "expected {} rows, got {}",
+            expected.height(),
+            df.height()
+        )))
+    }
+}
+
+// Reads the SQL from a file, or from stdin when there is no path or the path is `-`.
+pub fn read_sql(path: Option<&str>) -> AppResult<String> {
+    let sql = match path {
+        Some(path) if path != "-" => std::fs::read_to_string(path).map_err(AppError::Io)?,
+        _ => {
+            let mut sql = String::new();
+            std::io::stdin()
+                .read_to_string(&mut sql)
+                .map_err(AppError::Io)?;
+            sql
+        }
+    };
+
+    let sql = sql.trim();
+
+    if sql.is_empty() {
+        return Err(AppError::Assertion("no SQL to grade".to_string()));
+    }
+
+    Ok(sql.to_string())
+}
+
+pub async fn display_exercise(id: &str, path: Option<&str>) -> AppResult<Grade> {
+    let lesson = get_lesson(id).ok_or_else(|| AppError::Assertion(format!("no lesson {id}")))?;
+    // Note: the lesson is graded on its own dataset whatever the database of `DATABASE_URL`.
+    let (_, db_sqlx) = get_database_dataset(lesson.dataset()).await?;
+    let exercise = get_exercise(&db_sqlx, &lesson).await?;
+
+    exercise.display();
+
+    let sql = read_sql(path)?;
+    let grade = exercise.grade(&db_sqlx, &sql).await?;
+
+    log_debug("GRADE", &grade, None);
+
+    let mut tracker = Tracker::load()?;
+    tracker.record_attempt(id, grade == Grade::Correct);
+    tracker.save()?;
+
+    Ok(grade)
+}
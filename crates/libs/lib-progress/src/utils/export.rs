@@ -0,0 +1,180 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use sea_orm::DatabaseConnection;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lesson};
+use crate::utils::catalog::{check_tables, get_catalog};
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::get_df_query;
+use crate::utils::debug::log_debug;
+use crate::utils::statements::get_sea_orm_statements;
+use crate::utils::tracker::Tracker;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Parquet,
+    Ipc,
+}
+
+impl Format {
+    // The format is given by the extension of the output file.
+    pub fn from_path(path: &Path) -> AppResult<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("json") => Ok(Format::Json),
+            Some("parquet") => Ok(Format::Parquet),
+            Some("arrow" | "ipc" | "feather") => Ok(Format::Ipc),
+            _ => Err(AppError::Assertion(format!(
+                "unknown output format {}, expected .csv, .json, .parquet or .arrow",
+                path.display()
+            ))),
+        }
+    }
+}
+
+// The engine whose result is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Polars,
+    Sqlx,
+    SeaOrm,
+}
+
+impl Source {
+    pub fn parse(name: &str) -> AppResult<Self> {
+        match name {
+            "polars" => Ok(Source::Polars),
+            "sqlx" => Ok(Source::Sqlx),
+            "sea_orm" | "seaorm" => Ok(Source::SeaOrm),
+            _ => Err(AppError::Assertion(format!(
+                "unknown source {name}, expected polars, sqlx or sea_orm"
+            ))),
+        }
+    }
+}
+
+// Note: the file and the source are checked before any query runs.
+pub struct Output {
+    pub path: PathBuf,
+    pub format: Format,
+    pub source: Source,
+}
+
+impl Output {
+    pub fn new(path: &str, source: Source) -> AppResult<Self> {
+        let path = PathBuf::from(path);
+        let format = Format::from_path(&path)?;
+
+        Ok(Self {
+            path,
+            format,
+            source,
+        })
+    }
+}
+
+pub fn write_df(df: &mut DataFrame, path: &Path, format: Format) -> AppResult<()> {
+    let mut file = File::create(path).map_err(AppError::Io)?;
+
+    match format {
+        Format::Csv => CsvWriter::new(&mut file)
+            .include_header(true)
+            .finish(df)
+            .map_err(AppError::Polars),
+        // Note: an array of row objects, the layout notebooks and spreadsheets import.
+        Format::Json => JsonWriter::new(&mut file)
+            .with_json_format(JsonFormat::Json)
+            .finish(df)
+            .map_err(AppError::Polars),
+        Format::Parquet => ParquetWriter::new(&mut file)
+            .finish(df)
+            .map(|_| ())
+            .map_err(AppError::Polars),
+        Format::Ipc => IpcWriter::new(&mut file)
+            .finish(df)
+            .map_err(AppError::Polars),
+    }
+}
+
+// Note: the rows of sqlx and SeaORM are read back through `get_df_query`, so the three
+// engines share one serializer whatever the result struct of the lesson.
+async fn get_df_sea_orm(db_sqlx: &Pool<Postgres>, lesson: &Lesson) -> AppResult<DataFrame> {
+    let statements = get_sea_orm_statements(lesson).await?;
+
+    // Note: lessons without a union API send one statement per table, there is no single table.
+    let [statement] = statements.as_slice() else {
+        return Err(AppError::Assertion(format!(
+            "SeaORM builds {} statements for {}",
+            statements.len(),
+            lesson.id
+        )));
+    };
+
+    get_df_query(db_sqlx, statement).await
+}
+
+pub async fn get_df_lesson(
+    db_sea_orm: &DatabaseConnection,
+    db_sqlx: &Pool<Postgres>,
+    lesson: &Lesson,
+    source: Source,
+) -> AppResult<DataFrame> {
+    match source {
+        Source::Polars => {
+            let polars_query = lesson
+                .polars_query
+                .ok_or_else(|| AppError::Assertion(format!("{} has no Polars query", lesson.id)))?;
+
+            polars_query(db_sea_orm).await
+        }
+        Source::Sqlx => {
+            let query = lesson
+                .sqlx_sql()
+                .ok_or_else(|| AppError::Assertion(format!("{} has no sqlx query", lesson.id)))?;
+
+            get_df_query(db_sqlx, &query).await
+        }
+        Source::SeaOrm => get_df_sea_orm(db_sqlx, lesson).await,
+    }
+}
+
+// Runs the lesson once on its own dataset, prints the result of the source and writes it,
+// in place of `display_lesson`.
+pub async fn export_lesson(id: &str, output: &Output) -> AppResult<()> {
+    let lesson = get_lesson(id).ok_or_else(|| AppError::Assertion(format!("no lesson {id}")))?;
+    let (db_sea_orm, db_sqlx) = get_database_dataset(lesson.dataset()).await?;
+
+    check_tables(&get_catalog(&db_sqlx).await?, &lesson)?;
+
+    let mut df = get_df_lesson(&db_sea_orm, &db_sqlx, &lesson, output.source).await?;
+    let name = match output.source {
+        Source::Polars => "POLARS",
+        Source::Sqlx => "SQLX",
+        Source::SeaOrm => "SEA ORM",
+    };
+
+    log_debug(name, &df, None);
+    write_df(&mut df, &output.path, output.format)?;
+
+    log_debug(
+        "OUTPUT",
+        &format!("{} rows written to {}", df.height(), output.path.display()),
+        None,
+    );
+
+    let mut tracker = Tracker::load()?;
+    tracker.record_run(&lesson);
+    tracker.save()
+}
@@ -0,0 +1,467 @@
+use chrono::{Duration, NaiveDate};
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use sqlx::{PgConnection, Pool, Postgres};
+
+use lib_core::connection::{get_db_sqlx, get_db_sqlx_dataset};
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::debug::log_debug;
+
+// Note: the course fixtures stay in `salesdb`, generated data goes to another dataset.
+const FIXTURES_DATASET: &str = "salesdb";
+// Rows are sent to COPY in chunks of about this many bytes.
+const CHUNK_SIZE: usize = 1 << 20;
+
+const FIRST_NAMES: [&str; 12] = [
+    "Jossef", "Kevin", "Mary", "Mark", "Anna", "Frank", "Michael", "Carol", "Maria", "John",
+    "Georg", "Peter",
+];
+const LAST_NAMES: [&str; 10] = [
+    "Goldberg", "Brown", "Schwarz", "Adams", "Lee", "Ray", "Baker", "Miller", "Garcia", "Smith",
+];
+const COUNTRIES: [&str; 6] = ["Germany", "USA", "UK", "France", "Italy", "Spain"];
+const DEPARTMENTS: [&str; 5] = ["Marketing", "Sales", "Finance", "IT", "HR"];
+const PRODUCTS: [(&str, &str); 8] = [
+    ("Bottle", "Accessories"),
+    ("Tire", "Accessories"),
+    ("Helmet", "Accessories"),
+    ("Socks", "Clothing"),
+    ("Caps", "Clothing"),
+    ("Gloves", "Clothing"),
+    ("Chain", "Components"),
+    ("Pedals", "Components"),
+];
+const STATUSES: [&str; 2] = ["Delivered", "Shipped"];
+const STREETS: [&str; 8] = [
+    "Main St",
+    "Elm St",
+    "Oak St",
+    "Pine St",
+    "Maple St",
+    "Victory Lane",
+    "Belmont Rd.",
+    "Door Way",
+];
+
+// Row counts of each table, scale factor 1 loads about 1.3 million rows.
+#[derive(Clone, Copy, Debug)]
+pub struct Scale {
+    pub customers: usize,
+    pub employees: usize,
+    pub products: usize,
+    pub orders: usize,
+    pub orders_archive: usize,
+}
+
+impl Scale {
+    pub fn new(factor: f64) -> Self {
+        let rows = |base: f64| ((base * factor).round() as usize).max(1);
+
+        Self {
+            customers: rows(100_000.0),
+            employees: rows(1_000.0),
+            products: rows(1_000.0),
+            orders: rows(1_000_000.0),
+            orders_archive: rows(200_000.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Table {
+    Products,
+    Customers,
+    Employees,
+    Orders,
+    OrdersArchive,
+}
+
+// Referenced tables come first, the prices of the products are needed by the orders.
+const TABLES: [Table; 5] = [
+    Table::Products,
+    Table::Customers,
+    Table::Employees,
+    Table::Orders,
+    Table::OrdersArchive,
+];
+
+impl Table {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Products => "sales.products",
+            Table::Customers => "sales.customers",
+            Table::Employees => "sales.employees",
+            Table::Orders => "sales.orders",
+            Table::OrdersArchive => "sales.ordersarchive",
+        }
+    }
+
+    fn columns(&self) -> &'static str {
+        match self {
+            Table::Products => "productid, product, category, price",
+            Table::Customers => "customerid, firstname, lastname, country, score",
+            Table::Employees => {
+                "employeeid, firstname, lastname, department, birthdate, gender, salary, managerid"
+            }
+            Table::Orders => {
+                "orderid, productid, customerid, salespersonid, orderdate, shipdate, \
+                 orderstatus, shipaddress, billaddress, quantity, sales, creationtime"
+            }
+            Table::OrdersArchive => {
+                "archiveid, orderid, productid, customerid, salespersonid, orderdate, shipdate, \
+                 orderstatus, shipaddress, billaddress, quantity, sales, creationtime"
+            }
+        }
+    }
+
+    pub fn count(&self, scale: &Scale) -> usize {
+        match self {
+            Table::Products => scale.products,
+            Table::Customers => scale.customers,
+            Table::Employees => scale.employees,
+            Table::Orders => scale.orders,
+            Table::OrdersArchive => scale.orders_archive,
+        }
+    }
+}
+
+// Note: every value comes from one generator seeded once and the tables are generated in
+// a fixed order, so a seed and a scale always give the same data.
+pub struct Generator {
+    rng: ChaCha8Rng,
+    scale: Scale,
+    prices: Vec<i32>,
+}
+
+fn csv_text(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("\"{}\"", value.replace('"', "\"\"")),
+        None => String::new(),
+    }
+}
+
+fn csv_value<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+impl Generator {
+    pub fn new(scale: Scale, seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            scale,
+            prices: Vec::new(),
+        }
+    }
+
+    fn pick<'a>(&mut self, values: &'a [&'a str]) -> &'a str {
+        values.choose(&mut self.rng).copied().unwrap_or_default()
+    }
+
+    fn maybe<T>(&mut self, probability: f64, value: T) -> Option<T> {
+        if self.rng.random_bool(probability) {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    fn date(&mut self, first: NaiveDate, days: i64) -> NaiveDate {
+        first + Duration::days(self.rng.random_range(0..days))
+    }
+
+    fn address(&mut self) -> String {
+        let number = self.rng.random_range(1..10_000);
+        let street = self.pick(&STREETS);
+
+        format!("{number} {street}")
+    }
+
+    fn customer(&mut self, id: usize) -> String {
+        let firstname = self.pick(&FIRST_NAMES);
+        let lastname = self.pick(&LAST_NAMES);
+        let lastname = self.maybe(0.05, lastname);
+        let country = self.pick(&COUNTRIES);
+        let score = self.rng.random_range(0..=100) * 10;
+        let score = self.maybe(0.05, score);
+
+        format!(
+            "{id},{},{},{},{}\n",
+            csv_text(Some(firstname)),
+            csv_text(lastname),
+            csv_text(Some(country)),
+            csv_value(score)
+        )
+    }
+
+    // Note: a manager always comes before the employees reporting to them, the first
+    // employee is the root of the tree.
+    fn employee(&mut self, id: usize) -> String {
+        let firstname = self.pick(&FIRST_NAMES);
+        let lastname = self.pick(&LAST_NAMES);
+        let lastname = self.maybe(0.05, lastname);
+        let department = self.pick(&DEPARTMENTS);
+        let birthdate = self.date(
+            NaiveDate::from_ymd_opt(1960, 1, 1).unwrap_or_default(),
+            14_600,
+        );
+        let gender = self.pick(&["M", "F"]);
+        let salary = self.rng.random_range(40..=120) * 1_000;
+        let managerid = (id > 1).then(|| self.rng.random_range(1..id));
+
+        format!(
+            "{id},{},{},{},{birthdate},{},{salary},{}\n",
+            csv_text(Some(firstname)),
+            csv_text(lastname),
+            csv_text(Some(department)),
+            csv_text(Some(gender)),
+            csv_value(managerid)
+        )
+    }
+
+    fn product(&mut self, index: usize) -> String {
+        let (name, category) = *PRODUCTS.choose(&mut self.rng).unwrap_or(&PRODUCTS[0]);
+        let price = self.rng.random_range(1..=100) * 5;
+        self.prices.push(price);
+
+        format!(
+            "{},{},{},{price}\n",
+            index + 100,
+            csv_text(Some(&format!("{name} {index}"))),
+            csv_text(Some(category))
+        )
+    }
+
+    // The columns shared by `orders` and `ordersarchive`, from `productid` on.
+    fn order_columns(&mut self) -> String {
+        let product = self.rng.random_range(0..self.prices.len());
+        let customerid = self.rng.random_range(1..=self.scale.customers);
+        let salespersonid = self.rng.random_range(1..=self.scale.employees);
+        let orderdate = self.date(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap_or_default(),
+            1_826,
+        );
+        let shipdate = orderdate + Duration::days(self.rng.random_range(1..=15));
+        let status = self.pick(&STATUSES);
+        let shipaddress = self.address();
+        let shipaddress = self.maybe(0.1, shipaddress);
+        let billaddress = match self.rng.random_range(0..4) {
+            0 => None,
+            1 => Some(String::new()),
+            2 => shipaddress.clone(),
+            _ => Some(self.address()),
+        };
+        let quantity = self.rng.random_range(0..=5);
+        let creationtime = orderdate.and_hms_opt(0, 0, 0).unwrap_or_default()
+            + Duration::seconds(self.rng.random_range(0..86_400));
+
+        format!(
+            "{},{customerid},{salespersonid},{orderdate},{shipdate},{},{},{},{quantity},{},{creationtime}\n",
+            product + 101,
+            csv_text(Some(status)),
+            csv_text(shipaddress.as_deref()),
+            csv_text(billaddress.as_deref()),
+            quantity.max(1) * self.prices[product]
+        )
+    }
+
+    fn order(&mut self, id: usize) -> String {
+        format!("{id},{}", self.order_columns())
+    }
+
+    fn order_archive(&mut self, id: usize) -> String {
+        let orderid = self.rng.random_range(1..=self.scale.orders);
+
+        format!("{id},{orderid},{}", self.order_columns())
+    }
+
+    // One CSV line of the table, ids start at 1 and products at 101 as in the fixtures.
+    pub fn row(&mut self, table: Table, index: usize) -> String {
+        match table {
+            Table::Products => self.product(index),
+            Table::Customers => self.customer(index),
+            Table::Employees => self.employee(index),
+            Table::Orders => self.order(index),
+            Table::OrdersArchive => self.order_archive(index),
+        }
+    }
+}
+
+async fn copy_rows<F>(
+    conn: &mut PgConnection,
+    statement: &str,
+    count: usize,
+    mut row: F,
+) -> AppResult<u64>
+where
+    F: FnMut(usize) -> String,
+{
+    let mut copy = conn.copy_in_raw(statement).await.map_err(AppError::Sqlx)?;
+    let mut buffer = String::with_capacity(CHUNK_SIZE);
+
+    for index in 1..=count {
+        buffer.push_str(&row(index));
+
+        if buffer.len() >= CHUNK_SIZE {
+            copy.send(std::mem::take(&mut buffer).into_bytes())
+                .await
+                .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    if !buffer.is_empty() {
+        copy.send(buffer.into_bytes())
+            .await
+            .map_err(AppError::Sqlx)?;
+    }
+
+    copy.finish().await.map_err(AppError::Sqlx)
+}
+
+// Same tables as `docker/03-init.sql`, the foreign keys are added once the rows are loaded.
+async fn reset_tables(db: &Pool<Postgres>) -> AppResult<()> {
+    let query = "
+    DROP SCHEMA IF EXISTS sales CASCADE;
+    CREATE SCHEMA sales;
+    CREATE EXTENSION IF NOT EXISTS tablefunc;
+    CREATE TABLE sales.customers (
+        customerid INT PRIMARY KEY,
+        firstname VARCHAR(50),
+        lastname VARCHAR(50),
+        country VARCHAR(50),
+        score INT
+    );
+    CREATE TABLE sales.employees (
+        employeeid INT PRIMARY KEY,
+        firstname VARCHAR(50),
+        lastname VARCHAR(50),
+        department VARCHAR(50),
+        birthdate DATE,
+        gender CHAR(1),
+        salary INT,
+        managerid INT
+    );
+    CREATE TABLE sales.products (
+        productid INT PRIMARY KEY,
+        product VARCHAR(50),
+        category VARCHAR(50),
+        price INT
+    );
+    CREATE TABLE sales.orders (
+        orderid INT PRIMARY KEY,
+        productid INT,
+        customerid INT,
+        salespersonid INT,
+        orderdate DATE,
+        shipdate DATE,
+        orderstatus VARCHAR(50),
+        shipaddress VARCHAR(255),
+        billaddress VARCHAR(255),
+        quantity INT,
+        sales INT,
+        creationtime TIMESTAMP
+    );
+    CREATE TABLE sales.ordersarchive (
+        archiveid INT PRIMARY KEY,
+        orderid INT,
+        productid INT,
+        customerid INT,
+        salespersonid INT,
+        orderdate DATE,
+        shipdate DATE,
+        orderstatus VARCHAR(50),
+        shipaddress VARCHAR(255),
+        billaddress VARCHAR(255),
+        quantity INT,
+        sales INT,
+        creationtime TIMESTAMP
+    );
+    ";
+
+    sqlx::raw_sql(query)
+        .execute(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(())
+}
+
+// Note: Postgres checks every row when a foreign key is added, a broken reference in the
+// generated data fails here.
+async fn add_constraints(db: &Pool<Postgres>) -> AppResult<()> {
+    let query = "
+    ALTER TABLE sales.employees
+        ADD CONSTRAINT fk_manager FOREIGN KEY (managerid) REFERENCES sales.employees(employeeid);
+    ALTER TABLE sales.orders
+        ADD CONSTRAINT fk_product FOREIGN KEY (productid) REFERENCES sales.products(productid),
+        ADD CONSTRAINT fk_customer FOREIGN KEY (customerid) REFERENCES sales.customers(customerid),
+        ADD CONSTRAINT fk_salesperson FOREIGN KEY (salespersonid) REFERENCES sales.employees(employeeid);
+    ALTER TABLE sales.ordersarchive
+        ADD CONSTRAINT fk_product_archive FOREIGN KEY (productid) REFERENCES sales.products(productid),
+        ADD CONSTRAINT fk_customer_archive FOREIGN KEY (customerid) REFERENCES sales.customers(customerid),
+        ADD CONSTRAINT fk_salesperson_archive FOREIGN KEY (salespersonid) REFERENCES sales.employees(employeeid);
+    ANALYZE sales.customers, sales.employees, sales.products, sales.orders, sales.ordersarchive;
+    ";
+
+    sqlx::raw_sql(query)
+        .execute(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(())
+}
+
+// Creates the dataset on the server of `DATABASE_URL` when it does not exist yet.
+pub async fn create_dataset(dataset: &str) -> AppResult<()> {
+    let db = get_db_sqlx().await?;
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_database WHERE datname = $1)")
+            .bind(dataset)
+            .fetch_one(&db)
+            .await
+            .map_err(AppError::Sqlx)?;
+
+    if !exists {
+        let query = format!("CREATE DATABASE \"{}\"", dataset.replace('"', "\"\""));
+
+        sqlx::raw_sql(&query)
+            .execute(&db)
+            .await
+            .map_err(AppError::Sqlx)?;
+    }
+
+    Ok(())
+}
+
+pub async fn generate_sales(dataset: &str, scale: Scale, seed: u64) -> AppResult<()> {
+    if dataset == FIXTURES_DATASET {
+        return Err(AppError::Assertion(format!(
+            "{FIXTURES_DATASET} holds the course fixtures, generate into another dataset"
+        )));
+    }
+
+    create_dataset(dataset).await?;
+
+    let db = get_db_sqlx_dataset(dataset).await?;
+    let mut conn = db.acquire().await.map_err(AppError::Sqlx)?;
+    let mut generator = Generator::new(scale, seed);
+
+    reset_tables(&db).await?;
+
+    for table in TABLES {
+        let statement = format!(
+            "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+            table.name(),
+            table.columns()
+        );
+        let rows = copy_rows(&mut conn, &statement, table.count(&scale), |index| {
+            generator.row(table, index)
+        })
+        .await?;
+
+        log_debug(table.name(), &rows, None);
+    }
+
+    add_constraints(&db).await
+}
@@ -0,0 +1,77 @@
+use polars::prelude::*;
+
+use lib_core::error::{AppError, AppResult};
+
+// Note: Polars has no GROUPING SETS, so every set is grouped on its own and the
+// results are stacked. Keys outside a set become NULL and get a GROUPING() flag.
+pub fn grouping_sets(
+    lf: LazyFrame,
+    keys: &[&str],
+    sets: &[Vec<&str>],
+    aggs: &[Expr],
+) -> AppResult<LazyFrame> {
+    let schema = lf.clone().collect_schema().map_err(AppError::Polars)?;
+    let mut frames = Vec::with_capacity(sets.len());
+
+    for set in sets {
+        let mut columns = Vec::with_capacity(keys.len() * 2 + aggs.len());
+
+        for key in keys {
+            if set.contains(key) {
+                columns.push(col(*key));
+            } else {
+                let dtype = schema.try_get(key).map_err(AppError::Polars)?.clone();
+
+                columns.push(lit(NULL).cast(dtype).alias(*key));
+            }
+        }
+        for agg in aggs {
+            let name = agg.clone().meta().output_name().map_err(AppError::Polars)?;
+
+            columns.push(col(name));
+        }
+        for key in keys {
+            let flag = if set.contains(key) { 0i32 } else { 1i32 };
+
+            columns.push(lit(flag).alias(format!("grouping_{key}")));
+        }
+
+        let grouped = if set.is_empty() {
+            lf.clone().select(aggs)
+        } else {
+            let by: Vec<Expr> = set.iter().map(|key| col(*key)).collect();
+
+            lf.clone().group_by_stable(by).agg(aggs)
+        };
+
+        frames.push(grouped.select(columns));
+    }
+
+    concat(frames, UnionArgs::default()).map_err(AppError::Polars)
+}
+
+// ROLLUP(a, b) == GROUPING SETS ((a, b), (a), ())
+pub fn rollup<'a>(keys: &[&'a str]) -> Vec<Vec<&'a str>> {
+    (0..=keys.len())
+        .rev()
+        .map(|len| keys[..len].to_vec())
+        .collect()
+}
+
+// CUBE(a, b) == GROUPING SETS ((a, b), (a), (b), ())
+pub fn cube<'a>(keys: &[&'a str]) -> Vec<Vec<&'a str>> {
+    let mut sets = Vec::with_capacity(1 << keys.len());
+
+    for mask in (0..1usize << keys.len()).rev() {
+        let set = keys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << (keys.len() - 1 - i)) != 0)
+            .map(|(_, key)| *key)
+            .collect();
+
+        sets.push(set);
+    }
+
+    sets
+}
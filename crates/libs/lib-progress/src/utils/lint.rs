@@ -0,0 +1,69 @@
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lessons};
+use crate::utils::debug::log_debug;
+use crate::utils::transpiler::{CorpusOutcome, parse_query};
+
+// Note: both statements are printed back from their syntax tree, so only the layout and
+// the case of the keywords may differ.
+pub fn check_lesson(lesson: &Lesson) -> CorpusOutcome {
+    let (Some(comment), Some(sqlx)) = (lesson.query(), lesson.sqlx_sql()) else {
+        return CorpusOutcome::Skipped("no query or no sqlx query".to_string());
+    };
+
+    let comment = match parse_query(&comment) {
+        Ok(query) => query.to_string(),
+        Err(err) => return CorpusOutcome::Mismatch(format!("comment: {err}")),
+    };
+    let sqlx = match parse_query(&sqlx) {
+        Ok(query) => query.to_string(),
+        Err(err) => return CorpusOutcome::Mismatch(format!("sqlx: {err}")),
+    };
+
+    if comment == sqlx {
+        CorpusOutcome::Match
+    } else {
+        CorpusOutcome::Mismatch(format!("comment: {comment}\nsqlx:    {sqlx}"))
+    }
+}
+
+pub fn display_lint() -> AppResult<()> {
+    let mut drifted = Vec::new();
+
+    for lesson in get_lessons() {
+        let outcome = check_lesson(&lesson);
+
+        if let CorpusOutcome::Mismatch(_) = outcome {
+            drifted.push(lesson.id);
+        }
+
+        log_debug(lesson.id, &outcome, None);
+    }
+
+    if drifted.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Assertion(format!(
+            "the `# QUERY:` comment drifted from the sqlx query in {}",
+            drifted.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_query_comment_matches_its_sqlx_query() {
+        let drifted: Vec<String> = get_lessons()
+            .iter()
+            .filter_map(|lesson| match check_lesson(lesson) {
+                CorpusOutcome::Mismatch(message) => Some(format!("{}: {message}", lesson.id)),
+                _ => None,
+            })
+            .collect();
+
+        assert!(drifted.is_empty(), "{}", drifted.join("\n"));
+    }
+}
@@ -1,4 +1,20 @@
+pub mod bench;
+pub mod catalog;
+pub mod codegen;
 pub mod compare;
 pub mod database;
 pub mod dataframe;
 pub mod debug;
+pub mod exercise;
+pub mod export;
+pub mod generator;
+pub mod grouping;
+pub mod lint;
+pub mod repl;
+pub mod report;
+pub mod sandbox;
+pub mod session;
+pub mod sql_context;
+pub mod statements;
+pub mod tracker;
+pub mod transpiler;
@@ -0,0 +1,260 @@
+use std::io::{BufRead, Write};
+
+use polars::prelude::*;
+use sqlx::{Pool, Postgres};
+
+use lib_core::connection::{get_db_sqlx, get_db_sqlx_dataset};
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::get_lesson;
+use crate::utils::catalog::{Catalog, get_catalog};
+use crate::utils::compare::diff_dfs;
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::get_df_query;
+use crate::utils::debug::log_debug;
+
+const HELP: &str = "\
+\\d                 list the tables
+\\d <table>         describe a table, e.g. \\d sales.orders
+\\dataset <name>    connect to another database, e.g. \\dataset salesdb
+\\history           list the statements run so far
+\\compare <lesson>  compare the last result with a lesson, e.g. \\compare p_012
+\\q                 quit
+SQL runs when a line ends with `;`.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command<'a> {
+    Tables,
+    Describe(&'a str),
+    Dataset(&'a str),
+    History,
+    Compare(&'a str),
+    Help,
+    Quit,
+}
+
+impl<'a> Command<'a> {
+    // Lines that do not start with a backslash are SQL.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+
+        if !line.trim_start().starts_with('\\') {
+            return None;
+        }
+
+        let command = match (words.next()?, words.next()) {
+            ("\\d", None) => Command::Tables,
+            ("\\d", Some(table)) => Command::Describe(table),
+            ("\\dataset", Some(name)) => Command::Dataset(name),
+            ("\\history", None) => Command::History,
+            ("\\compare", Some(id)) => Command::Compare(id),
+            ("\\q", None) => Command::Quit,
+            _ => Command::Help,
+        };
+
+        Some(command)
+    }
+}
+
+pub struct Repl {
+    db: Pool<Postgres>,
+    catalog: Catalog,
+    dataset: Option<String>,
+    history: Vec<String>,
+    last: Option<DataFrame>,
+}
+
+impl Repl {
+    pub async fn new() -> AppResult<Self> {
+        let db = get_db_sqlx().await?;
+
+        Ok(Self {
+            catalog: get_catalog(&db).await?,
+            db,
+            dataset: None,
+            history: Vec::new(),
+            last: None,
+        })
+    }
+
+    pub async fn run_sql(&mut self, sql: &str) -> AppResult<DataFrame> {
+        self.history.push(sql.to_string());
+
+        let df = get_df_query(&self.db, sql).await?;
+        self.last = Some(df.clone());
+
+        Ok(df)
+    }
+
+    // Returns false when the REPL should stop.
+    pub async fn run_command(&mut self, command: Command<'_>) -> AppResult<bool> {
+        match command {
+            Command::Tables => {
+                let names: Vec<&str> = self
+                    .catalog
+                    .tables
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect();
+                let keys: Vec<String> = self
+                    .catalog
+                    .tables
+                    .iter()
+                    .map(|t| t.primary_key.join(", "))
+                    .collect();
+                let comments: Vec<Option<&str>> = self
+                    .catalog
+                    .tables
+                    .iter()
+                    .map(|t| t.comment.as_deref())
+                    .collect();
+                let df = df!(
+                    "table" => names,
+                    "primary_key" => keys,
+                    "comment" => comments,
+                )
+                .map_err(AppError::Polars)?;
+
+                log_debug("TABLES", &df, None);
+            }
+            Command::Describe(name) => {
+                let table = self
+                    .catalog
+                    .table(name)
+                    .ok_or_else(|| AppError::Assertion(format!("no table {name}")))?;
+                let columns = &table.columns;
+                // Note: the key of a column is PK, or the column it references.
+                let keys: Vec<Option<String>> = columns
+                    .iter()
+                    .map(|column| {
+                        if table.primary_key.contains(&column.name) {
+                            return Some("PK".to_string());
+                        }
+
+                        self.catalog
+                            .relations
+                            .iter()
+                            .filter(|relation| relation.table == table.name)
+                            .find_map(|relation| {
+                                let index =
+                                    relation.columns.iter().position(|c| *c == column.name)?;
+                                let foreign_column = relation.foreign_columns.get(index)?;
+
+                                Some(format!("FK {}.{foreign_column}", relation.foreign_table))
+                            })
+                    })
+                    .collect();
+                let df = df!(
+                    "column_name" => columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+                    "data_type" => columns.iter().map(|c| c.data_type.as_str()).collect::<Vec<_>>(),
+                    "is_nullable" => columns.iter().map(|c| c.nullable).collect::<Vec<_>>(),
+                    "polars" => columns
+                        .iter()
+                        .map(|c| c.dtype.as_ref().map_or("unsupported".to_string(), |d| d.to_string()))
+                        .collect::<Vec<_>>(),
+                    "key" => keys,
+                    "comment" => columns.iter().map(|c| c.comment.as_deref()).collect::<Vec<_>>(),
+                )
+                .map_err(AppError::Polars)?;
+
+                log_debug(&table.name, &df, None);
+            }
+            Command::Dataset(name) => {
+                self.db = get_db_sqlx_dataset(name).await?;
+                self.catalog = get_catalog(&self.db).await?;
+                self.dataset = Some(name.to_string());
+                self.last = None;
+            }
+            Command::History => {
+                for (index, sql) in self.history.iter().enumerate() {
+                    println!("{:>3}  {}", index + 1, sql.replace('\n', " "));
+                }
+            }
+            Command::Compare(id) => {
+                let lesson =
+                    get_lesson(id).ok_or_else(|| AppError::Assertion(format!("no lesson {id}")))?;
+                let query = lesson
+                    .query()
+                    .ok_or_else(|| AppError::Assertion(format!("{id} has no query")))?;
+                let last = self
+                    .last
+                    .as_ref()
+                    .ok_or_else(|| AppError::Assertion("no result yet".to_string()))?;
+                // Note: the lesson runs on its own dataset, whichever the REPL is on.
+                let (_, db) = get_database_dataset(lesson.dataset()).await?;
+                let expected = get_df_query(&db, &query).await?;
+
+                match diff_dfs(last, &expected)? {
+                    None => println!("{id}: same result\n"),
+                    Some(diff) => println!("{id}:\n\n{diff}\n"),
+                }
+            }
+            Command::Help => println!("{HELP}"),
+            Command::Quit => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn prompt(&self, continued: bool) -> String {
+        let dataset = self.dataset.as_deref().unwrap_or("sql");
+
+        if continued {
+            format!("{}> ", " ".repeat(dataset.len()))
+        } else {
+            format!("{dataset}> ")
+        }
+    }
+
+    // Note: errors are printed and the REPL goes on, only `\q` or the end of the input stop it.
+    pub async fn run(&mut self) -> AppResult<()> {
+        let stdin = std::io::stdin();
+        let mut lines = stdin.lock().lines();
+        let mut buffer = String::new();
+
+        loop {
+            print!("{}", self.prompt(!buffer.is_empty()));
+            std::io::stdout().flush().map_err(AppError::Io)?;
+
+            let Some(line) = lines.next() else { break };
+            let line = line.map_err(AppError::Io)?;
+
+            // Note: a blank line before any SQL is not the start of a statement.
+            if buffer.is_empty() && line.trim().is_empty() {
+                continue;
+            }
+
+            if buffer.is_empty()
+                && let Some(command) = Command::parse(&line)
+            {
+                match self.run_command(command).await {
+                    Ok(true) => {}
+                    Ok(false) => return Ok(()),
+                    Err(err) => println!("{err}\n"),
+                }
+                continue;
+            }
+
+            buffer.push_str(&line);
+            buffer.push('\n');
+
+            if line.trim_end().ends_with(';') {
+                let sql = std::mem::take(&mut buffer);
+                self.print_sql(sql.trim()).await;
+            }
+        }
+
+        if !buffer.trim().is_empty() {
+            self.print_sql(buffer.trim()).await;
+        }
+
+        Ok(())
+    }
+
+    async fn print_sql(&mut self, sql: &str) {
+        match self.run_sql(sql).await {
+            Ok(df) => println!("{df}\n"),
+            Err(err) => println!("{err}\n"),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::path::Path;
+
+use polars::prelude::*;
+use sea_orm::DatabaseConnection;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lessons};
+use crate::utils::catalog::{Catalog, get_catalog};
+use crate::utils::compare::diff_dfs;
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::get_df_query;
+use crate::utils::debug::log_debug;
+use crate::utils::export::{Source, get_df_lesson};
+use crate::utils::lint;
+use crate::utils::statements::{self, get_sea_orm_statements};
+use crate::utils::transpiler::CorpusOutcome;
+
+// Note: result tables longer than this are cut, the full result is one `--output` away.
+const ROW_LIMIT: usize = 50;
+
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: CorpusOutcome,
+    // Note: a check that does not verify the lesson is only shown, e.g. the shape of the SQL.
+    pub verifies: bool,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        matches!(
+            self.outcome,
+            CorpusOutcome::Match | CorpusOutcome::Skipped(_)
+        )
+    }
+
+    fn describe(&self) -> String {
+        match &self.outcome {
+            CorpusOutcome::Match => "match".to_string(),
+            CorpusOutcome::Mismatch(message) => format!("mismatch: {message}"),
+            CorpusOutcome::Unsupported(message) => format!("unsupported: {message}"),
+            CorpusOutcome::Skipped(message) => format!("skipped: {message}"),
+        }
+    }
+}
+
+pub struct LessonReport {
+    pub id: &'static str,
+    pub chapter: &'static str,
+    pub checks: Vec<Check>,
+    pub markdown: String,
+}
+
+impl LessonReport {
+    pub fn verified(&self) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.verifies)
+            .all(Check::passed)
+    }
+}
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub fn df_to_markdown(df: &DataFrame) -> AppResult<String> {
+    let names: Vec<String> = df
+        .get_column_names()
+        .iter()
+        .map(|name| escape_cell(name))
+        .collect();
+    let mut markdown = format!("| {} |\n", names.join(" | "));
+
+    markdown.push_str(&format!("|{}\n", " --- |".repeat(names.len())));
+
+    for index in 0..df.height().min(ROW_LIMIT) {
+        let row = df
+            .get_columns()
+            .iter()
+            .map(|column| {
+                let value = column.as_materialized_series().str_value(index)?;
+                Ok(escape_cell(&value))
+            })
+            .collect::<PolarsResult<Vec<_>>>()
+            .map_err(AppError::Polars)?;
+
+        markdown.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+
+    if df.height() > ROW_LIMIT {
+        markdown.push_str(&format!("\n{} of {} rows.\n", ROW_LIMIT, df.height()));
+    }
+
+    Ok(markdown)
+}
+
+fn code_block(language: &str, code: &str) -> String {
+    format!("```{language}\n{}\n```\n", code.trim_end())
+}
+
+fn error_block(err: &AppError) -> String {
+    format!("> {}\n", escape_cell(&err.to_string()))
+}
+
+fn compare_results(
+    name: &'static str,
+    df: &AppResult<DataFrame>,
+    expected: &AppResult<DataFrame>,
+) -> AppResult<Check> {
+    let outcome = match (df, expected) {
+        (Ok(df), Ok(expected)) => match diff_dfs(df, expected)? {
+            None => CorpusOutcome::Match,
+            Some(diff) => CorpusOutcome::Mismatch(diff.lines().next().unwrap_or("").to_string()),
+        },
+        (Err(err), _) | (_, Err(err)) => CorpusOutcome::Mismatch(err.to_string()),
+    };
+
+    Ok(Check {
+        name,
+        outcome,
+        verifies: true,
+    })
+}
+
+pub async fn get_explain(db: &Pool<Postgres>, query: &str) -> AppResult<String> {
+    let df = get_df_query(db, &format!("EXPLAIN {query}")).await?;
+    let plan = df
+        .column("QUERY PLAN")
+        .map_err(AppError::Polars)?
+        .str()
+        .map_err(AppError::Polars)?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(plan)
+}
+
+// Note: every section runs on its own, a failing engine is reported on the page instead of
+// stopping the report.
+pub async fn get_lesson_report(
+    db_sea_orm: &DatabaseConnection,
+    db_sqlx: &Pool<Postgres>,
+    catalog: &Catalog,
+    lesson: &Lesson,
+) -> AppResult<LessonReport> {
+    let mut markdown = format!(
+        "# {} - {}\n\n[Index](index.md) · dataset `{}`\n\n",
+        lesson.id,
+        lesson.chapter(),
+        lesson.dataset()
+    );
+
+    // Note: a lesson without sqlx is run on Postgres with the SQL of its comment.
+    let Some(query) = lesson.sqlx_sql().or_else(|| lesson.query()) else {
+        markdown.push_str(&format!(
+            "This lesson has no single query, run it with `lessons run {}`.\n",
+            lesson.id
+        ));
+
+        return Ok(LessonReport {
+            id: lesson.id,
+            chapter: lesson.chapter(),
+            checks: Vec::new(),
+            markdown,
+        });
+    };
+
+    markdown.push_str("## SQL\n\n");
+    markdown.push_str(&code_block("sql", &lesson.query().unwrap_or(query.clone())));
+
+    // Note: the rows of the SeaORM statement are shown when there is a single statement, the
+    // check compares the structs of the SeaORM query with those of sqlx.
+    let mut sea_orm = None;
+
+    if lesson.sea_orm_query.is_some() {
+        markdown.push_str("\n## SeaORM SQL\n\n");
+        match get_sea_orm_statements(lesson).await {
+            Ok(statements) => {
+                markdown.push_str(&code_block("sql", &format!("{};", statements.join(";\n"))));
+
+                if let [statement] = statements.as_slice() {
+                    sea_orm = Some(get_df_query(db_sqlx, statement).await);
+                }
+            }
+            Err(err) => markdown.push_str(&error_block(&err)),
+        }
+    }
+
+    if let Some(code) = lesson.polars_code() {
+        markdown.push_str("\n## Polars\n\n");
+        markdown.push_str(&code_block("rust", code));
+    }
+
+    let postgres = get_df_query(db_sqlx, &query).await;
+    let mut results = vec![("Postgres", &postgres)];
+
+    let polars = match lesson.polars_query {
+        Some(_) => Some(get_df_lesson(db_sea_orm, db_sqlx, lesson, Source::Polars).await),
+        None => None,
+    };
+
+    if let Some(sea_orm) = &sea_orm {
+        results.push(("SeaORM SQL", sea_orm));
+    }
+    if let Some(polars) = &polars {
+        results.push(("Polars", polars));
+    }
+
+    markdown.push_str("\n## Results\n");
+    for (engine, df) in results {
+        markdown.push_str(&format!("\n### {engine}\n\n"));
+        match df {
+            Ok(df) => markdown.push_str(&df_to_markdown(df)?),
+            Err(err) => markdown.push_str(&error_block(err)),
+        }
+    }
+
+    let mut checks = vec![
+        Check {
+            name: "`# QUERY:` comment and sqlx SQL",
+            outcome: lint::check_lesson(lesson),
+            verifies: true,
+        },
+        Check {
+            name: "SeaORM and sqlx SQL",
+            outcome: match statements::check_lesson(catalog, lesson).await {
+                Ok((outcome, _)) => outcome,
+                Err(err) => CorpusOutcome::Mismatch(err.to_string()),
+            },
+            verifies: false,
+        },
+    ];
+    if let Some(compare_sea_orm) = lesson.compare_sea_orm {
+        checks.push(Check {
+            name: "SeaORM and sqlx results",
+            outcome: match compare_sea_orm(db_sea_orm, db_sqlx).await {
+                Ok(true) => CorpusOutcome::Match,
+                Ok(false) => CorpusOutcome::Mismatch("the rows differ".to_string()),
+                Err(err) => CorpusOutcome::Mismatch(err.to_string()),
+            },
+            verifies: true,
+        });
+    }
+    if let Some(polars) = &polars {
+        checks.push(compare_results(
+            "Polars and Postgres results",
+            polars,
+            &postgres,
+        )?);
+    }
+
+    markdown.push_str("\n## Verification\n\n| check | outcome | verifies |\n| --- | --- | --- |\n");
+    for check in &checks {
+        markdown.push_str(&format!(
+            "| {} | {} | {} |\n",
+            check.name,
+            escape_cell(&check.describe()),
+            if check.verifies { "yes" } else { "no" }
+        ));
+    }
+
+    markdown.push_str("\n## EXPLAIN\n\n");
+    match get_explain(db_sqlx, &query).await {
+        Ok(plan) => markdown.push_str(&code_block("text", &plan)),
+        Err(err) => markdown.push_str(&error_block(&err)),
+    }
+
+    Ok(LessonReport {
+        id: lesson.id,
+        chapter: lesson.chapter(),
+        checks,
+        markdown,
+    })
+}
+
+fn get_index(reports: &[LessonReport]) -> String {
+    let mut markdown = "# Course report\n".to_string();
+    let mut chapter = "";
+
+    for report in reports {
+        if report.chapter != chapter {
+            chapter = report.chapter;
+            markdown.push_str(&format!(
+                "\n## {chapter}\n\n| lesson | status |\n| --- | --- |\n"
+            ));
+        }
+
+        let status = if report.checks.is_empty() {
+            "no query".to_string()
+        } else if report.verified() {
+            "verified".to_string()
+        } else {
+            let failed: Vec<&str> = report
+                .checks
+                .iter()
+                .filter(|check| check.verifies && !check.passed())
+                .map(|check| check.name)
+                .collect();
+            format!("check {}", failed.join(", "))
+        };
+
+        markdown.push_str(&format!("| [{id}]({id}.md) | {status} |\n", id = report.id));
+    }
+
+    markdown
+}
+
+// Writes one Markdown page per lesson and an index, each lesson runs on its own dataset.
+pub async fn write_report(dir: &str) -> AppResult<()> {
+    let dir = Path::new(dir);
+    let mut databases: HashMap<&str, (DatabaseConnection, Pool<Postgres>, Catalog)> =
+        HashMap::new();
+    let mut reports = Vec::new();
+
+    std::fs::create_dir_all(dir).map_err(AppError::Io)?;
+
+    for lesson in get_lessons() {
+        let dataset = lesson.dataset();
+
+        let (db_sea_orm, db_sqlx, catalog) = match databases.entry(dataset) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let (db_sea_orm, db_sqlx) = get_database_dataset(dataset).await?;
+                let catalog = get_catalog(&db_sqlx).await?;
+
+                entry.insert((db_sea_orm, db_sqlx, catalog))
+            }
+        };
+
+        let report = get_lesson_report(db_sea_orm, db_sqlx, catalog, &lesson).await?;
+        let path = dir.join(format!("{}.md", report.id));
+
+        std::fs::write(&path, &report.markdown).map_err(AppError::Io)?;
+        log_debug(report.id, &report.verified(), None);
+
+        reports.push(report);
+    }
+
+    std::fs::write(dir.join("index.md"), get_index(&reports)).map_err(AppError::Io)?;
+
+    Ok(())
+}
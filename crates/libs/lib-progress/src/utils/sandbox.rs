@@ -0,0 +1,84 @@
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+// A sandbox table a lesson reads, created before its queries run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sandbox {
+    OrderPayloads,
+}
+
+impl Sandbox {
+    pub async fn reset(self, db: &Pool<Postgres>) -> AppResult<()> {
+        match self {
+            Self::OrderPayloads => reset_order_payloads(db).await,
+        }
+    }
+}
+
+// Note: lessons that write data use their own schema, the course tables stay untouched.
+pub async fn reset_accounts(db: &Pool<Postgres>) -> AppResult<()> {
+    let query = "
+    CREATE SCHEMA IF NOT EXISTS sandbox;
+    DROP TABLE IF EXISTS sandbox.accounts;
+    CREATE TABLE sandbox.accounts (
+        id INT PRIMARY KEY,
+        owner VARCHAR(50),
+        balance BIGINT NOT NULL
+    );
+    INSERT INTO sandbox.accounts VALUES
+        (1, 'Kevin', 100),
+        (2, 'Mary', 100);
+    ";
+
+    sqlx::raw_sql(query)
+        .execute(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(())
+}
+
+// Note: every order becomes one JSONB document, the GIN index serves `@>` lookups.
+pub async fn reset_order_payloads(db: &Pool<Postgres>) -> AppResult<()> {
+    let query = "
+    CREATE SCHEMA IF NOT EXISTS sandbox;
+    DROP TABLE IF EXISTS sandbox.order_payloads;
+    CREATE TABLE sandbox.order_payloads (
+        orderid INT PRIMARY KEY,
+        payload JSONB NOT NULL
+    );
+    INSERT INTO sandbox.order_payloads
+    SELECT
+        o.orderid,
+        jsonb_build_object(
+            'status', o.orderstatus,
+            'customer', jsonb_build_object(
+                'id', c.customerid,
+                'firstname', c.firstname,
+                'country', c.country
+            ),
+            'items', jsonb_build_array(
+                jsonb_build_object(
+                    'product', p.product,
+                    'quantity', o.quantity,
+                    'price', p.price
+                )
+            )
+        )
+    FROM sales.orders AS o
+    LEFT JOIN sales.customers AS c
+    ON o.customerid = c.customerid
+    LEFT JOIN sales.products AS p
+    ON o.productid = p.productid;
+    CREATE INDEX idx_order_payloads_payload
+    ON sandbox.order_payloads USING GIN (payload);
+    ";
+
+    sqlx::raw_sql(query)
+        .execute(db)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    Ok(())
+}
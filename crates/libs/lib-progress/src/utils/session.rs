@@ -0,0 +1,277 @@
+use std::time::Duration;
+
+use polars::prelude::*;
+use sqlx::postgres::PgRow;
+use sqlx::{Column, PgConnection, Pool, Postgres, Row, TypeInfo};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::timeout;
+
+use lib_core::error::{AppError, AppResult};
+
+// Note: how often a step that has not answered is checked for a lock wait.
+const POLL_EVERY: Duration = Duration::from_millis(10);
+
+// Note: one connection for each session and one to watch them wait.
+const CONNECTIONS: u32 = 3;
+
+const WAITING_ON_LOCK: &str = "SELECT coalesce(bool_or(wait_event_type = 'Lock'), false) FROM pg_stat_activity WHERE pid = $1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Session {
+    A,
+    B,
+}
+
+impl Session {
+    fn index(self) -> usize {
+        match self {
+            Session::A => 0,
+            Session::B => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub session: Session,
+    pub sql: &'static str,
+}
+
+pub fn step(session: Session, sql: &'static str) -> Step {
+    Step { session, sql }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    // First column of every returned row, statements without rows give an empty Vec.
+    Rows(Vec<i64>),
+    Failed {
+        code: Option<String>,
+        message: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct StepResult {
+    pub session: Session,
+    pub sql: &'static str,
+    pub blocked: bool,
+    pub outcome: Outcome,
+}
+
+impl StepResult {
+    pub fn value(&self) -> Option<i64> {
+        match &self.outcome {
+            Outcome::Rows(values) => values.first().copied(),
+            Outcome::Failed { .. } => None,
+        }
+    }
+
+    pub fn failed_with(&self, sqlstate: &str) -> bool {
+        matches!(&self.outcome, Outcome::Failed { code: Some(code), .. } if code == sqlstate)
+    }
+}
+
+type Command = (&'static str, oneshot::Sender<Outcome>);
+
+// Note: the first column as an integer of any width, another type is a failed step rather
+// than a row silently left out.
+fn get_values(sql: &str, rows: &[PgRow]) -> Outcome {
+    let Some(column) = rows.first().and_then(|row| row.columns().first()) else {
+        return Outcome::Rows(Vec::new());
+    };
+    let type_name = column.type_info().name().to_string();
+    let values: Result<Vec<i64>, sqlx::Error> = rows
+        .iter()
+        .map(|row| match type_name.as_str() {
+            "INT2" => row.try_get::<Option<i16>, _>(0).map(|v| v.map(i64::from)),
+            "INT4" => row.try_get::<Option<i32>, _>(0).map(|v| v.map(i64::from)),
+            _ => row.try_get::<Option<i64>, _>(0),
+        })
+        .filter_map(Result::transpose)
+        .collect();
+
+    match values {
+        Ok(values) => Outcome::Rows(values),
+        Err(_) => Outcome::Failed {
+            code: None,
+            message: format!("the first column of `{sql}` is {type_name}, cast it to an integer"),
+        },
+    }
+}
+
+// A session on a connection of its own, with the backend pid Postgres knows it by.
+async fn open_session(db: &Pool<Postgres>) -> AppResult<(mpsc::UnboundedSender<Command>, i32)> {
+    let mut conn = db.acquire().await.map_err(AppError::Sqlx)?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<Command>();
+
+    // Note: a broken script must fail instead of waiting on a lock forever.
+    sqlx::query("SET lock_timeout = '5s'")
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::Sqlx)?;
+
+    tokio::spawn(async move {
+        while let Some((sql, reply)) = rx.recv().await {
+            let outcome = match sqlx::query(sql).fetch_all(&mut *conn).await {
+                Ok(rows) => get_values(sql, &rows),
+                Err(sqlx::Error::Database(err)) => Outcome::Failed {
+                    code: err.code().map(|code| code.into_owned()),
+                    message: err.message().to_string(),
+                },
+                Err(err) => Outcome::Failed {
+                    code: None,
+                    message: err.to_string(),
+                },
+            };
+            let _ = reply.send(outcome);
+        }
+
+        // Note: never hand a connection with an open transaction or the lock timeout of the
+        // session back to the pool.
+        let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+        let _ = sqlx::query("RESET lock_timeout").execute(&mut *conn).await;
+    });
+
+    Ok((tx, pid))
+}
+
+async fn receive(reply: oneshot::Receiver<Outcome>) -> AppResult<Outcome> {
+    reply
+        .await
+        .map_err(|_| AppError::Assertion("session closed before answering".to_string()))
+}
+
+// The outcome of the step, or `None` once Postgres shows its session waiting on a lock.
+// Note: polled from a connection of its own, neither session can ask while it waits.
+async fn wait_step(
+    monitor: &mut PgConnection,
+    pid: i32,
+    reply: &mut oneshot::Receiver<Outcome>,
+) -> AppResult<Option<Outcome>> {
+    loop {
+        if let Ok(outcome) = timeout(POLL_EVERY, &mut *reply).await {
+            return outcome
+                .map(Some)
+                .map_err(|_| AppError::Assertion("session closed before answering".to_string()));
+        }
+
+        let waiting: bool = sqlx::query_scalar(WAITING_ON_LOCK)
+            .bind(pid)
+            .fetch_one(&mut *monitor)
+            .await
+            .map_err(AppError::Sqlx)?;
+
+        if waiting {
+            return Ok(None);
+        }
+    }
+}
+
+// Runs the steps strictly in order, one session at a time. A blocked step keeps
+// waiting in its session while the other session goes on; it is resolved before
+// its own session receives the next step.
+pub async fn run_interleaving(db: &Pool<Postgres>, steps: &[Step]) -> AppResult<Vec<StepResult>> {
+    let max_connections = db.options().get_max_connections();
+
+    if max_connections < CONNECTIONS {
+        return Err(AppError::Assertion(format!(
+            "the sessions need {CONNECTIONS} connections and the pool has at most \
+            {max_connections}, set DATABASE_MAX_CONNECTIONS to {CONNECTIONS} or more"
+        )));
+    }
+
+    let mut monitor = db.acquire().await.map_err(AppError::Sqlx)?;
+    let sessions = [open_session(db).await?, open_session(db).await?];
+    let mut pending: [Option<(usize, oneshot::Receiver<Outcome>)>; 2] = [None, None];
+    let mut results: Vec<Option<StepResult>> = vec![None; steps.len()];
+
+    for (index, step) in steps.iter().enumerate() {
+        let session = step.session.index();
+
+        if let Some((blocked_index, reply)) = pending[session].take() {
+            let outcome = receive(reply).await?;
+
+            results[blocked_index] = Some(StepResult {
+                session: steps[blocked_index].session,
+                sql: steps[blocked_index].sql,
+                blocked: true,
+                outcome,
+            });
+        }
+
+        let (tx, mut reply) = oneshot::channel();
+        let (sender, pid) = &sessions[session];
+
+        sender
+            .send((step.sql, tx))
+            .map_err(|_| AppError::Assertion("session closed before the step".to_string()))?;
+
+        let Some(outcome) = wait_step(&mut monitor, *pid, &mut reply).await? else {
+            pending[session] = Some((index, reply));
+            continue;
+        };
+
+        results[index] = Some(StepResult {
+            session: step.session,
+            sql: step.sql,
+            blocked: false,
+            outcome,
+        });
+    }
+
+    for (blocked_index, reply) in pending.into_iter().flatten() {
+        results[blocked_index] = Some(StepResult {
+            session: steps[blocked_index].session,
+            sql: steps[blocked_index].sql,
+            blocked: true,
+            outcome: receive(reply).await?,
+        });
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+pub fn get_df_timeline(results: &[StepResult]) -> AppResult<DataFrame> {
+    let iter = results.iter();
+    let steps: Vec<u32> = (1..=results.len() as u32).collect();
+    let sessions: Vec<String> = iter.clone().map(|r| format!("{:?}", r.session)).collect();
+    let sqls: Vec<&str> = iter.clone().map(|r| r.sql).collect();
+    let blocked: Vec<bool> = iter.clone().map(|r| r.blocked).collect();
+    let values: Vec<Option<i64>> = iter.clone().map(|r| r.value()).collect();
+    let errors: Vec<Option<String>> = iter
+        .clone()
+        .map(|r| match &r.outcome {
+            Outcome::Rows(_) => None,
+            Outcome::Failed { code, message } => Some(match code {
+                Some(code) => format!("{code}: {message}"),
+                None => message.clone(),
+            }),
+        })
+        .collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("step".into(), steps).into(),
+        Series::new("session".into(), sessions).into(),
+        Series::new("sql".into(), sqls).into(),
+        Series::new("blocked".into(), blocked).into(),
+        Series::new("value".into(), values).into(),
+        Series::new("error".into(), errors).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+pub fn expect(condition: bool, message: &str) -> AppResult<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(AppError::Assertion(message.to_string()))
+    }
+}
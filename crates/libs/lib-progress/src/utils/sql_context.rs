@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::ops::ControlFlow;
+
+use polars::prelude::*;
+use polars::sql::SQLContext;
+use sea_orm::DatabaseConnection;
+use sqlparser::ast::visit_relations_mut;
+use sqlx::{Pool, Postgres};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lessons};
+use crate::utils::compare::diff_dfs;
+use crate::utils::database::get_database_dataset;
+use crate::utils::dataframe::get_df_query;
+use crate::utils::debug::log_debug;
+use crate::utils::transpiler::{CorpusOutcome, get_tables, parse_query};
+
+/*
+# POLARS SQL vs POSTGRES (polars 0.51):
+
+match        LIKE '%__r%' (p_027), FULL JOIN (p_031, p_034), CROSS JOIN (p_035),
+             UNION (p_042, p_043, p_046)
+unsupported  HAVING on a column that is not selected (p_010 - p_012), EXCEPT / INTERSECT
+             (p_044, p_045), GROUPING() (p_049 - p_051), crosstab (p_053), LATERAL (p_054),
+             aggregate FILTER (p_056, p_058), JSONB operators and functions (p_069 - p_072)
+*/
+
+// Note: Polars SQL only reads the first part of a table name, so `sales.orders` is
+// registered as `orders` and the schema is stripped from the query.
+pub async fn sql_context_query(db: &DatabaseConnection, sql: &str) -> AppResult<LazyFrame> {
+    let mut query = parse_query(sql)?;
+    let tables = get_tables(db, &query).await?;
+    let mut ctx = SQLContext::new();
+
+    for (name, lf) in tables {
+        let table = name.rsplit('.').next().unwrap_or(&name);
+        ctx.register(table, lf);
+    }
+
+    let _ = visit_relations_mut(&mut query, |relation| {
+        if relation.0.len() > 1 {
+            relation.0.drain(..relation.0.len() - 1);
+        }
+        ControlFlow::<()>::Continue(())
+    });
+
+    ctx.execute(&query.to_string()).map_err(AppError::Polars)
+}
+
+// Postgres is the reference here, the lesson SQL runs unchanged on both sides.
+pub async fn check_lesson(
+    db_sea_orm: &DatabaseConnection,
+    db_sqlx: &Pool<Postgres>,
+    lesson: &Lesson,
+) -> AppResult<CorpusOutcome> {
+    let Some(query) = lesson.query() else {
+        return Ok(CorpusOutcome::Skipped("no query".to_string()));
+    };
+
+    // Note: a query Postgres rejects, e.g. on a table of another dataset, is skipped.
+    let expected = match get_df_query(db_sqlx, &query).await {
+        Ok(df) => df,
+        Err(AppError::Sqlx(err @ sqlx::Error::Database(_))) => {
+            return Ok(CorpusOutcome::Skipped(err.to_string()));
+        }
+        Err(err) => return Err(err),
+    };
+    let df = match sql_context_query(db_sea_orm, &query).await {
+        Ok(lf) => lf.collect(),
+        Err(AppError::Polars(err)) => Err(err),
+        Err(AppError::Transpile(message)) => return Ok(CorpusOutcome::Unsupported(message)),
+        // Note: a table the database lacks, e.g. of another dataset, is skipped.
+        Err(AppError::Assertion(message)) => return Ok(CorpusOutcome::Skipped(message)),
+        Err(err) => return Err(err),
+    };
+    let df = match df {
+        Ok(df) => df,
+        Err(err) => return Ok(CorpusOutcome::Unsupported(err.to_string())),
+    };
+
+    match diff_dfs(&df, &expected)? {
+        None => Ok(CorpusOutcome::Match),
+        Some(diff) => Ok(CorpusOutcome::Mismatch(diff)),
+    }
+}
+
+// Runs each lesson on its own dataset. A mismatch is reported, not an error, see above.
+pub async fn display_sql_context() -> AppResult<()> {
+    let mut databases = HashMap::new();
+
+    for lesson in get_lessons() {
+        let (db_sea_orm, db_sqlx) = match databases.entry(lesson.dataset()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(get_database_dataset(lesson.dataset()).await?),
+        };
+        let outcome = check_lesson(db_sea_orm, db_sqlx, &lesson).await?;
+
+        log_debug(lesson.id, &outcome, None);
+    }
+
+    Ok(())
+}
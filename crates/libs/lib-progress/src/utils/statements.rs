@@ -0,0 +1,560 @@
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::ControlFlow;
+
+use polars::prelude::*;
+use sea_orm::{DbBackend, MockDatabase, MockExecResult, Value};
+use sqlparser::ast::{
+    BinaryOperator, Expr, GroupByExpr, Ident, ObjectName, Query, SelectItem, SetExpr, TableFactor,
+    TableWithJoins, Visit, VisitMut, Visitor, VisitorMut,
+};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lessons};
+use crate::utils::catalog::{Catalog, get_catalog};
+use crate::utils::database::get_database_dataset;
+use crate::utils::debug::log_debug;
+use crate::utils::transpiler::{CorpusOutcome, parse_query};
+
+/*
+# SEA ORM vs SQLX STATEMENTS:
+
+match        p_001 - p_011, p_013 - p_034, p_036 - p_041, p_048, p_054, p_069 - p_071
+select       `find_also_related` / `find_with_related` select every column of both tables
+             as `A_` / `B_` (p_055 - p_057), casts, CONCAT and qualifiers differ
+             (p_012, p_047, p_049 - p_052)
+self join    SeaORM keeps the first table unaliased (p_060 - p_062)
+statements   UNION lessons build one statement per table (p_042 - p_046), the loaders of
+             p_058 and p_059 build theirs from the rows and are not compared
+where        SeaORM casts the JSONB literal (p_072)
+from         the crosstab source keeps the indentation of the SeaORM code (p_053)
+*/
+
+#[derive(Clone, Debug)]
+pub struct Clause {
+    pub name: &'static str,
+    pub sea_orm: String,
+    pub sqlx: String,
+}
+
+impl Clause {
+    pub fn same(&self) -> bool {
+        self.sea_orm == self.sqlx
+    }
+}
+
+#[derive(Default)]
+struct Relations {
+    // Table name and alias of every table in the query, in order.
+    tables: Vec<(String, Option<String>)>,
+}
+
+impl Visitor for Relations {
+    type Break = ();
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<()> {
+        if let TableFactor::Table { name, alias, .. } = table_factor
+            && let Some(table) = name.0.last()
+        {
+            let alias = alias.as_ref().map(|alias| alias.name.value.clone());
+            self.tables.push((table.value.clone(), alias));
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+// Note: `*` and `t.*` become the columns of the tables in the order of the catalog, the
+// list SeaORM's `find()` selects, for the tables the catalog knows.
+struct Wildcards<'a> {
+    catalog: &'a Catalog,
+}
+
+fn get_name(name: &ObjectName) -> String {
+    name.0
+        .iter()
+        .map(|ident| ident.value.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+impl Wildcards<'_> {
+    // The qualifier and the columns of every table of a FROM clause, in order.
+    fn get_tables(&self, from: &[TableWithJoins]) -> Option<Vec<(ObjectName, Vec<String>)>> {
+        from.iter()
+            .flat_map(|table| {
+                std::iter::once(&table.relation).chain(table.joins.iter().map(|j| &j.relation))
+            })
+            .map(|relation| {
+                let TableFactor::Table { name, alias, .. } = relation else {
+                    return None;
+                };
+                let table = self.catalog.table(&get_name(name))?;
+                let qualifier = match alias {
+                    Some(alias) => ObjectName(vec![alias.name.clone()]),
+                    None => name.clone(),
+                };
+                let columns = table.columns.iter().map(|c| c.name.clone()).collect();
+
+                Some((qualifier, columns))
+            })
+            .collect()
+    }
+}
+
+impl VisitorMut for Wildcards<'_> {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<()> {
+        if let SetExpr::Select(select) = query.body.as_mut()
+            && let Some(tables) = self.get_tables(&select.from)
+        {
+            let mut projection = Vec::new();
+
+            for item in select.projection.drain(..) {
+                let expanded: Vec<&(ObjectName, Vec<String>)> = match &item {
+                    SelectItem::Wildcard(_) => tables.iter().collect(),
+                    SelectItem::QualifiedWildcard(name, _) => tables
+                        .iter()
+                        .filter(|(qualifier, _)| get_name(qualifier) == get_name(name))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                if expanded.is_empty() {
+                    projection.push(item);
+                    continue;
+                }
+
+                for (qualifier, columns) in expanded {
+                    for column in columns {
+                        let mut idents = qualifier.0.clone();
+
+                        idents.push(Ident::new(column));
+                        projection.push(SelectItem::UnnamedExpr(Expr::CompoundIdentifier(idents)));
+                    }
+                }
+            }
+
+            select.projection = projection;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+struct Normalizer {
+    // Alias to table name, for the tables that appear only once.
+    aliases: HashMap<String, String>,
+    tables: Vec<String>,
+    single_table: bool,
+}
+
+// Postgres operator precedence, higher binds tighter.
+fn precedence(op: &BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::Or => 1,
+        BinaryOperator::And => 2,
+        BinaryOperator::Eq
+        | BinaryOperator::NotEq
+        | BinaryOperator::Lt
+        | BinaryOperator::Gt
+        | BinaryOperator::LtEq
+        | BinaryOperator::GtEq => 3,
+        BinaryOperator::Plus | BinaryOperator::Minus => 5,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 6,
+        _ => 4,
+    }
+}
+
+fn get_column(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Identifier(column) => Some(column),
+        Expr::CompoundIdentifier(idents) => idents.last(),
+        _ => None,
+    }
+}
+
+fn unnest(expr: &mut Expr, keep: impl Fn(&Expr) -> bool) {
+    while let Expr::Nested(inner) = expr
+        && !keep(inner)
+    {
+        *expr = inner.as_ref().clone();
+    }
+}
+
+impl VisitorMut for Normalizer {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<()> {
+        if let SetExpr::Select(select) = query.body.as_mut() {
+            for item in select.projection.iter_mut() {
+                if let SelectItem::ExprWithAlias { expr, alias } = item
+                    && get_column(expr).is_some_and(|column| column.value == alias.value)
+                {
+                    *item = SelectItem::UnnamedExpr(expr.clone());
+                }
+            }
+
+            for expr in [&mut select.selection, &mut select.having]
+                .into_iter()
+                .flatten()
+            {
+                unnest(expr, |_| false);
+            }
+        }
+
+        // Note: ASC is the default order.
+        for order_by in query.order_by.iter_mut().flat_map(|o| o.exprs.iter_mut()) {
+            if order_by.asc == Some(true) {
+                order_by.asc = None;
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &mut TableFactor) -> ControlFlow<()> {
+        if let TableFactor::Table { alias, .. } = table_factor
+            && alias
+                .as_ref()
+                .is_some_and(|alias| self.aliases.contains_key(&alias.name.value))
+        {
+            *alias = None;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<()> {
+        // Note: SeaORM wraps custom expressions in parentheses.
+        unnest(expr, |inner| {
+            !matches!(
+                inner,
+                Expr::Function(_) | Expr::Identifier(_) | Expr::CompoundIdentifier(_)
+            )
+        });
+
+        if let Expr::BinaryOp { left, op, right } = expr {
+            let outer = precedence(op);
+
+            unnest(
+                left,
+                |inner| !matches!(inner, Expr::BinaryOp { op, .. } if precedence(op) >= outer),
+            );
+            unnest(
+                right,
+                |inner| !matches!(inner, Expr::BinaryOp { op, .. } if precedence(op) > outer),
+            );
+
+            // Note: both sides of an equality are sorted, `a = b` is the same as `b = a`.
+            let key = |expr: &Expr| expr.to_string().replace('"', "");
+
+            if *op == BinaryOperator::Eq && key(left) > key(right) {
+                std::mem::swap(left, right);
+            }
+        }
+
+        if let Expr::CompoundIdentifier(idents) = expr
+            && let [.., qualifier, column] = idents.as_slice()
+        {
+            let table = self
+                .aliases
+                .get(&qualifier.value)
+                .unwrap_or(&qualifier.value);
+
+            if self.tables.contains(table) {
+                *expr = if self.single_table {
+                    Expr::Identifier(column.clone())
+                } else {
+                    Expr::CompoundIdentifier(vec![Ident::new(table), column.clone()])
+                };
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+// Note: wildcards are expanded, aliases of tables that appear once are replaced by the
+// table name, the columns of a query on a single table lose their qualifier and redundant
+// parentheses, aliases and ASC are dropped.
+fn normalize_query(catalog: &Catalog, sql: &str) -> AppResult<Box<Query>> {
+    let mut query = parse_query(sql)?;
+    let _ = VisitMut::visit(&mut query, &mut Wildcards { catalog });
+    let mut relations = Relations::default();
+    let _ = Visit::visit(&query, &mut relations);
+
+    let tables: Vec<String> = relations.tables.iter().map(|(t, _)| t.clone()).collect();
+    let aliases = relations
+        .tables
+        .iter()
+        .filter(|(table, _)| tables.iter().filter(|t| *t == table).count() == 1)
+        .filter_map(|(table, alias)| Some((alias.clone()?, table.clone())))
+        .collect();
+    let mut normalizer = Normalizer {
+        aliases,
+        single_table: tables.len() == 1,
+        tables,
+    };
+    let _ = VisitMut::visit(&mut query, &mut normalizer);
+
+    Ok(query)
+}
+
+// Note: quotes around a lowercase identifier do not change its meaning in Postgres.
+fn unquote(sql: &str) -> AppResult<String> {
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, sql)
+        .tokenize()
+        .map_err(|err| AppError::Transpile(err.to_string()))?;
+
+    Ok(tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::Word(mut word)
+                if word.quote_style == Some('"')
+                    && word
+                        .value
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') =>
+            {
+                word.quote_style = None;
+                Token::Word(word).to_string()
+            }
+            token => token.to_string(),
+        })
+        .collect())
+}
+
+fn get_clauses(query: &Query) -> Vec<(&'static str, String)> {
+    let display = |value: Option<String>| value.unwrap_or_default();
+    let mut clauses = vec![("with", display(query.with.as_ref().map(|w| w.to_string())))];
+
+    match query.body.as_ref() {
+        SetExpr::Select(select) => {
+            let projection = select
+                .projection
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let from = select
+                .from
+                .iter()
+                .map(|table| table.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let distinct = display(select.distinct.as_ref().map(|d| format!("{d} ")));
+
+            clauses.push(("select", format!("{distinct}{projection}")));
+            clauses.push(("from", from));
+            clauses.push((
+                "where",
+                display(select.selection.as_ref().map(|e| e.to_string())),
+            ));
+            let group_by = match &select.group_by {
+                GroupByExpr::Expressions(exprs, modifiers)
+                    if exprs.is_empty() && modifiers.is_empty() =>
+                {
+                    String::new()
+                }
+                group_by => group_by.to_string(),
+            };
+
+            clauses.push(("group by", group_by));
+            clauses.push((
+                "having",
+                display(select.having.as_ref().map(|e| e.to_string())),
+            ));
+        }
+        body => clauses.push(("body", body.to_string())),
+    }
+
+    clauses.push((
+        "order by",
+        display(query.order_by.as_ref().map(|o| o.to_string())),
+    ));
+    clauses.push((
+        "limit",
+        display(query.limit.as_ref().map(|l| l.to_string())),
+    ));
+    clauses.push((
+        "offset",
+        display(query.offset.as_ref().map(|o| o.to_string())),
+    ));
+
+    clauses
+}
+
+pub fn compare_statements(catalog: &Catalog, sea_orm: &str, sqlx: &str) -> AppResult<Vec<Clause>> {
+    let sea_orm = normalize_query(catalog, sea_orm)?;
+    let sqlx = normalize_query(catalog, sqlx)?;
+    let sea_orm_clauses = get_clauses(&sea_orm);
+    let sqlx_clauses = get_clauses(&sqlx);
+
+    // Note: a set operation against a single SELECT, compare the whole statements.
+    if sea_orm_clauses.len() != sqlx_clauses.len() {
+        return compare_bodies(&sea_orm.to_string(), &sqlx.to_string());
+    }
+
+    let mut clauses = Vec::new();
+
+    for ((name, sea_orm), (_, sqlx)) in sea_orm_clauses.into_iter().zip(sqlx_clauses) {
+        if !sea_orm.is_empty() || !sqlx.is_empty() {
+            clauses.push(Clause {
+                name,
+                sea_orm: unquote(&sea_orm)?,
+                sqlx: unquote(&sqlx)?,
+            });
+        }
+    }
+
+    Ok(clauses)
+}
+
+fn compare_bodies(sea_orm: &str, sqlx: &str) -> AppResult<Vec<Clause>> {
+    Ok(vec![Clause {
+        name: "statement",
+        sea_orm: unquote(sea_orm)?,
+        sqlx: unquote(sqlx)?,
+    }])
+}
+
+pub fn get_df_statements(clauses: &[Clause]) -> AppResult<DataFrame> {
+    let iter = clauses.iter();
+    let names: Vec<&str> = iter.clone().map(|c| c.name).collect();
+    let sea_orm: Vec<&str> = iter.clone().map(|c| c.sea_orm.as_str()).collect();
+    let sqlx: Vec<&str> = iter.clone().map(|c| c.sqlx.as_str()).collect();
+    let same: Vec<bool> = iter.clone().map(|c| c.same()).collect();
+
+    let df = DataFrame::new(vec![
+        Series::new("clause".into(), names).into(),
+        Series::new("sea_orm".into(), sea_orm).into(),
+        Series::new("sqlx".into(), sqlx).into(),
+        Series::new("same".into(), same).into(),
+    ])
+    .map_err(AppError::Polars)?;
+
+    Ok(df)
+}
+
+// Note: a result set per statement, more than any lesson sends.
+const MOCK_RESULTS: usize = 16;
+
+// Runs the SeaORM query of the lesson on a mock connection and returns the statements it
+// builds with `build(DbBackend::Postgres)`, with their values inlined. Nothing reaches
+// Postgres and every query returns no rows.
+pub async fn get_sea_orm_statements(lesson: &Lesson) -> AppResult<Vec<String>> {
+    let sea_orm_query = lesson
+        .sea_orm_query
+        .ok_or_else(|| AppError::Assertion(format!("{} has no SeaORM query", lesson.id)))?;
+
+    // Note: a loader builds its `WHERE ... IN` from the rows it gets, which the mock lacks.
+    if lesson.loader {
+        return Err(AppError::Assertion(format!(
+            "{} loads related rows, its statements depend on the rows",
+            lesson.id
+        )));
+    }
+
+    let db = MockDatabase::new(DbBackend::Postgres)
+        .append_query_results(vec![Vec::<BTreeMap<&str, Value>>::new(); MOCK_RESULTS])
+        .append_exec_results(vec![MockExecResult::default(); MOCK_RESULTS])
+        .into_connection();
+
+    // Note: the result of the lesson on no rows says nothing, the statements are built first.
+    let _ = sea_orm_query(&db).await;
+
+    Ok(db
+        .into_transaction_log()
+        .iter()
+        .flat_map(|transaction| transaction.statements())
+        .map(|statement| statement.to_string())
+        .collect())
+}
+
+// Builds the SeaORM statements of the lesson and compares them with the SQL of its sqlx
+// query.
+pub async fn check_lesson(
+    catalog: &Catalog,
+    lesson: &Lesson,
+) -> AppResult<(CorpusOutcome, Vec<Clause>)> {
+    let (Some(_), Some(sqlx)) = (lesson.sea_orm_query, lesson.sqlx_sql()) else {
+        return Ok((
+            CorpusOutcome::Skipped("no SeaORM or no sqlx query".to_string()),
+            Vec::new(),
+        ));
+    };
+
+    if lesson.loader {
+        return Ok((
+            CorpusOutcome::Unsupported("loads related rows".to_string()),
+            Vec::new(),
+        ));
+    }
+
+    let statements = match get_sea_orm_statements(lesson).await {
+        Ok(statements) => statements,
+        Err(AppError::Assertion(message)) => {
+            return Ok((CorpusOutcome::Unsupported(message), Vec::new()));
+        }
+        Err(err) => return Err(err),
+    };
+
+    let [sea_orm] = statements.as_slice() else {
+        // Note: lessons without a union API send one statement per table.
+        let sea_orm = statements
+            .iter()
+            .map(|statement| Ok(normalize_query(catalog, statement)?.to_string()))
+            .collect::<AppResult<Vec<_>>>()?;
+        let sqlx = normalize_query(catalog, &sqlx)?.to_string();
+        let clauses = compare_bodies(&sea_orm.join(";\n"), &sqlx)?;
+        let message = format!("SeaORM builds {} statements", statements.len());
+        return Ok((CorpusOutcome::Mismatch(message), clauses));
+    };
+
+    let clauses = compare_statements(catalog, sea_orm, &sqlx)?;
+    let different: Vec<&str> = clauses
+        .iter()
+        .filter(|clause| !clause.same())
+        .map(|clause| clause.name)
+        .collect();
+
+    if different.is_empty() {
+        Ok((CorpusOutcome::Match, clauses))
+    } else {
+        Ok((CorpusOutcome::Mismatch(different.join(", ")), clauses))
+    }
+}
+
+// Runs each lesson on the catalog of its own dataset and prints the clauses that differ.
+pub async fn display_statements() -> AppResult<()> {
+    let mut catalogs = HashMap::new();
+
+    for lesson in get_lessons() {
+        let catalog = match catalogs.entry(lesson.dataset()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let (_, db_sqlx) = get_database_dataset(lesson.dataset()).await?;
+                entry.insert(get_catalog(&db_sqlx).await?)
+            }
+        };
+        let (outcome, clauses) = check_lesson(catalog, &lesson).await?;
+
+        log_debug(lesson.id, &outcome, None);
+
+        if let CorpusOutcome::Mismatch(_) = outcome {
+            log_debug("STATEMENTS", &get_df_statements(&clauses)?, None);
+
+            for clause in clauses.iter().filter(|clause| !clause.same()) {
+                log_debug("SEA ORM", &clause.sea_orm, None);
+                log_debug("SQLX", &clause.sqlx, None);
+            }
+        }
+    }
+
+    Ok(())
+}
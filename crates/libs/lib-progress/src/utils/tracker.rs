@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use lib_core::connection::get_db_sqlx;
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lesson, get_lessons};
+use crate::utils::catalog::{check_tables, get_catalog};
+use crate::utils::debug::log_debug;
+
+const APP_DIR: &str = "learning-sql-querying";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LessonProgress {
+    pub runs: u32,
+    // Exercise submissions, see `utils::exercise`.
+    pub attempts: u32,
+    pub passed: bool,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub passed_at: Option<DateTime<Utc>>,
+}
+
+impl LessonProgress {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            runs: 0,
+            attempts: 0,
+            passed: false,
+            first_seen: now,
+            last_seen: now,
+            passed_at: None,
+        }
+    }
+
+    fn pass(&mut self, now: DateTime<Utc>) {
+        if !self.passed {
+            self.passed = true;
+            self.passed_at = Some(now);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Tracker {
+    pub lessons: BTreeMap<String, LessonProgress>,
+}
+
+// Note: `PROGRESS_FILE` overrides the default file in the user's data directory.
+pub fn get_progress_path() -> AppResult<PathBuf> {
+    if let Some(path) = std::env::var_os("PROGRESS_FILE") {
+        return Ok(PathBuf::from(path));
+    }
+
+    let data_dir = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None if cfg!(windows) => std::env::var_os("APPDATA")
+            .map(PathBuf::from)
+            .ok_or_else(|| AppError::Assertion("APPDATA is not set".to_string()))?,
+        None => {
+            let home = std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .ok_or_else(|| AppError::Assertion("HOME is not set".to_string()))?;
+
+            if cfg!(target_os = "macos") {
+                home.join("Library").join("Application Support")
+            } else {
+                home.join(".local").join("share")
+            }
+        }
+    };
+
+    Ok(data_dir.join(APP_DIR).join("progress.json"))
+}
+
+impl Tracker {
+    // A missing file is a learner who has not started yet.
+    pub fn load() -> AppResult<Self> {
+        let path = get_progress_path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json = std::fs::read_to_string(&path).map_err(AppError::Io)?;
+
+        serde_json::from_str(&json).map_err(|err| AppError::Dynamic(err.into()))
+    }
+
+    // Note: the file is written next to the old one and renamed, a crash never leaves
+    // half a file behind.
+    pub fn save(&self) -> AppResult<()> {
+        let path = get_progress_path()?;
+        let json =
+            serde_json::to_string_pretty(self).map_err(|err| AppError::Dynamic(err.into()))?;
+        let tmp = path.with_extension("json.tmp");
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(AppError::Io)?;
+        }
+        std::fs::write(&tmp, json).map_err(AppError::Io)?;
+        std::fs::rename(&tmp, &path).map_err(AppError::Io)?;
+
+        Ok(())
+    }
+
+    fn get_progress(&mut self, id: &str, now: DateTime<Utc>) -> &mut LessonProgress {
+        let progress = self
+            .lessons
+            .entry(id.to_string())
+            .or_insert_with(|| LessonProgress::new(now));
+        progress.last_seen = now;
+
+        progress
+    }
+
+    // Note: a lesson without a query has no exercise, running it is enough to pass it.
+    pub fn record_run(&mut self, lesson: &Lesson) {
+        let now = Utc::now();
+        let progress = self.get_progress(lesson.id, now);
+        progress.runs += 1;
+
+        if lesson.query().is_none() {
+            progress.pass(now);
+        }
+    }
+
+    pub fn record_attempt(&mut self, id: &str, passed: bool) {
+        let now = Utc::now();
+        let progress = self.get_progress(id, now);
+        progress.attempts += 1;
+
+        if passed {
+            progress.pass(now);
+        }
+    }
+
+    pub fn is_passed(&self, id: &str) -> bool {
+        self.lessons.get(id).is_some_and(|progress| progress.passed)
+    }
+
+    // The first lesson of the course that is not passed yet.
+    pub fn next_lesson(&self) -> Option<&'static str> {
+        get_lessons()
+            .into_iter()
+            .find(|lesson| !self.is_passed(lesson.id))
+            .map(|lesson| lesson.id)
+    }
+
+    pub fn get_df_chapters(&self) -> AppResult<DataFrame> {
+        let mut chapters: Vec<&str> = Vec::new();
+        let mut totals: Vec<u32> = Vec::new();
+        let mut attempted: Vec<u32> = Vec::new();
+        let mut passed: Vec<u32> = Vec::new();
+
+        for lesson in get_lessons() {
+            let chapter = lesson.chapter();
+
+            if chapters.last() != Some(&chapter) {
+                chapters.push(chapter);
+                totals.push(0);
+                attempted.push(0);
+                passed.push(0);
+            }
+
+            let index = chapters.len() - 1;
+            totals[index] += 1;
+            attempted[index] += u32::from(self.lessons.contains_key(lesson.id));
+            passed[index] += u32::from(self.is_passed(lesson.id));
+        }
+
+        let completion: Vec<u32> = passed
+            .iter()
+            .zip(&totals)
+            .map(|(passed, total)| passed * 100 / total)
+            .collect();
+
+        let df = df!(
+            "chapter" => chapters,
+            "lessons" => totals,
+            "attempted" => attempted,
+            "passed" => passed,
+            "completion_pct" => completion,
+        )
+        .map_err(AppError::Polars)?;
+
+        Ok(df)
+    }
+}
+
+pub fn display_progress() -> AppResult<()> {
+    let tracker = Tracker::load()?;
+
+    log_debug("PROGRESS", &tracker.get_df_chapters()?, None);
+
+    match tracker.next_lesson() {
+        Some(id) => log_debug("NEXT LESSON", &id, None),
+        None => log_debug("NEXT LESSON", &"course completed", None),
+    }
+
+    Ok(())
+}
+
+pub async fn display_lesson(id: &str) -> AppResult<()> {
+    let lesson = get_lesson(id).ok_or_else(|| AppError::Assertion(format!("no lesson {id}")))?;
+    let db = get_db_sqlx().await?;
+
+    check_tables(&get_catalog(&db).await?, &lesson)?;
+    (lesson.display_table)().await?;
+
+    let mut tracker = Tracker::load()?;
+    tracker.record_run(&lesson);
+    tracker.save()
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use sea_orm::DatabaseConnection;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::progress::{Lesson, get_lessons};
+use crate::utils::compare::diff_dfs;
+use crate::utils::database::get_database_dataset;
+use crate::utils::debug::log_debug;
+
+use super::transpile_sql;
+
+#[derive(Debug)]
+pub enum CorpusOutcome {
+    Match,
+    Mismatch(String),
+    Unsupported(String),
+    Skipped(String),
+}
+
+// The hand-written Polars pipeline of a lesson is the expected output of the transpiler.
+pub async fn check_lesson(db: &DatabaseConnection, lesson: &Lesson) -> AppResult<CorpusOutcome> {
+    let (Some(query), Some(polars_query)) = (lesson.query(), lesson.polars_query) else {
+        return Ok(CorpusOutcome::Skipped(
+            "no query or no Polars pipeline".to_string(),
+        ));
+    };
+
+    let lf = match transpile_sql(db, &query).await {
+        Ok(lf) => lf,
+        Err(AppError::Transpile(message)) => return Ok(CorpusOutcome::Unsupported(message)),
+        // Note: a table the database lacks, e.g. of another dataset, is skipped.
+        Err(AppError::Assertion(message)) => return Ok(CorpusOutcome::Skipped(message)),
+        Err(err) => return Err(err),
+    };
+    let transpiled = match lf.collect() {
+        Ok(df) => df,
+        Err(err) => return Ok(CorpusOutcome::Unsupported(err.to_string())),
+    };
+    let expected = polars_query(db).await?;
+
+    match diff_dfs(&transpiled, &expected)? {
+        None => Ok(CorpusOutcome::Match),
+        Some(diff) => Ok(CorpusOutcome::Mismatch(diff)),
+    }
+}
+
+// Runs the corpus, each lesson on its own dataset, and fails when a transpiled query and
+// its hand-written pipeline disagree.
+pub async fn display_corpus() -> AppResult<()> {
+    let mut databases: HashMap<&str, DatabaseConnection> = HashMap::new();
+    let mut mismatches = Vec::new();
+
+    for lesson in get_lessons() {
+        let db = match databases.entry(lesson.dataset()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(get_database_dataset(lesson.dataset()).await?.0),
+        };
+        let outcome = check_lesson(db, &lesson).await?;
+
+        if let CorpusOutcome::Mismatch(diff) = &outcome {
+            mismatches.push(format!("{} ({diff})", lesson.id));
+        }
+
+        log_debug(lesson.id, &outcome, None);
+    }
+
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::Assertion(format!(
+        "the transpiler and the Polars pipeline disagree on {}",
+        mismatches.join(", ")
+    )))
+}
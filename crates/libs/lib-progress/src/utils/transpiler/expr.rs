@@ -0,0 +1,656 @@
+use polars::prelude::*;
+use sqlparser::ast::{
+    self, BinaryOperator, DataType as SqlType, DuplicateTreatment, FunctionArg, FunctionArgExpr,
+    FunctionArguments, Ident, TimezoneInfo, UnaryOperator, Value, WindowType,
+};
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::dataframe::PgType;
+
+use super::planner::{Planner, Scope};
+use super::unsupported;
+
+const AGGREGATES: [&str; 5] = ["count", "sum", "avg", "min", "max"];
+
+// Unquoted identifiers are case-insensitive in Postgres, quoted ones are kept as written.
+pub fn normalize(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+fn function_name(function: &ast::Function) -> String {
+    function.name.0.last().map(normalize).unwrap_or_default()
+}
+
+fn function_args(function: &ast::Function) -> Vec<&ast::Expr> {
+    match &function.args {
+        FunctionArguments::List(list) => list
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Some(expr),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn contains_aggregate(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Function(function) => {
+            (function.over.is_none() && AGGREGATES.contains(&function_name(function).as_str()))
+                || function_args(function).into_iter().any(contains_aggregate)
+        }
+        ast::Expr::BinaryOp { left, right, .. } => {
+            contains_aggregate(left) || contains_aggregate(right)
+        }
+        ast::Expr::UnaryOp { expr, .. }
+        | ast::Expr::Nested(expr)
+        | ast::Expr::Cast { expr, .. }
+        | ast::Expr::IsNull(expr)
+        | ast::Expr::IsNotNull(expr) => contains_aggregate(expr),
+        ast::Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            operand
+                .iter()
+                .chain(else_result)
+                .any(|e| contains_aggregate(e))
+                || conditions.iter().chain(results).any(contains_aggregate)
+        }
+        _ => false,
+    }
+}
+
+// The column name Postgres gives an unaliased select list expression.
+pub fn default_name(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::Identifier(ident) => normalize(ident),
+        ast::Expr::CompoundIdentifier(idents) => idents.last().map(normalize).unwrap_or_default(),
+        ast::Expr::Function(function) => function_name(function),
+        ast::Expr::Nested(expr) => default_name(expr),
+        ast::Expr::Cast {
+            expr, data_type, ..
+        } => match expr.as_ref() {
+            ast::Expr::Identifier(_) | ast::Expr::CompoundIdentifier(_) => default_name(expr),
+            _ => data_type.to_string().to_lowercase(),
+        },
+        ast::Expr::Case { .. } => "case".to_string(),
+        _ => "?column?".to_string(),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Number {
+    Integer,
+    Fractional,
+}
+
+impl Number {
+    fn of(dtype: &DataType) -> Option<Number> {
+        if dtype.is_integer() {
+            Some(Number::Integer)
+        } else if dtype.is_float() || dtype.is_decimal() {
+            Some(Number::Fractional)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct Translator<'a, 't> {
+    scope: &'a Scope,
+    planner: &'a Planner<'t>,
+}
+
+impl<'a, 't> Translator<'a, 't> {
+    pub fn new(scope: &'a Scope, planner: &'a Planner<'t>) -> Self {
+        Translator { scope, planner }
+    }
+
+    pub fn expr(&self, expr: &ast::Expr) -> AppResult<Expr> {
+        let result = match expr {
+            ast::Expr::Identifier(ident) => col(self.scope.resolve(std::slice::from_ref(ident))?),
+            ast::Expr::CompoundIdentifier(idents) => col(self.scope.resolve(idents)?),
+            ast::Expr::Value(value) => literal(value)?,
+            ast::Expr::Nested(inner) => self.expr(inner)?,
+            ast::Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Divide,
+                right,
+            } => self.divide(left, right)?,
+            ast::Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Modulo,
+                right,
+            } => self.modulo(left, right)?,
+            ast::Expr::BinaryOp { left, op, right } => {
+                binary(self.expr(left)?, op, self.expr(right)?)?
+            }
+            ast::Expr::UnaryOp { op, expr } => match op {
+                UnaryOperator::Not => self.expr(expr)?.not(),
+                UnaryOperator::Minus => lit(0) - self.expr(expr)?,
+                UnaryOperator::Plus => self.expr(expr)?,
+                other => return Err(unsupported(format!("operator `{other}`"))),
+            },
+            ast::Expr::IsNull(inner) => self.expr(inner)?.is_null(),
+            ast::Expr::IsNotNull(inner) => self.expr(inner)?.is_not_null(),
+            ast::Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let value = self.expr(expr)?;
+                let between = value
+                    .clone()
+                    .gt_eq(self.expr(low)?)
+                    .and(value.lt_eq(self.expr(high)?));
+                negate(between, *negated)
+            }
+            ast::Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let value = self.expr(expr)?;
+                let mut any = lit(false);
+                for item in list {
+                    any = any.or(value.clone().eq(self.expr(item)?));
+                }
+                negate(any, *negated)
+            }
+            ast::Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                let plan = self.planner.query(subquery)?;
+                let [name] = plan.names.as_slice() else {
+                    return Err(AppError::Transpile(
+                        "subquery has too many columns".to_string(),
+                    ));
+                };
+                // Note: the subquery is uncorrelated, it runs once and is inlined as a list.
+                let values = plan
+                    .lf
+                    .collect()
+                    .map_err(AppError::Polars)?
+                    .column(name)
+                    .map_err(AppError::Polars)?
+                    .as_materialized_series()
+                    .clone();
+                negate(
+                    self.expr(expr)?.is_in(lit(values).implode(), false),
+                    *negated,
+                )
+            }
+            ast::Expr::Like {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char: None,
+            } => negate(like(self.expr(expr)?, pattern, false)?, *negated),
+            ast::Expr::ILike {
+                negated,
+                any: false,
+                expr,
+                pattern,
+                escape_char: None,
+            } => negate(like(self.expr(expr)?, pattern, true)?, *negated),
+            ast::Expr::Trim {
+                expr,
+                trim_where: None,
+                trim_what: None,
+                trim_characters: None,
+            } => self.expr(expr)?.str().strip_chars(lit(NULL)),
+            ast::Expr::Function(function) => self.function(function)?,
+            ast::Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.case(
+                operand.as_deref(),
+                conditions,
+                results,
+                else_result.as_deref(),
+            )?,
+            ast::Expr::Cast {
+                expr,
+                data_type,
+                format: None,
+                ..
+            } => self.expr(expr)?.cast(dtype(data_type)?),
+            ast::Expr::TypedString { data_type, value } => {
+                lit(value.clone()).cast(dtype(data_type)?)
+            }
+            other => return Err(unsupported(format!("expression `{other}`"))),
+        };
+
+        Ok(result)
+    }
+
+    fn function(&self, function: &ast::Function) -> AppResult<Expr> {
+        let name = function_name(function);
+
+        if function.filter.is_some()
+            || function.null_treatment.is_some()
+            || !function.within_group.is_empty()
+            || !matches!(function.parameters, FunctionArguments::None)
+        {
+            return Err(unsupported(format!("function call `{function}`")));
+        }
+
+        let (args, distinct) = match &function.args {
+            FunctionArguments::None => (Vec::new(), false),
+            FunctionArguments::List(list) if list.clauses.is_empty() => (
+                list.args.iter().collect::<Vec<_>>(),
+                matches!(list.duplicate_treatment, Some(DuplicateTreatment::Distinct)),
+            ),
+            _ => return Err(unsupported(format!("function call `{function}`"))),
+        };
+
+        if name == "count" && matches!(args[..], [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)])
+        {
+            return self.over(function, len().cast(DataType::Int64));
+        }
+
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => self.expr(expr),
+                other => Err(unsupported(format!("function argument `{other}`"))),
+            })
+            .collect::<AppResult<Vec<Expr>>>()?;
+
+        // Note: aggregates follow the Postgres result types, COUNT is BIGINT and AVG is not an integer.
+        let result = match (name.as_str(), args.as_slice(), distinct) {
+            ("count", [arg], false) => arg.clone().count().cast(DataType::Int64),
+            ("count", [arg], true) => arg.clone().drop_nulls().n_unique().cast(DataType::Int64),
+            ("sum", [arg], false) => arg.clone().sum(),
+            ("avg", [arg], false) => arg.clone().cast(DataType::Float64).mean(),
+            ("min", [arg], false) => arg.clone().min(),
+            ("max", [arg], false) => arg.clone().max(),
+            (_, _, true) => return Err(unsupported(format!("DISTINCT in `{function}`"))),
+            ("upper", [arg], _) => arg.clone().str().to_uppercase(),
+            ("lower", [arg], _) => arg.clone().str().to_lowercase(),
+            ("length" | "char_length", [arg], _) => {
+                arg.clone().str().len_chars().cast(DataType::Int32)
+            }
+            // Note: CONCAT skips NULL arguments, unlike the `||` operator.
+            ("concat", [first, rest @ ..], _) => rest
+                .iter()
+                .fold(concat_arg(first), |acc, arg| acc + concat_arg(arg)),
+            ("coalesce", [_, ..], _) => coalesce(&args),
+            ("nullif", [a, b], _) => when(a.clone().eq(b.clone()))
+                .then(lit(NULL))
+                .otherwise(a.clone()),
+            _ => return Err(unsupported(format!("function `{function}`"))),
+        };
+
+        self.over(function, result)
+    }
+
+    // Only partitioned windows map to `over`, ordered windows need a running frame.
+    fn over(&self, function: &ast::Function, expr: Expr) -> AppResult<Expr> {
+        match &function.over {
+            None => Ok(expr),
+            Some(WindowType::WindowSpec(spec))
+                if spec.order_by.is_empty()
+                    && spec.window_frame.is_none()
+                    && spec.window_name.is_none() =>
+            {
+                if spec.partition_by.is_empty() {
+                    return Ok(expr);
+                }
+                let partition = spec
+                    .partition_by
+                    .iter()
+                    .map(|e| self.expr(e))
+                    .collect::<AppResult<Vec<_>>>()?;
+                Ok(expr.over(partition))
+            }
+            Some(window) => Err(unsupported(format!("window `{window}`"))),
+        }
+    }
+
+    // Postgres truncates a division between integers toward zero, Polars `floor_div` rounds
+    // down, so a negative inexact quotient is moved up by one.
+    fn divide(&self, left: &ast::Expr, right: &ast::Expr) -> AppResult<Expr> {
+        let (l, r) = (self.expr(left)?, self.expr(right)?);
+
+        match (self.number(left), self.number(right)) {
+            (Some(Number::Integer), Some(Number::Integer)) => {
+                let quotient = l.clone().floor_div(r.clone());
+                let inexact = (l.clone() % r.clone()).neq(lit(0));
+                let negative = l.lt(lit(0)).xor(r.lt(lit(0)));
+                Ok(when(inexact.and(negative))
+                    .then(quotient.clone() + lit(1))
+                    .otherwise(quotient))
+            }
+            (Some(_), Some(_)) => Ok(l / r),
+            _ => Err(unsupported(format!(
+                "division `{left} / {right}` of operands of unknown type"
+            ))),
+        }
+    }
+
+    // Postgres `%` takes the sign of the dividend, Polars `%` the sign of the divisor.
+    fn modulo(&self, left: &ast::Expr, right: &ast::Expr) -> AppResult<Expr> {
+        let (l, r) = (self.expr(left)?, self.expr(right)?);
+
+        match (self.number(left), self.number(right)) {
+            (Some(Number::Integer), Some(Number::Integer)) => {
+                let remainder = l.clone() % r.clone();
+                let negative = remainder
+                    .clone()
+                    .neq(lit(0))
+                    .and(l.lt(lit(0)).xor(r.clone().lt(lit(0))));
+                Ok(when(negative)
+                    .then(remainder.clone() - r)
+                    .otherwise(remainder))
+            }
+            _ => Err(unsupported(format!(
+                "modulo `{left} % {right}` of operands that are not integers"
+            ))),
+        }
+    }
+
+    // The kind of number an expression evaluates to, `None` when it is not known.
+    fn number(&self, expr: &ast::Expr) -> Option<Number> {
+        match expr {
+            ast::Expr::Value(Value::Number(number, _)) => match number.parse::<i64>() {
+                Ok(_) => Some(Number::Integer),
+                Err(_) => Some(Number::Fractional),
+            },
+            ast::Expr::Identifier(ident) => self
+                .scope
+                .lookup(std::slice::from_ref(ident))
+                .ok()
+                .and_then(|c| Number::of(&c.dtype)),
+            ast::Expr::CompoundIdentifier(idents) => self
+                .scope
+                .lookup(idents)
+                .ok()
+                .and_then(|c| Number::of(&c.dtype)),
+            ast::Expr::Nested(inner)
+            | ast::Expr::UnaryOp {
+                op: UnaryOperator::Minus | UnaryOperator::Plus,
+                expr: inner,
+            } => self.number(inner),
+            ast::Expr::BinaryOp {
+                left,
+                op:
+                    BinaryOperator::Plus
+                    | BinaryOperator::Minus
+                    | BinaryOperator::Multiply
+                    | BinaryOperator::Divide
+                    | BinaryOperator::Modulo,
+                right,
+            } => match (self.number(left)?, self.number(right)?) {
+                (Number::Integer, Number::Integer) => Some(Number::Integer),
+                _ => Some(Number::Fractional),
+            },
+            ast::Expr::Cast { data_type, .. } => {
+                dtype(data_type).ok().and_then(|dtype| Number::of(&dtype))
+            }
+            ast::Expr::Function(function) if function.over.is_none() => {
+                let args = function_args(function);
+                match (function_name(function).as_str(), args.as_slice()) {
+                    ("count", _) => Some(Number::Integer),
+                    ("avg", _) => Some(Number::Fractional),
+                    ("min" | "max", [arg]) => self.number(arg),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn case(
+        &self,
+        operand: Option<&ast::Expr>,
+        conditions: &[ast::Expr],
+        results: &[ast::Expr],
+        else_result: Option<&ast::Expr>,
+    ) -> AppResult<Expr> {
+        let operand = operand.map(|o| self.expr(o)).transpose()?;
+        let mut result = match else_result {
+            Some(e) => self.expr(e)?,
+            None => lit(NULL),
+        };
+
+        // Note: the branches are folded from the last one, so the first true condition wins.
+        for (condition, value) in conditions.iter().zip(results).rev() {
+            let mut condition = self.expr(condition)?;
+            if let Some(operand) = &operand {
+                condition = operand.clone().eq(condition);
+            }
+            result = when(condition).then(self.expr(value)?).otherwise(result);
+        }
+
+        Ok(result)
+    }
+}
+
+fn negate(expr: Expr, negated: bool) -> Expr {
+    if negated { expr.not() } else { expr }
+}
+
+fn concat_arg(expr: &Expr) -> Expr {
+    expr.clone().cast(DataType::String).fill_null(lit(""))
+}
+
+fn literal(value: &Value) -> AppResult<Expr> {
+    match value {
+        Value::Number(number, _) => number
+            .parse::<i32>()
+            .map(lit)
+            .or_else(|_| number.parse::<i64>().map(lit))
+            .or_else(|_| number.parse::<f64>().map(lit))
+            .map_err(|_| AppError::Transpile(format!("invalid number `{number}`"))),
+        Value::SingleQuotedString(text) => Ok(lit(text.clone())),
+        Value::Boolean(value) => Ok(lit(*value)),
+        Value::Null => Ok(lit(NULL)),
+        other => Err(unsupported(format!("literal `{other}`"))),
+    }
+}
+
+fn binary(left: Expr, op: &BinaryOperator, right: Expr) -> AppResult<Expr> {
+    let result = match op {
+        BinaryOperator::Eq => left.eq(right),
+        BinaryOperator::NotEq => left.neq(right),
+        BinaryOperator::Lt => left.lt(right),
+        BinaryOperator::LtEq => left.lt_eq(right),
+        BinaryOperator::Gt => left.gt(right),
+        BinaryOperator::GtEq => left.gt_eq(right),
+        BinaryOperator::And => left.and(right),
+        BinaryOperator::Or => left.or(right),
+        BinaryOperator::Plus => left + right,
+        BinaryOperator::Minus => left - right,
+        BinaryOperator::Multiply => left * right,
+        BinaryOperator::StringConcat => left.cast(DataType::String) + right.cast(DataType::String),
+        other => return Err(unsupported(format!("operator `{other}`"))),
+    };
+
+    Ok(result)
+}
+
+// LIKE is anchored at both ends, `%` and `_` become `.*` and `.` in a regex.
+fn like(expr: Expr, pattern: &ast::Expr, case_insensitive: bool) -> AppResult<Expr> {
+    let ast::Expr::Value(Value::SingleQuotedString(pattern)) = pattern else {
+        return Err(unsupported(format!("LIKE pattern `{pattern}`")));
+    };
+
+    let mut regex = String::from(if case_insensitive { "(?is)^" } else { "(?s)^" });
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    push_escaped(&mut regex, next);
+                }
+            }
+            c => push_escaped(&mut regex, c),
+        }
+    }
+    regex.push('$');
+
+    Ok(expr.str().contains(lit(regex), true))
+}
+
+fn push_escaped(regex: &mut String, c: char) {
+    if "\\.+*?()|[]{}^$#&-~".contains(c) {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
+fn dtype(data_type: &SqlType) -> AppResult<DataType> {
+    let pg_type = match data_type {
+        SqlType::SmallInt(_) | SqlType::Int2(_) => PgType::Int2,
+        SqlType::Int(_) | SqlType::Integer(_) | SqlType::Int4(_) => PgType::Int4,
+        SqlType::BigInt(_) | SqlType::Int8(_) => PgType::Int8,
+        SqlType::Real | SqlType::Float4 => PgType::Float4,
+        SqlType::Double | SqlType::DoublePrecision | SqlType::Float8 | SqlType::Float(_) => {
+            PgType::Float8
+        }
+        SqlType::Numeric(_) | SqlType::Decimal(_) => PgType::Numeric,
+        SqlType::Text
+        | SqlType::Varchar(_)
+        | SqlType::CharacterVarying(_)
+        | SqlType::Char(_)
+        | SqlType::Character(_) => PgType::Text,
+        SqlType::Bool | SqlType::Boolean => PgType::Bool,
+        SqlType::Date => PgType::Date,
+        SqlType::Timestamp(_, TimezoneInfo::None | TimezoneInfo::WithoutTimeZone) => {
+            PgType::Timestamp
+        }
+        other => return Err(unsupported(format!("type `{other}`"))),
+    };
+
+    Ok(pg_type.dtype())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::utils::transpiler::{parse_query, transpile};
+
+    fn run(sql: &str) -> AppResult<DataFrame> {
+        let numbers = df!(
+            "a" => [7, -7, 7, -7, 6],
+            "b" => [2, 2, -2, -2, 3],
+            "x" => [7.0, -7.0, 7.0, -7.0, 6.0],
+            "t" => ["7", "-7", "7", "-7", "6"],
+        )
+        .map_err(AppError::Polars)?;
+        let tables = HashMap::from([("numbers".to_string(), numbers.lazy())]);
+
+        transpile(&*parse_query(sql)?, &tables)?
+            .collect()
+            .map_err(AppError::Polars)
+    }
+
+    fn column(df: &DataFrame, name: &str) -> AppResult<Series> {
+        Ok(df
+            .column(name)
+            .map_err(AppError::Polars)?
+            .as_materialized_series()
+            .clone())
+    }
+
+    #[test]
+    fn integer_division_truncates_toward_zero() -> AppResult<()> {
+        let df = run("SELECT a / b AS q, a % b AS r, a / 2 AS half, 7 / 2 AS lit FROM numbers")?;
+
+        assert_eq!(
+            column(&df, "q")?,
+            Series::new("q".into(), [3, -3, -3, 3, 2])
+        );
+        assert_eq!(
+            column(&df, "r")?,
+            Series::new("r".into(), [1, -1, 1, -1, 0])
+        );
+        assert_eq!(
+            column(&df, "half")?,
+            Series::new("half".into(), [3, -3, 3, -3, 3])
+        );
+        assert_eq!(column(&df, "lit")?, Series::new("lit".into(), [3; 5]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fractional_division_is_exact() -> AppResult<()> {
+        let df =
+            run("SELECT x / b AS q, a / 2.0 AS half, CAST(a AS FLOAT) / 2 AS cast FROM numbers")?;
+
+        assert_eq!(
+            column(&df, "q")?,
+            Series::new("q".into(), [3.5, -3.5, -3.5, 3.5, 2.0])
+        );
+        assert_eq!(
+            column(&df, "half")?,
+            Series::new("half".into(), [3.5, -3.5, 3.5, -3.5, 3.0])
+        );
+        assert_eq!(
+            column(&df, "cast")?,
+            Series::new("cast".into(), [3.5, -3.5, 3.5, -3.5, 3.0])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn operators_of_unknown_types_are_unsupported() {
+        for sql in [
+            "SELECT t / 2 FROM numbers",
+            "SELECT x % 2 FROM numbers",
+            "SELECT a / (SELECT 1) FROM numbers",
+        ] {
+            assert!(
+                matches!(run(sql), Err(AppError::Transpile(m)) if m.starts_with("unsupported")),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn comparisons_and_concat_follow_postgres() -> AppResult<()> {
+        let df = run("SELECT a + b * 2 AS sum, a BETWEEN -7 AND 6 AS between, \
+             t || '!' AS bang, t LIKE '-%' AS negative FROM numbers WHERE a <> 6")?;
+
+        assert_eq!(
+            column(&df, "sum")?,
+            Series::new("sum".into(), [11, -3, 3, -11])
+        );
+        assert_eq!(
+            column(&df, "between")?,
+            Series::new("between".into(), [false, true, false, true])
+        );
+        assert_eq!(
+            column(&df, "bang")?,
+            Series::new("bang".into(), ["7!", "-7!", "7!", "-7!"])
+        );
+        assert_eq!(
+            column(&df, "negative")?,
+            Series::new("negative".into(), [false, true, false, true])
+        );
+
+        Ok(())
+    }
+}
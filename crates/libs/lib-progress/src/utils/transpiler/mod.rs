@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use polars::prelude::*;
+use sea_orm::DatabaseConnection;
+use sqlparser::ast::{Query, Statement, TableFactor, Visit, Visitor};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+
+use lib_core::error::{AppError, AppResult};
+
+use crate::utils::catalog::get_catalog;
+use crate::utils::dataframe::scan_table;
+
+mod corpus;
+mod expr;
+mod planner;
+
+pub use corpus::{CorpusOutcome, check_lesson, display_corpus};
+
+pub type Tables = HashMap<String, LazyFrame>;
+
+fn unsupported(what: impl std::fmt::Display) -> AppError {
+    AppError::Transpile(format!("unsupported {what}"))
+}
+
+pub fn parse_query(sql: &str) -> AppResult<Box<Query>> {
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|err| AppError::Transpile(err.to_string()))?;
+
+    if statements.len() != 1 {
+        return Err(AppError::Transpile(
+            "expected exactly one statement".to_string(),
+        ));
+    }
+
+    match statements.remove(0) {
+        Statement::Query(query) => Ok(query),
+        statement => Err(unsupported(format!("statement `{statement}`"))),
+    }
+}
+
+// The tables a query reads and the names of its CTEs, subqueries included.
+#[derive(Default)]
+struct Relations {
+    tables: Vec<String>,
+    ctes: Vec<String>,
+}
+
+impl Visitor for Relations {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.ctes.push(cte.alias.name.value.to_lowercase());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // Note: a table function, e.g. `crosstab(...)`, has arguments and is not a table.
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+        if let TableFactor::Table {
+            name, args: None, ..
+        } = table_factor
+        {
+            self.tables.push(name.to_string().to_lowercase());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+// The tables a query reads, in the order they first appear.
+pub fn get_table_names(query: &Query) -> Vec<String> {
+    let mut relations = Relations::default();
+    let _ = query.visit(&mut relations);
+    let mut names: Vec<String> = Vec::new();
+
+    for name in relations.tables {
+        if !names.contains(&name) && !relations.ctes.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+// Note: only the tables the query reads are loaded, a table that is not in the catalog is
+// a table of another dataset.
+pub async fn get_tables(db: &DatabaseConnection, query: &Query) -> AppResult<Tables> {
+    let catalog = get_catalog(db.get_postgres_connection_pool()).await?;
+    let mut tables = Tables::new();
+    for name in get_table_names(query) {
+        let table = catalog
+            .table(&name)
+            .ok_or_else(|| AppError::Assertion(format!("no table {name} in the database")))?;
+        tables.insert(name, scan_table(db, &table.name).await?);
+    }
+
+    Ok(tables)
+}
+
+pub fn transpile(query: &Query, tables: &Tables) -> AppResult<LazyFrame> {
+    let plan = planner::Planner::new(tables).query(query)?;
+
+    Ok(plan.lf)
+}
+
+pub async fn transpile_sql(db: &DatabaseConnection, sql: &str) -> AppResult<LazyFrame> {
+    let query = parse_query(sql)?;
+    let tables = get_tables(db, &query).await?;
+
+    transpile(&query, &tables)
+}
@@ -0,0 +1,676 @@
+use std::collections::HashMap;
+
+use polars::prelude::*;
+use sqlparser::ast::{
+    self, BinaryOperator, Distinct, GroupByExpr, Ident, JoinConstraint, JoinOperator, SelectItem,
+    SetExpr, SetOperator, SetQuantifier, TableFactor, TableWithJoins, Value,
+};
+
+use lib_core::error::{AppError, AppResult};
+
+use super::expr::{Translator, contains_aggregate, default_name, normalize};
+use super::{Tables, unsupported};
+
+// Every column is renamed to `qualifier.name` while planning, so joined tables never collide.
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub qualifier: String,
+    pub name: String,
+    pub dtype: DataType,
+}
+
+impl Column {
+    pub fn key(&self) -> String {
+        format!("{}.{}", self.qualifier, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Scope {
+    pub columns: Vec<Column>,
+}
+
+impl Scope {
+    pub fn resolve(&self, idents: &[Ident]) -> AppResult<String> {
+        Ok(self.lookup(idents)?.key())
+    }
+
+    pub fn lookup(&self, idents: &[Ident]) -> AppResult<&Column> {
+        let (qualifier, name) = match idents {
+            [name] => (None, normalize(name)),
+            [.., qualifier, name] => (Some(normalize(qualifier)), normalize(name)),
+            [] => return Err(AppError::Transpile("empty identifier".to_string())),
+        };
+        let reference = idents.iter().map(normalize).collect::<Vec<_>>().join(".");
+        let matches: Vec<&Column> = self
+            .columns
+            .iter()
+            .filter(|c| c.name == name && qualifier.as_ref().is_none_or(|q| &c.qualifier == q))
+            .collect();
+
+        match matches.as_slice() {
+            [column] => Ok(*column),
+            [] => Err(AppError::Transpile(format!(
+                "column `{reference}` does not exist"
+            ))),
+            _ => Err(AppError::Transpile(format!(
+                "column reference `{reference}` is ambiguous"
+            ))),
+        }
+    }
+
+    fn extend(&mut self, other: Scope) -> AppResult<()> {
+        for column in &other.columns {
+            if self.columns.iter().any(|c| c.qualifier == column.qualifier) {
+                return Err(AppError::Transpile(format!(
+                    "table name `{}` specified more than once",
+                    column.qualifier
+                )));
+            }
+        }
+        self.columns.extend(other.columns);
+
+        Ok(())
+    }
+}
+
+pub struct Plan {
+    pub lf: LazyFrame,
+    pub names: Vec<String>,
+    pub exprs: Vec<ast::Expr>,
+}
+
+impl Plan {
+    // ORDER BY may use an output name, a position or repeat a select list expression.
+    fn output_name(&self, expr: &ast::Expr) -> AppResult<String> {
+        if let ast::Expr::Value(Value::Number(number, _)) = expr {
+            return number
+                .parse::<usize>()
+                .ok()
+                .and_then(|position| self.names.get(position.wrapping_sub(1)))
+                .cloned()
+                .ok_or_else(|| {
+                    AppError::Transpile(format!("ORDER BY position {number} is not in select list"))
+                });
+        }
+
+        if let ast::Expr::Identifier(ident) = expr {
+            let name = normalize(ident);
+            if self.names.contains(&name) {
+                return Ok(name);
+            }
+        }
+
+        match self.exprs.iter().position(|e| e == expr) {
+            Some(position) => Ok(self.names[position].clone()),
+            None => Err(unsupported(format!(
+                "ORDER BY `{expr}` outside the select list"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Planner<'t> {
+    tables: &'t Tables,
+    ctes: HashMap<String, LazyFrame>,
+}
+
+impl<'t> Planner<'t> {
+    pub fn new(tables: &'t Tables) -> Self {
+        Planner {
+            tables,
+            ctes: HashMap::new(),
+        }
+    }
+
+    pub fn query(&self, query: &ast::Query) -> AppResult<Plan> {
+        if query.fetch.is_some() || !query.limit_by.is_empty() || !query.locks.is_empty() {
+            return Err(unsupported(format!("query clause in `{query}`")));
+        }
+
+        let mut planner = self.clone();
+        if let Some(with) = &query.with {
+            if with.recursive {
+                return Err(unsupported("WITH RECURSIVE"));
+            }
+            for cte in &with.cte_tables {
+                if !cte.alias.columns.is_empty() {
+                    return Err(unsupported(format!("CTE column list in `{cte}`")));
+                }
+                let plan = planner.query(&cte.query)?;
+                planner.ctes.insert(normalize(&cte.alias.name), plan.lf);
+            }
+        }
+
+        let mut plan = planner.set_expr(&query.body)?;
+
+        if let Some(order_by) = &query.order_by {
+            plan = order(plan, &order_by.exprs)?;
+        }
+
+        let offset = match &query.offset {
+            Some(offset) => literal_count(&offset.value)?,
+            None => 0,
+        };
+        let limit = query.limit.as_ref().map(literal_count).transpose()?;
+        if offset > 0 || limit.is_some() {
+            let len = limit.map_or(IdxSize::MAX, |limit| limit as IdxSize);
+            plan.lf = plan.lf.slice(offset as i64, len);
+        }
+
+        Ok(plan)
+    }
+
+    fn set_expr(&self, body: &SetExpr) -> AppResult<Plan> {
+        match body {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation {
+                op,
+                set_quantifier,
+                left,
+                right,
+            } => self.set_operation(op, set_quantifier, left, right),
+            other => Err(unsupported(format!("query body `{other}`"))),
+        }
+    }
+
+    fn set_operation(
+        &self,
+        op: &SetOperator,
+        set_quantifier: &SetQuantifier,
+        left: &SetExpr,
+        right: &SetExpr,
+    ) -> AppResult<Plan> {
+        let left = self.set_expr(left)?;
+        let right = self.set_expr(right)?;
+
+        if left.names.len() != right.names.len() {
+            return Err(AppError::Transpile(format!(
+                "each {op} query must have the same number of columns"
+            )));
+        }
+
+        let all = match set_quantifier {
+            SetQuantifier::All => true,
+            SetQuantifier::None | SetQuantifier::Distinct => false,
+            other => return Err(unsupported(format!("{op} {other}"))),
+        };
+
+        // Note: set operations match columns by position, the right side takes the left names.
+        let right_lf = right.lf.select(
+            right
+                .names
+                .iter()
+                .zip(&left.names)
+                .map(|(r, l)| col(r.as_str()).alias(l.as_str()))
+                .collect::<Vec<_>>(),
+        );
+        let on: Vec<Expr> = left.names.iter().map(|n| col(n.as_str())).collect();
+        let lf = match op {
+            SetOperator::Union => concat(
+                [left.lf, right_lf],
+                UnionArgs {
+                    to_supertypes: true,
+                    ..Default::default()
+                },
+            )
+            .map_err(AppError::Polars)?,
+            SetOperator::Intersect | SetOperator::Except if all => {
+                return Err(unsupported(format!("{op} ALL")));
+            }
+            SetOperator::Intersect => {
+                left.lf
+                    .join(right_lf, on.clone(), on, set_join_args(JoinType::Semi))
+            }
+            SetOperator::Except => {
+                left.lf
+                    .join(right_lf, on.clone(), on, set_join_args(JoinType::Anti))
+            }
+        };
+        let lf = if all {
+            lf
+        } else {
+            lf.unique_stable(None, UniqueKeepStrategy::First)
+        };
+
+        Ok(Plan {
+            lf,
+            names: left.names,
+            exprs: left.exprs,
+        })
+    }
+
+    fn select(&self, select: &ast::Select) -> AppResult<Plan> {
+        if select.top.is_some()
+            || select.into.is_some()
+            || !select.lateral_views.is_empty()
+            || select.prewhere.is_some()
+            || !select.cluster_by.is_empty()
+            || !select.distribute_by.is_empty()
+            || !select.sort_by.is_empty()
+            || !select.named_window.is_empty()
+            || select.qualify.is_some()
+            || select.connect_by.is_some()
+            || select.value_table_mode.is_some()
+        {
+            return Err(unsupported(format!("SELECT clause in `{select}`")));
+        }
+
+        let (mut lf, scope) = self.from(&select.from)?;
+
+        if let Some(selection) = &select.selection {
+            lf = lf.filter(Translator::new(&scope, self).expr(selection)?);
+        }
+
+        let items = items(&select.projection, &scope)?;
+        let names: Vec<String> = items.iter().map(|(_, name)| name.clone()).collect();
+
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                return Err(unsupported(format!("duplicate output column `{name}`")));
+            }
+        }
+
+        let group_by = match &select.group_by {
+            GroupByExpr::Expressions(exprs, modifiers) if modifiers.is_empty() => exprs
+                .iter()
+                .map(|expr| group_key(expr, &items, &scope))
+                .collect::<AppResult<Vec<_>>>()?,
+            other => return Err(unsupported(format!("`{other}`"))),
+        };
+        let aggregated = !group_by.is_empty()
+            || select.having.is_some()
+            || items.iter().any(|(expr, _)| contains_aggregate(expr));
+        let translator = Translator::new(&scope, self);
+        let mut outputs = Vec::new();
+
+        if aggregated {
+            let keys = group_by
+                .iter()
+                .enumerate()
+                .map(|(i, key)| Ok(translator.expr(key)?.alias(format!("__group_{i}"))))
+                .collect::<AppResult<Vec<_>>>()?;
+            let mut aggs = Vec::new();
+
+            for (expr, name) in &items {
+                if let Some(i) = group_by.iter().position(|key| key == expr) {
+                    outputs.push(col(format!("__group_{i}")).alias(name.as_str()));
+                } else if contains_aggregate(expr) {
+                    let agg = format!("__agg_{}", aggs.len());
+                    aggs.push(translator.expr(expr)?.alias(agg.as_str()));
+                    outputs.push(col(agg).alias(name.as_str()));
+                } else if matches!(expr, ast::Expr::Value(_)) {
+                    outputs.push(translator.expr(expr)?.alias(name.as_str()));
+                } else {
+                    return Err(AppError::Transpile(format!(
+                        "`{expr}` must appear in the GROUP BY clause or be used in an aggregate function"
+                    )));
+                }
+            }
+
+            if let Some(having) = &select.having {
+                aggs.push(translator.expr(having)?.alias("__having"));
+            }
+
+            lf = if keys.is_empty() {
+                lf.select(aggs)
+            } else {
+                lf.group_by_stable(keys).agg(aggs)
+            };
+
+            if select.having.is_some() {
+                lf = lf.filter(col("__having"));
+            }
+        } else {
+            for (expr, name) in &items {
+                outputs.push(translator.expr(expr)?.alias(name.as_str()));
+            }
+        }
+
+        lf = lf.select(outputs);
+
+        match &select.distinct {
+            None => {}
+            Some(Distinct::Distinct) => lf = lf.unique_stable(None, UniqueKeepStrategy::First),
+            Some(Distinct::On(_)) => return Err(unsupported("DISTINCT ON")),
+        }
+
+        Ok(Plan {
+            lf,
+            names,
+            exprs: items.into_iter().map(|(expr, _)| expr).collect(),
+        })
+    }
+
+    fn from(&self, from: &[TableWithJoins]) -> AppResult<(LazyFrame, Scope)> {
+        let Some((first, rest)) = from.split_first() else {
+            return Err(unsupported("SELECT without FROM"));
+        };
+        let (mut lf, mut scope) = self.table_with_joins(first)?;
+
+        // Note: `FROM a, b` is a cross join.
+        for table in rest {
+            let (right, right_scope) = self.table_with_joins(table)?;
+            lf = lf.cross_join(right, None);
+            scope.extend(right_scope)?;
+        }
+
+        Ok((lf, scope))
+    }
+
+    fn table_with_joins(&self, table: &TableWithJoins) -> AppResult<(LazyFrame, Scope)> {
+        let (mut lf, mut scope) = self.relation(&table.relation)?;
+
+        for join in &table.joins {
+            let (right, right_scope) = self.relation(&join.relation)?;
+            let (join_type, constraint) = match &join.join_operator {
+                JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+                JoinOperator::LeftOuter(constraint) => (JoinType::Left, constraint),
+                JoinOperator::RightOuter(constraint) => (JoinType::Right, constraint),
+                JoinOperator::FullOuter(constraint) => (JoinType::Full, constraint),
+                JoinOperator::CrossJoin => (JoinType::Cross, &JoinConstraint::None),
+                _ => return Err(unsupported(format!("join `{join}`"))),
+            };
+
+            lf = match constraint {
+                JoinConstraint::None => lf.cross_join(right, None),
+                JoinConstraint::On(on) => {
+                    let (left_on, right_on) = equi_keys(on, &scope, &right_scope)?;
+                    lf.join(right, left_on, right_on, join_args(join_type))
+                }
+                JoinConstraint::Using(names) => {
+                    let mut left_on = Vec::new();
+                    let mut right_on = Vec::new();
+                    for name in names {
+                        left_on.push(col(scope.resolve(std::slice::from_ref(name))?));
+                        right_on.push(col(right_scope.resolve(std::slice::from_ref(name))?));
+                    }
+                    lf.join(right, left_on, right_on, join_args(join_type))
+                }
+                JoinConstraint::Natural => return Err(unsupported("NATURAL JOIN")),
+            };
+            scope.extend(right_scope)?;
+        }
+
+        Ok((lf, scope))
+    }
+
+    fn relation(&self, factor: &TableFactor) -> AppResult<(LazyFrame, Scope)> {
+        let (mut lf, qualifier) = match factor {
+            TableFactor::Table {
+                name,
+                alias,
+                args: None,
+                ..
+            } => {
+                let key = name.0.iter().map(normalize).collect::<Vec<_>>().join(".");
+                let lf = self
+                    .ctes
+                    .get(&key)
+                    .or_else(|| self.tables.get(&key))
+                    .cloned()
+                    .ok_or_else(|| {
+                        AppError::Transpile(format!("relation `{name}` does not exist"))
+                    })?;
+                let qualifier = match alias {
+                    Some(alias) if alias.columns.is_empty() => normalize(&alias.name),
+                    Some(alias) => return Err(unsupported(format!("column aliases in `{alias}`"))),
+                    None => name.0.last().map(normalize).unwrap_or(key),
+                };
+                (lf, qualifier)
+            }
+            TableFactor::Derived {
+                lateral: false,
+                subquery,
+                alias: Some(alias),
+            } if alias.columns.is_empty() => (self.query(subquery)?.lf, normalize(&alias.name)),
+            other => return Err(unsupported(format!("FROM item `{other}`"))),
+        };
+
+        let schema = lf.collect_schema().map_err(AppError::Polars)?;
+        let columns: Vec<Column> = schema
+            .iter()
+            .map(|(name, dtype)| Column {
+                qualifier: qualifier.clone(),
+                name: name.to_string(),
+                dtype: dtype.clone(),
+            })
+            .collect();
+        let lf = lf.select(
+            columns
+                .iter()
+                .map(|c| col(c.name.as_str()).alias(c.key()))
+                .collect::<Vec<_>>(),
+        );
+
+        Ok((lf, Scope { columns }))
+    }
+}
+
+type Items = Vec<(ast::Expr, String)>;
+
+fn items(projection: &[SelectItem], scope: &Scope) -> AppResult<Items> {
+    let mut items = Vec::new();
+
+    for item in projection {
+        match item {
+            SelectItem::UnnamedExpr(expr) => items.push((expr.clone(), default_name(expr))),
+            SelectItem::ExprWithAlias { expr, alias } => {
+                items.push((expr.clone(), normalize(alias)))
+            }
+            SelectItem::Wildcard(_) => items.extend(wildcard(scope, None)),
+            SelectItem::QualifiedWildcard(name, _) => {
+                let qualifier = name.0.last().map(normalize);
+                let columns = wildcard(scope, qualifier.as_deref());
+                if columns.is_empty() {
+                    return Err(AppError::Transpile(format!(
+                        "missing FROM-clause entry for table `{name}`"
+                    )));
+                }
+                items.extend(columns);
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+fn wildcard(scope: &Scope, qualifier: Option<&str>) -> Items {
+    scope
+        .columns
+        .iter()
+        .filter(|c| qualifier.is_none_or(|q| c.qualifier == q))
+        .map(|c| {
+            let expr = ast::Expr::CompoundIdentifier(vec![
+                Ident::with_quote('"', c.qualifier.as_str()),
+                Ident::with_quote('"', c.name.as_str()),
+            ]);
+            (expr, c.name.clone())
+        })
+        .collect()
+}
+
+// Postgres lets GROUP BY name an output column by position or alias.
+fn group_key(expr: &ast::Expr, items: &Items, scope: &Scope) -> AppResult<ast::Expr> {
+    match expr {
+        ast::Expr::Value(Value::Number(number, _)) => number
+            .parse::<usize>()
+            .ok()
+            .and_then(|position| items.get(position.wrapping_sub(1)))
+            .map(|(expr, _)| expr.clone())
+            .ok_or_else(|| {
+                AppError::Transpile(format!("GROUP BY position {number} is not in select list"))
+            }),
+        ast::Expr::Identifier(ident) if scope.resolve(std::slice::from_ref(ident)).is_err() => {
+            let name = normalize(ident);
+            items
+                .iter()
+                .find(|(_, output)| *output == name)
+                .map(|(expr, _)| expr.clone())
+                .ok_or_else(|| AppError::Transpile(format!("column `{name}` does not exist")))
+        }
+        ast::Expr::Rollup(_) | ast::Expr::Cube(_) | ast::Expr::GroupingSets(_) => {
+            Err(unsupported(format!("`{expr}`")))
+        }
+        _ => Ok(expr.clone()),
+    }
+}
+
+// Note: Polars joins on key lists, so ON must be a conjunction of column equalities.
+fn equi_keys(on: &ast::Expr, left: &Scope, right: &Scope) -> AppResult<(Vec<Expr>, Vec<Expr>)> {
+    match on {
+        ast::Expr::Nested(inner) => equi_keys(inner, left, right),
+        ast::Expr::BinaryOp {
+            left: a,
+            op: BinaryOperator::And,
+            right: b,
+        } => {
+            let (mut left_on, mut right_on) = equi_keys(a, left, right)?;
+            let (more_left, more_right) = equi_keys(b, left, right)?;
+            left_on.extend(more_left);
+            right_on.extend(more_right);
+            Ok((left_on, right_on))
+        }
+        ast::Expr::BinaryOp {
+            left: a,
+            op: BinaryOperator::Eq,
+            right: b,
+        } => match (
+            column(a, left),
+            column(b, right),
+            column(a, right),
+            column(b, left),
+        ) {
+            (Some(l), Some(r), _, _) | (_, _, Some(r), Some(l)) => Ok((vec![col(l)], vec![col(r)])),
+            _ => Err(unsupported(format!("join condition `{on}`"))),
+        },
+        _ => Err(unsupported(format!("join condition `{on}`"))),
+    }
+}
+
+fn column(expr: &ast::Expr, scope: &Scope) -> Option<String> {
+    match expr {
+        ast::Expr::Identifier(ident) => scope.resolve(std::slice::from_ref(ident)).ok(),
+        ast::Expr::CompoundIdentifier(idents) => scope.resolve(idents).ok(),
+        _ => None,
+    }
+}
+
+fn join_args(join_type: JoinType) -> JoinArgs {
+    // Note: SQL keeps both key columns of an outer join, Polars would merge them by default.
+    JoinArgs::new(join_type).with_coalesce(JoinCoalesce::KeepColumns)
+}
+
+fn set_join_args(join_type: JoinType) -> JoinArgs {
+    // Note: unlike joins, set operations treat two NULLs as equal.
+    let mut args = JoinArgs::new(join_type);
+    args.nulls_equal = true;
+    args
+}
+
+fn order(plan: Plan, exprs: &[ast::OrderByExpr]) -> AppResult<Plan> {
+    let mut by = Vec::new();
+    let mut descending = Vec::new();
+    let mut nulls_last = Vec::new();
+
+    for order in exprs {
+        let desc = order.asc == Some(false);
+        by.push(col(plan.output_name(&order.expr)?));
+        descending.push(desc);
+        // Note: Postgres sorts NULL as the largest value unless told otherwise.
+        nulls_last.push(order.nulls_first.map_or(!desc, |first| !first));
+    }
+
+    let lf = plan.lf.sort_by_exprs(
+        by,
+        SortMultipleOptions::default()
+            .with_order_descending_multi(descending)
+            .with_nulls_last_multi(nulls_last)
+            .with_maintain_order(true),
+    );
+
+    Ok(Plan { lf, ..plan })
+}
+
+fn literal_count(expr: &ast::Expr) -> AppResult<u64> {
+    match expr {
+        ast::Expr::Value(Value::Number(number, _)) => number
+            .parse()
+            .map_err(|_| AppError::Transpile(format!("invalid row count `{number}`"))),
+        other => Err(unsupported(format!("row count `{other}`"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::transpiler::{parse_query, transpile};
+
+    fn tables() -> AppResult<Tables> {
+        let customers = df!(
+            "id" => [1, 2, 3],
+            "name" => ["Ann", "Bob", "Cid"],
+        )
+        .map_err(AppError::Polars)?;
+        let orders = df!(
+            "id" => [10, 11, 12, 13],
+            "customer_id" => [1, 1, 2, 9],
+            "amount" => [5, 7, 4, 1],
+        )
+        .map_err(AppError::Polars)?;
+
+        Ok(HashMap::from([
+            ("customers".to_string(), customers.lazy()),
+            ("orders".to_string(), orders.lazy()),
+        ]))
+    }
+
+    fn run(sql: &str) -> AppResult<DataFrame> {
+        transpile(&*parse_query(sql)?, &tables()?)?
+            .collect()
+            .map_err(AppError::Polars)
+    }
+
+    #[test]
+    fn joins_match_the_equi_keys() -> AppResult<()> {
+        let inner = run("SELECT c.name, o.amount FROM customers c \
+             JOIN orders o ON o.customer_id = c.id ORDER BY o.amount")?;
+        let expected = df!("name" => ["Bob", "Ann", "Ann"], "amount" => [4, 5, 7])
+            .map_err(AppError::Polars)?;
+        assert_eq!(inner, expected);
+
+        let left = run("SELECT c.name, o.id FROM customers c \
+             LEFT JOIN orders o ON c.id = o.customer_id ORDER BY c.name, o.id")?;
+        let expected = df!(
+            "name" => ["Ann", "Ann", "Bob", "Cid"],
+            "id" => [Some(10), Some(11), Some(12), None],
+        )
+        .map_err(AppError::Polars)?;
+        assert_eq!(left, expected);
+
+        assert!(run("SELECT id FROM customers c JOIN orders o ON o.customer_id = c.id").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn group_by_aggregates_each_key() -> AppResult<()> {
+        let df = run(
+            "SELECT customer_id, COUNT(*) AS orders, SUM(amount) AS total \
+             FROM orders GROUP BY customer_id HAVING COUNT(*) > 0 ORDER BY customer_id",
+        )?;
+        let expected = df!(
+            "customer_id" => [1, 2, 9],
+            "orders" => [2i64, 1, 1],
+            "total" => [12, 4, 1],
+        )
+        .map_err(AppError::Polars)?;
+        assert_eq!(df, expected);
+
+        assert!(run("SELECT customer_id, amount FROM orders GROUP BY customer_id").is_err());
+
+        Ok(())
+    }
+}
@@ -19,6 +19,9 @@ CREATE DATABASE salesdb;
 DROP SCHEMA IF EXISTS sales CASCADE;
 CREATE SCHEMA sales;
 
+-- Extensions (crosstab)
+CREATE EXTENSION IF NOT EXISTS tablefunc;
+
 -- ======================================================
 -- Table: customers
 -- ======================================================
@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use lib_progress::utils::bench::{CountingAllocator, run_benchmarks};
+use lib_progress::utils::export::{Format, write_df};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "usage: bench [--runs <n>] [--output <file>] [--dataset <name>] [lesson...]";
+
+// Note: a single thread runs the queries, so the allocations counted are the ones of the
+// query being timed.
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenvy::dotenv()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = args.iter().map(String::as_str);
+    let mut runs = 20;
+    let mut output = None;
+    let mut dataset = None;
+    let mut ids = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--runs" => {
+                runs = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|runs| *runs > 0)
+                    .ok_or(USAGE)?;
+            }
+            "--output" => {
+                let path = PathBuf::from(args.next().ok_or(USAGE)?);
+                let format = Format::from_path(&path)?;
+                output = Some((path, format));
+            }
+            "--dataset" => dataset = Some(args.next().ok_or(USAGE)?),
+            flag if flag.starts_with("--") => return Err(USAGE.into()),
+            id => ids.push(id),
+        }
+    }
+
+    let mut df = run_benchmarks(&ids, runs, dataset).await?;
+
+    if let Some((path, format)) = output {
+        write_df(&mut df, &path, format)?;
+    }
+
+    Ok(())
+}
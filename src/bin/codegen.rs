@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::path::Path;
+
+use lib_progress::utils::codegen::generate_code;
+
+const USAGE: &str = "usage: codegen [--public <dataset>] [--sales <dataset>]";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenvy::dotenv()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = args.iter().map(String::as_str);
+    let mut public = "MyDatabase";
+    let mut sales = "salesdb";
+
+    while let Some(arg) = args.next() {
+        match (arg, args.next()) {
+            ("--public", Some(value)) => public = value,
+            ("--sales", Some(value)) => sales = value,
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    // Note: the files are written in the workspace whatever the directory it runs from.
+    generate_code(Path::new(env!("CARGO_MANIFEST_DIR")), public, sales).await?;
+
+    Ok(())
+}
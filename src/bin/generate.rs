@@ -0,0 +1,35 @@
+use std::error::Error;
+
+use lib_progress::utils::generator::{Scale, generate_sales};
+
+const USAGE: &str = "usage: generate [--scale <factor>] [--seed <n>] [--dataset <name>]";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenvy::dotenv()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = args.iter().map(String::as_str);
+    let mut factor = 1.0;
+    let mut seed = 42;
+    let mut dataset = "salesdb_large";
+
+    while let Some(arg) = args.next() {
+        match (arg, args.next()) {
+            ("--scale", Some(value)) => {
+                factor = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|factor| *factor > 0.0)
+                    .ok_or(USAGE)?;
+            }
+            ("--seed", Some(value)) => seed = value.parse().map_err(|_| USAGE)?,
+            ("--dataset", Some(value)) => dataset = value,
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    generate_sales(dataset, Scale::new(factor), seed).await?;
+
+    Ok(())
+}
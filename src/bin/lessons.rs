@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use lib_progress::utils::exercise::display_exercise;
+use lib_progress::utils::export::{Output, Source, export_lesson};
+use lib_progress::utils::lint::display_lint;
+use lib_progress::utils::report::write_report;
+use lib_progress::utils::sql_context::display_sql_context;
+use lib_progress::utils::statements::display_statements;
+use lib_progress::utils::tracker::{display_lesson, display_progress};
+use lib_progress::utils::transpiler::display_corpus;
+
+const USAGE: &str = "\
+usage: lessons run <lesson> [--output <file>] [--source polars|sqlx|sea_orm]
+       lessons exercise <lesson> [file.sql]
+       lessons progress
+       lessons report [dir]
+       lessons corpus
+       lessons sql-context
+       lessons statements
+       lessons lint";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenvy::dotenv()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["run", id] => {
+            display_lesson(id).await?;
+        }
+        ["run", id, "--output", path] => {
+            let output = Output::new(path, Source::Polars)?;
+
+            export_lesson(id, &output).await?;
+        }
+        ["run", id, "--output", path, "--source", source]
+        | ["run", id, "--source", source, "--output", path] => {
+            let output = Output::new(path, Source::parse(source)?)?;
+
+            export_lesson(id, &output).await?;
+        }
+        ["exercise", id] => {
+            display_exercise(id, None).await?;
+        }
+        ["exercise", id, path] => {
+            display_exercise(id, Some(path)).await?;
+        }
+        ["progress"] => {
+            display_progress()?;
+        }
+        ["report"] => {
+            write_report("report").await?;
+        }
+        ["report", dir] => {
+            write_report(dir).await?;
+        }
+        ["corpus"] => {
+            display_corpus().await?;
+        }
+        ["sql-context"] => {
+            display_sql_context().await?;
+        }
+        ["statements"] => {
+            display_statements().await?;
+        }
+        ["lint"] => {
+            display_lint()?;
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::error::Error;
+
+use lib_progress::utils::repl::Repl;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenvy::dotenv()?;
+
+    let mut repl = Repl::new().await?;
+    repl.run().await?;
+
+    Ok(())
+}